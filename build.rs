@@ -16,7 +16,7 @@ fn main() {
     // Build the Go library as a static library
     let output = Command::new("go")
         .current_dir(&go_dir)
-        .args(&[
+        .args([
             "build",
             "-buildmode=c-archive",
             "-o",
@@ -1,8 +1,9 @@
 use serde_json::json;
 use std::env;
+use pulumist::cancellation::CancellationToken;
 use pulumist::dynamic::DynamicResource;
 use pulumist::engine::PulumiEngine;
-use pulumist::error::Result;
+use pulumist::error::{PulumistError, Result};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -80,9 +81,7 @@ async fn main() -> Result<()> {
         Ok(result) => {
             println!("✅ Preview completed");
             println!("   Resources will be created with proper references");
-            if let Some(summary) = result.get("summary") {
-                println!("   Summary: {}", serde_json::to_string_pretty(summary)?);
-            }
+            println!("   Changes: {}", serde_json::to_string_pretty(&result.resource_changes)?);
         }
         Err(e) => {
             println!("❌ Preview failed: {}", e);
@@ -98,10 +97,17 @@ async fn main() -> Result<()> {
     // Deploy the resources
     println!("\n🚀 Deploying resources with output references...\n");
 
+    // Ctrl-C stops the deployment cleanly instead of killing it mid-update
+    let cancellation = CancellationToken::new();
+    cancellation.cancel_on_ctrl_c();
+
     match stack.deploy()
         .with_resource(rg)
         .with_resource(storage)
         .with_resource(container)
+        .with_output("storageAccountName", "${demo-storage.accountName}")
+        .with_output("containerName", "${demo-container.containerName}")
+        .with_cancellation_token(cancellation)
         .execute()
         .await
     {
@@ -109,11 +115,17 @@ async fn main() -> Result<()> {
             println!("\n✅ Deployment completed successfully!");
             println!("   Output references were resolved automatically");
 
-            if let Some(outputs) = result.get("outputs") {
+            println!("   Took {:.1}s", result.duration.as_secs_f64());
+
+            if !result.outputs.is_empty() {
                 println!("\n📤 Stack Outputs:");
-                println!("{}", serde_json::to_string_pretty(outputs)?);
+                println!("{}", serde_json::to_string_pretty(&result.outputs)?);
             }
         }
+        Err(PulumistError::Cancelled) => {
+            println!("\n🛑 Deployment cancelled, completed steps were saved to the stack state");
+            return Err(PulumistError::Cancelled);
+        }
         Err(e) => {
             println!("\n❌ Deployment failed: {}", e);
             return Err(e);
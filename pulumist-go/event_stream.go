@@ -2,7 +2,7 @@ package main
 
 /*
 #include <stdlib.h>
-typedef void (*event_callback)(const char* event_json);
+typedef void (*event_callback)(const char* event_data);
 static void call_event_callback(event_callback cb, const char* event_data) {
     if (cb != NULL) {
         cb(event_data);
@@ -12,31 +12,36 @@ static void call_event_callback(event_callback cb, const char* event_data) {
 import "C"
 import (
 	pb "github.com/evorine/pulumist/pulumist-go/generated"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto/events"
+	"github.com/pulumi/pulumi/sdk/v3/go/common/apitype"
 	"google.golang.org/protobuf/proto"
+	"sync"
 	"unsafe"
 )
 
 var (
+	// callbackMu guards currentEventCallback, which is registered once by the host and shared by
+	// every in-flight operation. Events carry their operation ID so the host can route them.
+	callbackMu sync.RWMutex
 	// A global variable to store the callback function for sending events back to the host application.
-	// WARNING: This is not thread-safe and will cause data races if accessed concurrently.
-	// TODO: Improve this currentEventCallback global variable to be thread-safe.
 	currentEventCallback C.event_callback
 )
 
 // RegisterEventCallback registers a C function to receive event notifications during Pulumi operations.
-// Only one callback can be registered at a time.
+// Only one callback can be registered at a time; it receives the events of all operations.
 //
 // callback should be a C function pointer with signature void (*)(const char*). Pass NULL to clear the callback.
 //
-// The callback will receive length-prefixed protobuf-encoded Event messages.
-//
-// TODO: Thread safety: NOT thread-safe. We should protect with mutex.
+// The callback will receive length-prefixed protobuf-encoded Event messages, each tagged with the
+// operation_id of the PulumiRequest that produced it.
 //
 // Memory management:
 //   - Event data is freed by the sender after callback returns
 //
 //export RegisterEventCallback
 func RegisterEventCallback(callback C.event_callback) {
+	callbackMu.Lock()
+	defer callbackMu.Unlock()
 	currentEventCallback = callback
 }
 
@@ -44,23 +49,30 @@ func RegisterEventCallback(callback C.event_callback) {
 //
 //export UnregisterEventCallback
 func UnregisterEventCallback() {
+	callbackMu.Lock()
+	defer callbackMu.Unlock()
 	currentEventCallback = nil
 }
 
-// sendEvent serializes an event to protobuf and sends it to the registered host callback.
+// emitEvent tags an event with its operation ID, serializes it to protobuf and sends it to the registered host callback.
 // This enables real-time streaming of operation progress back to the host application.
 //
-// TODO: Thread safety: Accesses to global currentEventCallback might have race condition issues.
-//
 // Remarks:
-// - C.CString allocates memory that should be freed by C runtime after callback returns.
+// - C.CBytes allocates memory that is freed here after the callback returns.
+// - The callback may be invoked concurrently from several operations; the host must be thread-safe.
 // - If callback panics/throws in host, it could corrupt memory. But whatever, if the host panics, we can't recover anyway.
-func emitEvent(event *pb.Event) {
+func emitEvent(operationID uint64, event *pb.Event) {
+	callbackMu.RLock()
+	callback := currentEventCallback
+	callbackMu.RUnlock()
+
 	// Event callback registration is optional.
-	if currentEventCallback == nil {
+	if callback == nil {
 		return
 	}
 
+	event.OperationId = operationID
+
 	// Serialize event to protobuf
 	eventBytes, err := proto.Marshal(event)
 	// If serialization fails, currently we don't have a way to report this back to the host.
@@ -82,5 +94,104 @@ func emitEvent(event *pb.Event) {
 	defer C.free(unsafe.Pointer(cEventData))
 
 	// Call through CGo wrapper with the length
-	C.call_event_callback(currentEventCallback, cEventData)
+	C.call_event_callback(callback, cEventData)
+}
+
+// engineEventForwarder streams the engine events of a single stack operation to the host as they arrive.
+//
+// Pass forwarder.events to the operation's EventStreams option. The automation API closes the channel once
+// the operation returns; call wait before responding so the host receives every event before the response.
+type engineEventForwarder struct {
+	events chan events.EngineEvent
+	done   chan struct{}
+}
+
+func newEngineEventForwarder(operationID uint64) *engineEventForwarder {
+	forwarder := &engineEventForwarder{
+		events: make(chan events.EngineEvent),
+		done:   make(chan struct{}),
+	}
+
+	go func() {
+		defer close(forwarder.done)
+		for event := range forwarder.events {
+			if pbEvent := convertEngineEvent(event); pbEvent != nil {
+				emitEvent(operationID, pbEvent)
+			}
+		}
+	}()
+
+	return forwarder
+}
+
+// wait blocks until the remaining events have been forwarded. The automation API closes the events channel
+// when the operation returns, so this does not wait for the pulumi CLI itself and never drops trailing events.
+func (f *engineEventForwarder) wait() {
+	<-f.done
+}
+
+// convertEngineEvent translates an engine event into its protobuf form.
+// Returns nil for events the host has no message for, such as cancellation and standard output events.
+func convertEngineEvent(event events.EngineEvent) *pb.Event {
+	switch {
+	case event.PreludeEvent != nil:
+		return &pb.Event{Event: &pb.Event_Prelude{Prelude: &pb.PreludeEvent{
+			Config: event.PreludeEvent.Config,
+		}}}
+	case event.SummaryEvent != nil:
+		changes := make(map[string]int32, len(event.SummaryEvent.ResourceChanges))
+		for op, count := range event.SummaryEvent.ResourceChanges {
+			changes[string(op)] = int32(count)
+		}
+		return &pb.Event{Event: &pb.Event_Summary{Summary: &pb.SummaryEvent{
+			DurationSeconds: int32(event.SummaryEvent.DurationSeconds),
+			ResourceChanges: changes,
+		}}}
+	case event.ResourcePreEvent != nil:
+		return &pb.Event{Event: &pb.Event_ResourcePre{ResourcePre: &pb.ResourcePreEvent{
+			Metadata: convertStepMetadata(event.ResourcePreEvent.Metadata),
+			Planning: event.ResourcePreEvent.Planning,
+		}}}
+	case event.ResOutputsEvent != nil:
+		return &pb.Event{Event: &pb.Event_ResourceOutputs{ResourceOutputs: &pb.ResOutputsEvent{
+			Metadata: convertStepMetadata(event.ResOutputsEvent.Metadata),
+			Planning: event.ResOutputsEvent.Planning,
+		}}}
+	case event.ResOpFailedEvent != nil:
+		return &pb.Event{Event: &pb.Event_ResourceFailed{ResourceFailed: &pb.ResOpFailedEvent{
+			Metadata: convertStepMetadata(event.ResOpFailedEvent.Metadata),
+			Status:   int32(event.ResOpFailedEvent.Status),
+			Steps:    int32(event.ResOpFailedEvent.Steps),
+		}}}
+	case event.DiagnosticEvent != nil:
+		return &pb.Event{Event: &pb.Event_Diagnostic{Diagnostic: &pb.DiagnosticEvent{
+			Urn:      event.DiagnosticEvent.URN,
+			Prefix:   event.DiagnosticEvent.Prefix,
+			Message:  event.DiagnosticEvent.Message,
+			Color:    event.DiagnosticEvent.Color,
+			Severity: event.DiagnosticEvent.Severity,
+		}}}
+	case event.PolicyEvent != nil:
+		return &pb.Event{Event: &pb.Event_Policy{Policy: &pb.PolicyEvent{
+			ResourceUrn: event.PolicyEvent.ResourceURN,
+			Message:     event.PolicyEvent.Message,
+			Color:       event.PolicyEvent.Color,
+			PolicyConfig: []*pb.PolicyConfig{{
+				Name:    event.PolicyEvent.PolicyPackName,
+				Version: event.PolicyEvent.PolicyPackVersion,
+			}},
+		}}}
+	default:
+		return nil
+	}
+}
+
+func convertStepMetadata(metadata apitype.StepEventMetadata) *pb.ResourceMetadata {
+	return &pb.ResourceMetadata{
+		Op:   string(metadata.Op),
+		Urn:  metadata.URN,
+		Type: metadata.Type,
+		Old:  metadata.Old != nil,
+		New:  metadata.New != nil,
+	}
 }
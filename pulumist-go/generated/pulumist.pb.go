@@ -28,9 +28,24 @@ type PulumiRequest struct {
 	StackName   string                 `protobuf:"bytes,2,opt,name=stack_name,json=stackName,proto3" json:"stack_name,omitempty"`
 	ProjectName string                 `protobuf:"bytes,3,opt,name=project_name,json=projectName,proto3" json:"project_name,omitempty"`
 	Resources   []*Resource            `protobuf:"bytes,4,rep,name=resources,proto3" json:"resources,omitempty"`
-	Config      map[string]string      `protobuf:"bytes,5,rep,name=config,proto3" json:"config,omitempty" protobuf_key:"bytes,1,opt,name=key" protobuf_val:"bytes,2,opt,name=value"`
+	// Stack configuration keyed by path-style keys (e.g. "app:db.hosts[0]"), applied with SetAllConfig
+	Config map[string]*ConfigValue `protobuf:"bytes,5,rep,name=config,proto3" json:"config,omitempty" protobuf_key:"bytes,1,opt,name=key" protobuf_val:"bytes,2,opt,name=value"`
 	// Extended configuration options
-	PulumiConfig  *PulumiConfiguration `protobuf:"bytes,6,opt,name=pulumi_config,json=pulumiConfig,proto3" json:"pulumi_config,omitempty"`
+	PulumiConfig *PulumiConfiguration `protobuf:"bytes,6,opt,name=pulumi_config,json=pulumiConfig,proto3" json:"pulumi_config,omitempty"`
+	// Identifies the operation so events can be routed back to its caller
+	OperationId uint64 `protobuf:"varint,7,opt,name=operation_id,json=operationId,proto3" json:"operation_id,omitempty"`
+	// Deadline for the whole operation in seconds, 0 means no deadline
+	TimeoutSeconds uint32 `protobuf:"varint,8,opt,name=timeout_seconds,json=timeoutSeconds,proto3" json:"timeout_seconds,omitempty"`
+	// Refresh only: compute the changes without writing them to the stack state (--preview-only)
+	PreviewOnly bool `protobuf:"varint,9,opt,name=preview_only,json=previewOnly,proto3" json:"preview_only,omitempty"`
+	// Import only: existing cloud resources to adopt into the stack state
+	Imports []*ImportResource `protobuf:"bytes,10,rep,name=imports,proto3" json:"imports,omitempty"`
+	// State import only: JSON-encoded UntypedDeployment to write to the stack
+	Deployment []byte `protobuf:"bytes,11,opt,name=deployment,proto3" json:"deployment,omitempty"`
+	// Deploy and preview only: stack outputs exported by the program
+	Outputs []*StackOutput `protobuf:"bytes,12,rep,name=outputs,proto3" json:"outputs,omitempty"`
+	// Deploy and preview: provider functions called by the program. Invoke: the single function to call
+	Invokes       []*Invoke `protobuf:"bytes,13,rep,name=invokes,proto3" json:"invokes,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
@@ -93,7 +108,7 @@ func (x *PulumiRequest) GetResources() []*Resource {
 	return nil
 }
 
-func (x *PulumiRequest) GetConfig() map[string]string {
+func (x *PulumiRequest) GetConfig() map[string]*ConfigValue {
 	if x != nil {
 		return x.Config
 	}
@@ -107,6 +122,324 @@ func (x *PulumiRequest) GetPulumiConfig() *PulumiConfiguration {
 	return nil
 }
 
+func (x *PulumiRequest) GetOperationId() uint64 {
+	if x != nil {
+		return x.OperationId
+	}
+	return 0
+}
+
+func (x *PulumiRequest) GetTimeoutSeconds() uint32 {
+	if x != nil {
+		return x.TimeoutSeconds
+	}
+	return 0
+}
+
+func (x *PulumiRequest) GetPreviewOnly() bool {
+	if x != nil {
+		return x.PreviewOnly
+	}
+	return false
+}
+
+func (x *PulumiRequest) GetImports() []*ImportResource {
+	if x != nil {
+		return x.Imports
+	}
+	return nil
+}
+
+func (x *PulumiRequest) GetDeployment() []byte {
+	if x != nil {
+		return x.Deployment
+	}
+	return nil
+}
+
+func (x *PulumiRequest) GetOutputs() []*StackOutput {
+	if x != nil {
+		return x.Outputs
+	}
+	return nil
+}
+
+func (x *PulumiRequest) GetInvokes() []*Invoke {
+	if x != nil {
+		return x.Invokes
+	}
+	return nil
+}
+
+// A provider function call, e.g. "azure-native:authorization:getClientConfig".
+// Its result can be referenced like resource outputs as ${name.field}
+type Invoke struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	Name  string                 `protobuf:"bytes,1,opt,name=name,proto3" json:"name,omitempty"`
+	Token string                 `protobuf:"bytes,2,opt,name=token,proto3" json:"token,omitempty"`
+	// Literal arguments, they cannot reference resources
+	Args map[string]*Value `protobuf:"bytes,3,rep,name=args,proto3" json:"args,omitempty" protobuf_key:"bytes,1,opt,name=key" protobuf_val:"bytes,2,opt,name=value"`
+	// Name of the explicit provider resource to call the function with, empty for the default provider
+	Provider      string `protobuf:"bytes,4,opt,name=provider,proto3" json:"provider,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *Invoke) Reset() {
+	*x = Invoke{}
+	mi := &file_pulumist_proto_msgTypes[1]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *Invoke) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*Invoke) ProtoMessage() {}
+
+func (x *Invoke) ProtoReflect() protoreflect.Message {
+	mi := &file_pulumist_proto_msgTypes[1]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use Invoke.ProtoReflect.Descriptor instead.
+func (*Invoke) Descriptor() ([]byte, []int) {
+	return file_pulumist_proto_rawDescGZIP(), []int{1}
+}
+
+func (x *Invoke) GetName() string {
+	if x != nil {
+		return x.Name
+	}
+	return ""
+}
+
+func (x *Invoke) GetToken() string {
+	if x != nil {
+		return x.Token
+	}
+	return ""
+}
+
+func (x *Invoke) GetArgs() map[string]*Value {
+	if x != nil {
+		return x.Args
+	}
+	return nil
+}
+
+func (x *Invoke) GetProvider() string {
+	if x != nil {
+		return x.Provider
+	}
+	return ""
+}
+
+// A stack output exported by the program
+type StackOutput struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	Name  string                 `protobuf:"bytes,1,opt,name=name,proto3" json:"name,omitempty"`
+	// Literal value, strings may contain ${resource.property} references
+	Value *Value `protobuf:"bytes,2,opt,name=value,proto3" json:"value,omitempty"`
+	// Stored encrypted with the stack's secrets provider
+	Secret        bool `protobuf:"varint,3,opt,name=secret,proto3" json:"secret,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *StackOutput) Reset() {
+	*x = StackOutput{}
+	mi := &file_pulumist_proto_msgTypes[2]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *StackOutput) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*StackOutput) ProtoMessage() {}
+
+func (x *StackOutput) ProtoReflect() protoreflect.Message {
+	mi := &file_pulumist_proto_msgTypes[2]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use StackOutput.ProtoReflect.Descriptor instead.
+func (*StackOutput) Descriptor() ([]byte, []int) {
+	return file_pulumist_proto_rawDescGZIP(), []int{2}
+}
+
+func (x *StackOutput) GetName() string {
+	if x != nil {
+		return x.Name
+	}
+	return ""
+}
+
+func (x *StackOutput) GetValue() *Value {
+	if x != nil {
+		return x.Value
+	}
+	return nil
+}
+
+func (x *StackOutput) GetSecret() bool {
+	if x != nil {
+		return x.Secret
+	}
+	return false
+}
+
+// An existing cloud resource to import, as in Pulumi's import file format
+type ImportResource struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	Type  string                 `protobuf:"bytes,1,opt,name=type,proto3" json:"type,omitempty"`
+	Name  string                 `protobuf:"bytes,2,opt,name=name,proto3" json:"name,omitempty"`
+	// Provider-specific ID of the cloud resource
+	Id string `protobuf:"bytes,3,opt,name=id,proto3" json:"id,omitempty"`
+	// Name of the parent resource in the stack, if any
+	Parent string `protobuf:"bytes,4,opt,name=parent,proto3" json:"parent,omitempty"`
+	// Name of the provider resource in the stack, if not the default provider
+	Provider      string `protobuf:"bytes,5,opt,name=provider,proto3" json:"provider,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ImportResource) Reset() {
+	*x = ImportResource{}
+	mi := &file_pulumist_proto_msgTypes[3]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ImportResource) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ImportResource) ProtoMessage() {}
+
+func (x *ImportResource) ProtoReflect() protoreflect.Message {
+	mi := &file_pulumist_proto_msgTypes[3]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ImportResource.ProtoReflect.Descriptor instead.
+func (*ImportResource) Descriptor() ([]byte, []int) {
+	return file_pulumist_proto_rawDescGZIP(), []int{3}
+}
+
+func (x *ImportResource) GetType() string {
+	if x != nil {
+		return x.Type
+	}
+	return ""
+}
+
+func (x *ImportResource) GetName() string {
+	if x != nil {
+		return x.Name
+	}
+	return ""
+}
+
+func (x *ImportResource) GetId() string {
+	if x != nil {
+		return x.Id
+	}
+	return ""
+}
+
+func (x *ImportResource) GetParent() string {
+	if x != nil {
+		return x.Parent
+	}
+	return ""
+}
+
+func (x *ImportResource) GetProvider() string {
+	if x != nil {
+		return x.Provider
+	}
+	return ""
+}
+
+// A single stack configuration value
+type ConfigValue struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	Value string                 `protobuf:"bytes,1,opt,name=value,proto3" json:"value,omitempty"`
+	// Stored encrypted with the stack's secrets provider
+	Secret        bool `protobuf:"varint,2,opt,name=secret,proto3" json:"secret,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ConfigValue) Reset() {
+	*x = ConfigValue{}
+	mi := &file_pulumist_proto_msgTypes[4]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ConfigValue) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ConfigValue) ProtoMessage() {}
+
+func (x *ConfigValue) ProtoReflect() protoreflect.Message {
+	mi := &file_pulumist_proto_msgTypes[4]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ConfigValue.ProtoReflect.Descriptor instead.
+func (*ConfigValue) Descriptor() ([]byte, []int) {
+	return file_pulumist_proto_rawDescGZIP(), []int{4}
+}
+
+func (x *ConfigValue) GetValue() string {
+	if x != nil {
+		return x.Value
+	}
+	return ""
+}
+
+func (x *ConfigValue) GetSecret() bool {
+	if x != nil {
+		return x.Secret
+	}
+	return false
+}
+
 // Extended Pulumi configuration
 type PulumiConfiguration struct {
 	state protoimpl.MessageState `protogen:"open.v1"`
@@ -126,7 +459,7 @@ type PulumiConfiguration struct {
 
 func (x *PulumiConfiguration) Reset() {
 	*x = PulumiConfiguration{}
-	mi := &file_pulumist_proto_msgTypes[1]
+	mi := &file_pulumist_proto_msgTypes[5]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -138,7 +471,7 @@ func (x *PulumiConfiguration) String() string {
 func (*PulumiConfiguration) ProtoMessage() {}
 
 func (x *PulumiConfiguration) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[1]
+	mi := &file_pulumist_proto_msgTypes[5]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -151,7 +484,7 @@ func (x *PulumiConfiguration) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use PulumiConfiguration.ProtoReflect.Descriptor instead.
 func (*PulumiConfiguration) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{1}
+	return file_pulumist_proto_rawDescGZIP(), []int{5}
 }
 
 func (x *PulumiConfiguration) GetSecretsProvider() *SecretsProvider {
@@ -203,7 +536,7 @@ type SecretsProvider struct {
 
 func (x *SecretsProvider) Reset() {
 	*x = SecretsProvider{}
-	mi := &file_pulumist_proto_msgTypes[2]
+	mi := &file_pulumist_proto_msgTypes[6]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -215,7 +548,7 @@ func (x *SecretsProvider) String() string {
 func (*SecretsProvider) ProtoMessage() {}
 
 func (x *SecretsProvider) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[2]
+	mi := &file_pulumist_proto_msgTypes[6]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -228,7 +561,7 @@ func (x *SecretsProvider) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use SecretsProvider.ProtoReflect.Descriptor instead.
 func (*SecretsProvider) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{2}
+	return file_pulumist_proto_rawDescGZIP(), []int{6}
 }
 
 func (x *SecretsProvider) GetProvider() isSecretsProvider_Provider {
@@ -296,7 +629,7 @@ type PassphraseProvider struct {
 
 func (x *PassphraseProvider) Reset() {
 	*x = PassphraseProvider{}
-	mi := &file_pulumist_proto_msgTypes[3]
+	mi := &file_pulumist_proto_msgTypes[7]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -308,7 +641,7 @@ func (x *PassphraseProvider) String() string {
 func (*PassphraseProvider) ProtoMessage() {}
 
 func (x *PassphraseProvider) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[3]
+	mi := &file_pulumist_proto_msgTypes[7]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -321,7 +654,7 @@ func (x *PassphraseProvider) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use PassphraseProvider.ProtoReflect.Descriptor instead.
 func (*PassphraseProvider) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{3}
+	return file_pulumist_proto_rawDescGZIP(), []int{7}
 }
 
 func (x *PassphraseProvider) GetPassphrase() string {
@@ -342,7 +675,7 @@ type CloudKmsProvider struct {
 
 func (x *CloudKmsProvider) Reset() {
 	*x = CloudKmsProvider{}
-	mi := &file_pulumist_proto_msgTypes[4]
+	mi := &file_pulumist_proto_msgTypes[8]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -354,7 +687,7 @@ func (x *CloudKmsProvider) String() string {
 func (*CloudKmsProvider) ProtoMessage() {}
 
 func (x *CloudKmsProvider) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[4]
+	mi := &file_pulumist_proto_msgTypes[8]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -367,7 +700,7 @@ func (x *CloudKmsProvider) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use CloudKmsProvider.ProtoReflect.Descriptor instead.
 func (*CloudKmsProvider) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{4}
+	return file_pulumist_proto_rawDescGZIP(), []int{8}
 }
 
 func (x *CloudKmsProvider) GetProviderType() string {
@@ -399,7 +732,7 @@ type LocalProvider struct {
 
 func (x *LocalProvider) Reset() {
 	*x = LocalProvider{}
-	mi := &file_pulumist_proto_msgTypes[5]
+	mi := &file_pulumist_proto_msgTypes[9]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -411,7 +744,7 @@ func (x *LocalProvider) String() string {
 func (*LocalProvider) ProtoMessage() {}
 
 func (x *LocalProvider) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[5]
+	mi := &file_pulumist_proto_msgTypes[9]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -424,7 +757,7 @@ func (x *LocalProvider) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use LocalProvider.ProtoReflect.Descriptor instead.
 func (*LocalProvider) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{5}
+	return file_pulumist_proto_rawDescGZIP(), []int{9}
 }
 
 type BackendConfig struct {
@@ -442,7 +775,7 @@ type BackendConfig struct {
 
 func (x *BackendConfig) Reset() {
 	*x = BackendConfig{}
-	mi := &file_pulumist_proto_msgTypes[6]
+	mi := &file_pulumist_proto_msgTypes[10]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -454,7 +787,7 @@ func (x *BackendConfig) String() string {
 func (*BackendConfig) ProtoMessage() {}
 
 func (x *BackendConfig) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[6]
+	mi := &file_pulumist_proto_msgTypes[10]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -467,7 +800,7 @@ func (x *BackendConfig) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use BackendConfig.ProtoReflect.Descriptor instead.
 func (*BackendConfig) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{6}
+	return file_pulumist_proto_rawDescGZIP(), []int{10}
 }
 
 func (x *BackendConfig) GetBackend() isBackendConfig_Backend {
@@ -550,7 +883,7 @@ type LocalBackend struct {
 
 func (x *LocalBackend) Reset() {
 	*x = LocalBackend{}
-	mi := &file_pulumist_proto_msgTypes[7]
+	mi := &file_pulumist_proto_msgTypes[11]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -562,7 +895,7 @@ func (x *LocalBackend) String() string {
 func (*LocalBackend) ProtoMessage() {}
 
 func (x *LocalBackend) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[7]
+	mi := &file_pulumist_proto_msgTypes[11]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -575,7 +908,7 @@ func (x *LocalBackend) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use LocalBackend.ProtoReflect.Descriptor instead.
 func (*LocalBackend) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{7}
+	return file_pulumist_proto_rawDescGZIP(), []int{11}
 }
 
 func (x *LocalBackend) GetPath() string {
@@ -595,7 +928,7 @@ type CloudBackend struct {
 
 func (x *CloudBackend) Reset() {
 	*x = CloudBackend{}
-	mi := &file_pulumist_proto_msgTypes[8]
+	mi := &file_pulumist_proto_msgTypes[12]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -607,7 +940,7 @@ func (x *CloudBackend) String() string {
 func (*CloudBackend) ProtoMessage() {}
 
 func (x *CloudBackend) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[8]
+	mi := &file_pulumist_proto_msgTypes[12]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -620,7 +953,7 @@ func (x *CloudBackend) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use CloudBackend.ProtoReflect.Descriptor instead.
 func (*CloudBackend) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{8}
+	return file_pulumist_proto_rawDescGZIP(), []int{12}
 }
 
 func (x *CloudBackend) GetUrl() string {
@@ -638,19 +971,21 @@ func (x *CloudBackend) GetApiToken() string {
 }
 
 type S3Backend struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	Bucket        string                 `protobuf:"bytes,1,opt,name=bucket,proto3" json:"bucket,omitempty"`
-	Region        string                 `protobuf:"bytes,2,opt,name=region,proto3" json:"region,omitempty"`
-	AccessKey     string                 `protobuf:"bytes,3,opt,name=access_key,json=accessKey,proto3" json:"access_key,omitempty"`
-	SecretKey     string                 `protobuf:"bytes,4,opt,name=secret_key,json=secretKey,proto3" json:"secret_key,omitempty"`
-	SessionToken  string                 `protobuf:"bytes,5,opt,name=session_token,json=sessionToken,proto3" json:"session_token,omitempty"`
+	state        protoimpl.MessageState `protogen:"open.v1"`
+	Bucket       string                 `protobuf:"bytes,1,opt,name=bucket,proto3" json:"bucket,omitempty"`
+	Region       string                 `protobuf:"bytes,2,opt,name=region,proto3" json:"region,omitempty"`
+	AccessKey    string                 `protobuf:"bytes,3,opt,name=access_key,json=accessKey,proto3" json:"access_key,omitempty"`
+	SecretKey    string                 `protobuf:"bytes,4,opt,name=secret_key,json=secretKey,proto3" json:"secret_key,omitempty"`
+	SessionToken string                 `protobuf:"bytes,5,opt,name=session_token,json=sessionToken,proto3" json:"session_token,omitempty"`
+	// Custom endpoint for S3-compatible servers such as MinIO, e.g. "http://localhost:9000"
+	Endpoint      string `protobuf:"bytes,6,opt,name=endpoint,proto3" json:"endpoint,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
 func (x *S3Backend) Reset() {
 	*x = S3Backend{}
-	mi := &file_pulumist_proto_msgTypes[9]
+	mi := &file_pulumist_proto_msgTypes[13]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -662,7 +997,7 @@ func (x *S3Backend) String() string {
 func (*S3Backend) ProtoMessage() {}
 
 func (x *S3Backend) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[9]
+	mi := &file_pulumist_proto_msgTypes[13]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -675,7 +1010,7 @@ func (x *S3Backend) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use S3Backend.ProtoReflect.Descriptor instead.
 func (*S3Backend) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{9}
+	return file_pulumist_proto_rawDescGZIP(), []int{13}
 }
 
 func (x *S3Backend) GetBucket() string {
@@ -713,6 +1048,13 @@ func (x *S3Backend) GetSessionToken() string {
 	return ""
 }
 
+func (x *S3Backend) GetEndpoint() string {
+	if x != nil {
+		return x.Endpoint
+	}
+	return ""
+}
+
 type AzureBlobBackend struct {
 	state          protoimpl.MessageState `protogen:"open.v1"`
 	StorageAccount string                 `protobuf:"bytes,1,opt,name=storage_account,json=storageAccount,proto3" json:"storage_account,omitempty"`
@@ -725,7 +1067,7 @@ type AzureBlobBackend struct {
 
 func (x *AzureBlobBackend) Reset() {
 	*x = AzureBlobBackend{}
-	mi := &file_pulumist_proto_msgTypes[10]
+	mi := &file_pulumist_proto_msgTypes[14]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -737,7 +1079,7 @@ func (x *AzureBlobBackend) String() string {
 func (*AzureBlobBackend) ProtoMessage() {}
 
 func (x *AzureBlobBackend) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[10]
+	mi := &file_pulumist_proto_msgTypes[14]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -750,7 +1092,7 @@ func (x *AzureBlobBackend) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use AzureBlobBackend.ProtoReflect.Descriptor instead.
 func (*AzureBlobBackend) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{10}
+	return file_pulumist_proto_rawDescGZIP(), []int{14}
 }
 
 func (x *AzureBlobBackend) GetStorageAccount() string {
@@ -783,19 +1125,40 @@ func (x *AzureBlobBackend) GetSasToken() string {
 
 // Resource definition
 type Resource struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	Type          string                 `protobuf:"bytes,1,opt,name=type,proto3" json:"type,omitempty"`
-	Name          string                 `protobuf:"bytes,2,opt,name=name,proto3" json:"name,omitempty"`
-	Properties    map[string]*Value      `protobuf:"bytes,3,rep,name=properties,proto3" json:"properties,omitempty" protobuf_key:"bytes,1,opt,name=key" protobuf_val:"bytes,2,opt,name=value"`
-	DependsOn     []string               `protobuf:"bytes,4,rep,name=depends_on,json=dependsOn,proto3" json:"depends_on,omitempty"`
-	Provider      string                 `protobuf:"bytes,5,opt,name=provider,proto3" json:"provider,omitempty"`
+	state      protoimpl.MessageState `protogen:"open.v1"`
+	Type       string                 `protobuf:"bytes,1,opt,name=type,proto3" json:"type,omitempty"`
+	Name       string                 `protobuf:"bytes,2,opt,name=name,proto3" json:"name,omitempty"`
+	Properties map[string]*Value      `protobuf:"bytes,3,rep,name=properties,proto3" json:"properties,omitempty" protobuf_key:"bytes,1,opt,name=key" protobuf_val:"bytes,2,opt,name=value"`
+	DependsOn  []string               `protobuf:"bytes,4,rep,name=depends_on,json=dependsOn,proto3" json:"depends_on,omitempty"`
+	// Name of an explicit provider resource (type pulumi:providers:<pkg>) in the same program
+	Provider       string          `protobuf:"bytes,5,opt,name=provider,proto3" json:"provider,omitempty"`
+	CustomTimeouts *CustomTimeouts `protobuf:"bytes,6,opt,name=custom_timeouts,json=customTimeouts,proto3" json:"custom_timeouts,omitempty"`
+	// Name of the parent resource in the same program
+	Parent string `protobuf:"bytes,7,opt,name=parent,proto3" json:"parent,omitempty"`
+	// Lifecycle options, see the matching pulumi.ResourceOption
+	Protect                 bool     `protobuf:"varint,8,opt,name=protect,proto3" json:"protect,omitempty"`
+	IgnoreChanges           []string `protobuf:"bytes,9,rep,name=ignore_changes,json=ignoreChanges,proto3" json:"ignore_changes,omitempty"`
+	RetainOnDelete          bool     `protobuf:"varint,10,opt,name=retain_on_delete,json=retainOnDelete,proto3" json:"retain_on_delete,omitempty"`
+	ReplaceOnChanges        []string `protobuf:"bytes,11,rep,name=replace_on_changes,json=replaceOnChanges,proto3" json:"replace_on_changes,omitempty"`
+	DeleteBeforeReplace     bool     `protobuf:"varint,12,opt,name=delete_before_replace,json=deleteBeforeReplace,proto3" json:"delete_before_replace,omitempty"`
+	AdditionalSecretOutputs []string `protobuf:"bytes,13,rep,name=additional_secret_outputs,json=additionalSecretOutputs,proto3" json:"additional_secret_outputs,omitempty"`
+	// Name of a resource in the same program whose deletion also deletes this resource
+	DeletedWith string `protobuf:"bytes,14,opt,name=deleted_with,json=deletedWith,proto3" json:"deleted_with,omitempty"`
+	// Previous identities of the resource, so renames and moves update it instead of replacing it
+	Aliases []*Alias `protobuf:"bytes,15,rep,name=aliases,proto3" json:"aliases,omitempty"`
+	// Registered as a component resource that groups its children (resources with this resource as parent)
+	Component bool `protobuf:"varint,16,opt,name=component,proto3" json:"component,omitempty"`
+	// Component only: outputs registered once all resources are registered, may reference any resource
+	ComponentOutputs map[string]*Value `protobuf:"bytes,17,rep,name=component_outputs,json=componentOutputs,proto3" json:"component_outputs,omitempty" protobuf_key:"bytes,1,opt,name=key" protobuf_val:"bytes,2,opt,name=value"`
+	// Provider-specific ID of an existing resource to read instead of managing it (ctx.ReadResource)
+	Id            string `protobuf:"bytes,18,opt,name=id,proto3" json:"id,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
 func (x *Resource) Reset() {
 	*x = Resource{}
-	mi := &file_pulumist_proto_msgTypes[11]
+	mi := &file_pulumist_proto_msgTypes[15]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -807,7 +1170,7 @@ func (x *Resource) String() string {
 func (*Resource) ProtoMessage() {}
 
 func (x *Resource) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[11]
+	mi := &file_pulumist_proto_msgTypes[15]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -820,7 +1183,7 @@ func (x *Resource) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use Resource.ProtoReflect.Descriptor instead.
 func (*Resource) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{11}
+	return file_pulumist_proto_rawDescGZIP(), []int{15}
 }
 
 func (x *Resource) GetType() string {
@@ -858,6 +1221,263 @@ func (x *Resource) GetProvider() string {
 	return ""
 }
 
+func (x *Resource) GetCustomTimeouts() *CustomTimeouts {
+	if x != nil {
+		return x.CustomTimeouts
+	}
+	return nil
+}
+
+func (x *Resource) GetParent() string {
+	if x != nil {
+		return x.Parent
+	}
+	return ""
+}
+
+func (x *Resource) GetProtect() bool {
+	if x != nil {
+		return x.Protect
+	}
+	return false
+}
+
+func (x *Resource) GetIgnoreChanges() []string {
+	if x != nil {
+		return x.IgnoreChanges
+	}
+	return nil
+}
+
+func (x *Resource) GetRetainOnDelete() bool {
+	if x != nil {
+		return x.RetainOnDelete
+	}
+	return false
+}
+
+func (x *Resource) GetReplaceOnChanges() []string {
+	if x != nil {
+		return x.ReplaceOnChanges
+	}
+	return nil
+}
+
+func (x *Resource) GetDeleteBeforeReplace() bool {
+	if x != nil {
+		return x.DeleteBeforeReplace
+	}
+	return false
+}
+
+func (x *Resource) GetAdditionalSecretOutputs() []string {
+	if x != nil {
+		return x.AdditionalSecretOutputs
+	}
+	return nil
+}
+
+func (x *Resource) GetDeletedWith() string {
+	if x != nil {
+		return x.DeletedWith
+	}
+	return ""
+}
+
+func (x *Resource) GetAliases() []*Alias {
+	if x != nil {
+		return x.Aliases
+	}
+	return nil
+}
+
+func (x *Resource) GetComponent() bool {
+	if x != nil {
+		return x.Component
+	}
+	return false
+}
+
+func (x *Resource) GetComponentOutputs() map[string]*Value {
+	if x != nil {
+		return x.ComponentOutputs
+	}
+	return nil
+}
+
+func (x *Resource) GetId() string {
+	if x != nil {
+		return x.Id
+	}
+	return ""
+}
+
+// A previous identity of a resource, empty fields default to the resource's current values
+type Alias struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	Name  string                 `protobuf:"bytes,1,opt,name=name,proto3" json:"name,omitempty"`
+	Type  string                 `protobuf:"bytes,2,opt,name=type,proto3" json:"type,omitempty"`
+	// Name of the previous parent resource in the same program
+	Parent string `protobuf:"bytes,3,opt,name=parent,proto3" json:"parent,omitempty"`
+	// URN of the previous parent resource, for parents that are no longer in the program
+	ParentUrn string `protobuf:"bytes,4,opt,name=parent_urn,json=parentUrn,proto3" json:"parent_urn,omitempty"`
+	// The resource previously had no parent
+	NoParent bool   `protobuf:"varint,5,opt,name=no_parent,json=noParent,proto3" json:"no_parent,omitempty"`
+	Stack    string `protobuf:"bytes,6,opt,name=stack,proto3" json:"stack,omitempty"`
+	Project  string `protobuf:"bytes,7,opt,name=project,proto3" json:"project,omitempty"`
+	// Full URN of the previous resource, other fields are ignored when set
+	Urn           string `protobuf:"bytes,8,opt,name=urn,proto3" json:"urn,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *Alias) Reset() {
+	*x = Alias{}
+	mi := &file_pulumist_proto_msgTypes[16]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *Alias) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*Alias) ProtoMessage() {}
+
+func (x *Alias) ProtoReflect() protoreflect.Message {
+	mi := &file_pulumist_proto_msgTypes[16]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use Alias.ProtoReflect.Descriptor instead.
+func (*Alias) Descriptor() ([]byte, []int) {
+	return file_pulumist_proto_rawDescGZIP(), []int{16}
+}
+
+func (x *Alias) GetName() string {
+	if x != nil {
+		return x.Name
+	}
+	return ""
+}
+
+func (x *Alias) GetType() string {
+	if x != nil {
+		return x.Type
+	}
+	return ""
+}
+
+func (x *Alias) GetParent() string {
+	if x != nil {
+		return x.Parent
+	}
+	return ""
+}
+
+func (x *Alias) GetParentUrn() string {
+	if x != nil {
+		return x.ParentUrn
+	}
+	return ""
+}
+
+func (x *Alias) GetNoParent() bool {
+	if x != nil {
+		return x.NoParent
+	}
+	return false
+}
+
+func (x *Alias) GetStack() string {
+	if x != nil {
+		return x.Stack
+	}
+	return ""
+}
+
+func (x *Alias) GetProject() string {
+	if x != nil {
+		return x.Project
+	}
+	return ""
+}
+
+func (x *Alias) GetUrn() string {
+	if x != nil {
+		return x.Urn
+	}
+	return ""
+}
+
+// Per-resource timeouts as Pulumi duration strings (e.g. "10m", "1h30m"), empty means provider default
+type CustomTimeouts struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Create        string                 `protobuf:"bytes,1,opt,name=create,proto3" json:"create,omitempty"`
+	Update        string                 `protobuf:"bytes,2,opt,name=update,proto3" json:"update,omitempty"`
+	Delete        string                 `protobuf:"bytes,3,opt,name=delete,proto3" json:"delete,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *CustomTimeouts) Reset() {
+	*x = CustomTimeouts{}
+	mi := &file_pulumist_proto_msgTypes[17]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *CustomTimeouts) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*CustomTimeouts) ProtoMessage() {}
+
+func (x *CustomTimeouts) ProtoReflect() protoreflect.Message {
+	mi := &file_pulumist_proto_msgTypes[17]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use CustomTimeouts.ProtoReflect.Descriptor instead.
+func (*CustomTimeouts) Descriptor() ([]byte, []int) {
+	return file_pulumist_proto_rawDescGZIP(), []int{17}
+}
+
+func (x *CustomTimeouts) GetCreate() string {
+	if x != nil {
+		return x.Create
+	}
+	return ""
+}
+
+func (x *CustomTimeouts) GetUpdate() string {
+	if x != nil {
+		return x.Update
+	}
+	return ""
+}
+
+func (x *CustomTimeouts) GetDelete() string {
+	if x != nil {
+		return x.Delete
+	}
+	return ""
+}
+
 // Generic value type that can hold different data types
 type Value struct {
 	state protoimpl.MessageState `protogen:"open.v1"`
@@ -877,7 +1497,7 @@ type Value struct {
 
 func (x *Value) Reset() {
 	*x = Value{}
-	mi := &file_pulumist_proto_msgTypes[12]
+	mi := &file_pulumist_proto_msgTypes[18]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -889,7 +1509,7 @@ func (x *Value) String() string {
 func (*Value) ProtoMessage() {}
 
 func (x *Value) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[12]
+	mi := &file_pulumist_proto_msgTypes[18]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -902,7 +1522,7 @@ func (x *Value) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use Value.ProtoReflect.Descriptor instead.
 func (*Value) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{12}
+	return file_pulumist_proto_rawDescGZIP(), []int{18}
 }
 
 func (x *Value) GetValue() isValue_Value {
@@ -948,101 +1568,521 @@ func (x *Value) GetBoolValue() bool {
 	return false
 }
 
-func (x *Value) GetListValue() *ValueList {
+func (x *Value) GetListValue() *ValueList {
+	if x != nil {
+		if x, ok := x.Value.(*Value_ListValue); ok {
+			return x.ListValue
+		}
+	}
+	return nil
+}
+
+func (x *Value) GetMapValue() *ValueMap {
+	if x != nil {
+		if x, ok := x.Value.(*Value_MapValue); ok {
+			return x.MapValue
+		}
+	}
+	return nil
+}
+
+func (x *Value) GetBytesValue() []byte {
+	if x != nil {
+		if x, ok := x.Value.(*Value_BytesValue); ok {
+			return x.BytesValue
+		}
+	}
+	return nil
+}
+
+type isValue_Value interface {
+	isValue_Value()
+}
+
+type Value_StringValue struct {
+	StringValue string `protobuf:"bytes,1,opt,name=string_value,json=stringValue,proto3,oneof"`
+}
+
+type Value_IntValue struct {
+	IntValue int64 `protobuf:"varint,2,opt,name=int_value,json=intValue,proto3,oneof"`
+}
+
+type Value_DoubleValue struct {
+	DoubleValue float64 `protobuf:"fixed64,3,opt,name=double_value,json=doubleValue,proto3,oneof"`
+}
+
+type Value_BoolValue struct {
+	BoolValue bool `protobuf:"varint,4,opt,name=bool_value,json=boolValue,proto3,oneof"`
+}
+
+type Value_ListValue struct {
+	ListValue *ValueList `protobuf:"bytes,5,opt,name=list_value,json=listValue,proto3,oneof"`
+}
+
+type Value_MapValue struct {
+	MapValue *ValueMap `protobuf:"bytes,6,opt,name=map_value,json=mapValue,proto3,oneof"`
+}
+
+type Value_BytesValue struct {
+	BytesValue []byte `protobuf:"bytes,7,opt,name=bytes_value,json=bytesValue,proto3,oneof"`
+}
+
+func (*Value_StringValue) isValue_Value() {}
+
+func (*Value_IntValue) isValue_Value() {}
+
+func (*Value_DoubleValue) isValue_Value() {}
+
+func (*Value_BoolValue) isValue_Value() {}
+
+func (*Value_ListValue) isValue_Value() {}
+
+func (*Value_MapValue) isValue_Value() {}
+
+func (*Value_BytesValue) isValue_Value() {}
+
+type ValueList struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Values        []*Value               `protobuf:"bytes,1,rep,name=values,proto3" json:"values,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ValueList) Reset() {
+	*x = ValueList{}
+	mi := &file_pulumist_proto_msgTypes[19]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ValueList) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ValueList) ProtoMessage() {}
+
+func (x *ValueList) ProtoReflect() protoreflect.Message {
+	mi := &file_pulumist_proto_msgTypes[19]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ValueList.ProtoReflect.Descriptor instead.
+func (*ValueList) Descriptor() ([]byte, []int) {
+	return file_pulumist_proto_rawDescGZIP(), []int{19}
+}
+
+func (x *ValueList) GetValues() []*Value {
+	if x != nil {
+		return x.Values
+	}
+	return nil
+}
+
+type ValueMap struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Fields        map[string]*Value      `protobuf:"bytes,1,rep,name=fields,proto3" json:"fields,omitempty" protobuf_key:"bytes,1,opt,name=key" protobuf_val:"bytes,2,opt,name=value"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ValueMap) Reset() {
+	*x = ValueMap{}
+	mi := &file_pulumist_proto_msgTypes[20]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ValueMap) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ValueMap) ProtoMessage() {}
+
+func (x *ValueMap) ProtoReflect() protoreflect.Message {
+	mi := &file_pulumist_proto_msgTypes[20]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ValueMap.ProtoReflect.Descriptor instead.
+func (*ValueMap) Descriptor() ([]byte, []int) {
+	return file_pulumist_proto_rawDescGZIP(), []int{20}
+}
+
+func (x *ValueMap) GetFields() map[string]*Value {
+	if x != nil {
+		return x.Fields
+	}
+	return nil
+}
+
+// Response message for Pulumi operations
+type PulumiResponse struct {
+	state   protoimpl.MessageState `protogen:"open.v1"`
+	Success bool                   `protobuf:"varint,1,opt,name=success,proto3" json:"success,omitempty"`
+	Error   string                 `protobuf:"bytes,2,opt,name=error,proto3" json:"error,omitempty"`
+	// State export only: JSON-encoded UntypedDeployment of the stack
+	Data    []byte        `protobuf:"bytes,3,opt,name=data,proto3" json:"data,omitempty"`
+	Outputs []*OutputItem `protobuf:"bytes,4,rep,name=outputs,proto3" json:"outputs,omitempty"`
+	// Set when the operation stopped because PulumiDynamicCancel was called for it
+	Cancelled bool `protobuf:"varint,5,opt,name=cancelled,proto3" json:"cancelled,omitempty"`
+	// Set when the operation stopped because PulumiRequest.timeout_seconds elapsed
+	TimedOut bool `protobuf:"varint,6,opt,name=timed_out,json=timedOut,proto3" json:"timed_out,omitempty"`
+	// URNs of resources whose steps had started but not finished when the operation timed out
+	PendingResources []string `protobuf:"bytes,7,rep,name=pending_resources,json=pendingResources,proto3" json:"pending_resources,omitempty"`
+	// Result of a successful preview, up, destroy, refresh, import or invoke
+	Result        *OperationResult `protobuf:"bytes,8,opt,name=result,proto3" json:"result,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *PulumiResponse) Reset() {
+	*x = PulumiResponse{}
+	mi := &file_pulumist_proto_msgTypes[21]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *PulumiResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*PulumiResponse) ProtoMessage() {}
+
+func (x *PulumiResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_pulumist_proto_msgTypes[21]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use PulumiResponse.ProtoReflect.Descriptor instead.
+func (*PulumiResponse) Descriptor() ([]byte, []int) {
+	return file_pulumist_proto_rawDescGZIP(), []int{21}
+}
+
+func (x *PulumiResponse) GetSuccess() bool {
+	if x != nil {
+		return x.Success
+	}
+	return false
+}
+
+func (x *PulumiResponse) GetError() string {
+	if x != nil {
+		return x.Error
+	}
+	return ""
+}
+
+func (x *PulumiResponse) GetData() []byte {
+	if x != nil {
+		return x.Data
+	}
+	return nil
+}
+
+func (x *PulumiResponse) GetOutputs() []*OutputItem {
+	if x != nil {
+		return x.Outputs
+	}
+	return nil
+}
+
+func (x *PulumiResponse) GetCancelled() bool {
+	if x != nil {
+		return x.Cancelled
+	}
+	return false
+}
+
+func (x *PulumiResponse) GetTimedOut() bool {
+	if x != nil {
+		return x.TimedOut
+	}
+	return false
+}
+
+func (x *PulumiResponse) GetPendingResources() []string {
+	if x != nil {
+		return x.PendingResources
+	}
+	return nil
+}
+
+func (x *PulumiResponse) GetResult() *OperationResult {
+	if x != nil {
+		return x.Result
+	}
+	return nil
+}
+
+type OperationResult struct {
+	state  protoimpl.MessageState `protogen:"open.v1"`
+	Stdout string                 `protobuf:"bytes,1,opt,name=stdout,proto3" json:"stdout,omitempty"`
+	Stderr string                 `protobuf:"bytes,2,opt,name=stderr,proto3" json:"stderr,omitempty"`
+	// Number of resources per operation kind, e.g. "create" or "same"
+	ResourceChanges map[string]int32 `protobuf:"bytes,3,rep,name=resource_changes,json=resourceChanges,proto3" json:"resource_changes,omitempty" protobuf_key:"bytes,1,opt,name=key" protobuf_val:"varint,2,opt,name=value"`
+	// Up: stack outputs after the update. Invoke: the result of the function
+	Outputs map[string]*Value `protobuf:"bytes,4,rep,name=outputs,proto3" json:"outputs,omitempty" protobuf_key:"bytes,1,opt,name=key" protobuf_val:"bytes,2,opt,name=value"`
+	// Update status recorded by the backend, e.g. "succeeded"; empty for previews
+	Status          string  `protobuf:"bytes,5,opt,name=status,proto3" json:"status,omitempty"`
+	Message         string  `protobuf:"bytes,6,opt,name=message,proto3" json:"message,omitempty"`
+	DurationSeconds float64 `protobuf:"fixed64,7,opt,name=duration_seconds,json=durationSeconds,proto3" json:"duration_seconds,omitempty"`
+	// Refresh only: URNs of the resources whose state was, or would be, updated or deleted
+	Updated []string `protobuf:"bytes,8,rep,name=updated,proto3" json:"updated,omitempty"`
+	Deleted []string `protobuf:"bytes,9,rep,name=deleted,proto3" json:"deleted,omitempty"`
+	// Up only: the state of the requested resources after the update
+	Resources []*ResourceState `protobuf:"bytes,10,rep,name=resources,proto3" json:"resources,omitempty"`
+	// Import only: the resources adopted into the stack state
+	Imported []*ImportedResource `protobuf:"bytes,11,rep,name=imported,proto3" json:"imported,omitempty"`
+	// Import only: the resources that could not be imported; they were left out of the stack state
+	Failures      []*ImportFailure `protobuf:"bytes,12,rep,name=failures,proto3" json:"failures,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *OperationResult) Reset() {
+	*x = OperationResult{}
+	mi := &file_pulumist_proto_msgTypes[22]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *OperationResult) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*OperationResult) ProtoMessage() {}
+
+func (x *OperationResult) ProtoReflect() protoreflect.Message {
+	mi := &file_pulumist_proto_msgTypes[22]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use OperationResult.ProtoReflect.Descriptor instead.
+func (*OperationResult) Descriptor() ([]byte, []int) {
+	return file_pulumist_proto_rawDescGZIP(), []int{22}
+}
+
+func (x *OperationResult) GetStdout() string {
+	if x != nil {
+		return x.Stdout
+	}
+	return ""
+}
+
+func (x *OperationResult) GetStderr() string {
+	if x != nil {
+		return x.Stderr
+	}
+	return ""
+}
+
+func (x *OperationResult) GetResourceChanges() map[string]int32 {
+	if x != nil {
+		return x.ResourceChanges
+	}
+	return nil
+}
+
+func (x *OperationResult) GetOutputs() map[string]*Value {
+	if x != nil {
+		return x.Outputs
+	}
+	return nil
+}
+
+func (x *OperationResult) GetStatus() string {
+	if x != nil {
+		return x.Status
+	}
+	return ""
+}
+
+func (x *OperationResult) GetMessage() string {
+	if x != nil {
+		return x.Message
+	}
+	return ""
+}
+
+func (x *OperationResult) GetDurationSeconds() float64 {
+	if x != nil {
+		return x.DurationSeconds
+	}
+	return 0
+}
+
+func (x *OperationResult) GetUpdated() []string {
+	if x != nil {
+		return x.Updated
+	}
+	return nil
+}
+
+func (x *OperationResult) GetDeleted() []string {
+	if x != nil {
+		return x.Deleted
+	}
+	return nil
+}
+
+func (x *OperationResult) GetResources() []*ResourceState {
 	if x != nil {
-		if x, ok := x.Value.(*Value_ListValue); ok {
-			return x.ListValue
-		}
+		return x.Resources
 	}
 	return nil
 }
 
-func (x *Value) GetMapValue() *ValueMap {
+func (x *OperationResult) GetImported() []*ImportedResource {
 	if x != nil {
-		if x, ok := x.Value.(*Value_MapValue); ok {
-			return x.MapValue
-		}
+		return x.Imported
 	}
 	return nil
 }
 
-func (x *Value) GetBytesValue() []byte {
+func (x *OperationResult) GetFailures() []*ImportFailure {
 	if x != nil {
-		if x, ok := x.Value.(*Value_BytesValue); ok {
-			return x.BytesValue
-		}
+		return x.Failures
 	}
 	return nil
 }
 
-type isValue_Value interface {
-	isValue_Value()
+// A resource as recorded in the stack state
+type ResourceState struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	Name  string                 `protobuf:"bytes,1,opt,name=name,proto3" json:"name,omitempty"`
+	Type  string                 `protobuf:"bytes,2,opt,name=type,proto3" json:"type,omitempty"`
+	Urn   string                 `protobuf:"bytes,3,opt,name=urn,proto3" json:"urn,omitempty"`
+	Id    string                 `protobuf:"bytes,4,opt,name=id,proto3" json:"id,omitempty"`
+	// Outputs reported by the provider, with secret values replaced by "[secret]"
+	Outputs       map[string]*Value `protobuf:"bytes,5,rep,name=outputs,proto3" json:"outputs,omitempty" protobuf_key:"bytes,1,opt,name=key" protobuf_val:"bytes,2,opt,name=value"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
 }
 
-type Value_StringValue struct {
-	StringValue string `protobuf:"bytes,1,opt,name=string_value,json=stringValue,proto3,oneof"`
+func (x *ResourceState) Reset() {
+	*x = ResourceState{}
+	mi := &file_pulumist_proto_msgTypes[23]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
 }
 
-type Value_IntValue struct {
-	IntValue int64 `protobuf:"varint,2,opt,name=int_value,json=intValue,proto3,oneof"`
+func (x *ResourceState) String() string {
+	return protoimpl.X.MessageStringOf(x)
 }
 
-type Value_DoubleValue struct {
-	DoubleValue float64 `protobuf:"fixed64,3,opt,name=double_value,json=doubleValue,proto3,oneof"`
-}
+func (*ResourceState) ProtoMessage() {}
 
-type Value_BoolValue struct {
-	BoolValue bool `protobuf:"varint,4,opt,name=bool_value,json=boolValue,proto3,oneof"`
+func (x *ResourceState) ProtoReflect() protoreflect.Message {
+	mi := &file_pulumist_proto_msgTypes[23]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
 }
 
-type Value_ListValue struct {
-	ListValue *ValueList `protobuf:"bytes,5,opt,name=list_value,json=listValue,proto3,oneof"`
+// Deprecated: Use ResourceState.ProtoReflect.Descriptor instead.
+func (*ResourceState) Descriptor() ([]byte, []int) {
+	return file_pulumist_proto_rawDescGZIP(), []int{23}
 }
 
-type Value_MapValue struct {
-	MapValue *ValueMap `protobuf:"bytes,6,opt,name=map_value,json=mapValue,proto3,oneof"`
+func (x *ResourceState) GetName() string {
+	if x != nil {
+		return x.Name
+	}
+	return ""
 }
 
-type Value_BytesValue struct {
-	BytesValue []byte `protobuf:"bytes,7,opt,name=bytes_value,json=bytesValue,proto3,oneof"`
+func (x *ResourceState) GetType() string {
+	if x != nil {
+		return x.Type
+	}
+	return ""
 }
 
-func (*Value_StringValue) isValue_Value() {}
-
-func (*Value_IntValue) isValue_Value() {}
-
-func (*Value_DoubleValue) isValue_Value() {}
-
-func (*Value_BoolValue) isValue_Value() {}
-
-func (*Value_ListValue) isValue_Value() {}
+func (x *ResourceState) GetUrn() string {
+	if x != nil {
+		return x.Urn
+	}
+	return ""
+}
 
-func (*Value_MapValue) isValue_Value() {}
+func (x *ResourceState) GetId() string {
+	if x != nil {
+		return x.Id
+	}
+	return ""
+}
 
-func (*Value_BytesValue) isValue_Value() {}
+func (x *ResourceState) GetOutputs() map[string]*Value {
+	if x != nil {
+		return x.Outputs
+	}
+	return nil
+}
 
-type ValueList struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	Values        []*Value               `protobuf:"bytes,1,rep,name=values,proto3" json:"values,omitempty"`
+// A resource adopted into the stack state by an import
+type ImportedResource struct {
+	state    protoimpl.MessageState `protogen:"open.v1"`
+	Type     string                 `protobuf:"bytes,1,opt,name=type,proto3" json:"type,omitempty"`
+	Name     string                 `protobuf:"bytes,2,opt,name=name,proto3" json:"name,omitempty"`
+	Id       string                 `protobuf:"bytes,3,opt,name=id,proto3" json:"id,omitempty"`
+	Urn      string                 `protobuf:"bytes,4,opt,name=urn,proto3" json:"urn,omitempty"`
+	Parent   string                 `protobuf:"bytes,5,opt,name=parent,proto3" json:"parent,omitempty"`
+	Provider string                 `protobuf:"bytes,6,opt,name=provider,proto3" json:"provider,omitempty"`
+	Protect  bool                   `protobuf:"varint,7,opt,name=protect,proto3" json:"protect,omitempty"`
+	// Inputs read from the cloud as recorded in the state, with secret inputs still encrypted
+	Inputs        map[string]*Value `protobuf:"bytes,8,rep,name=inputs,proto3" json:"inputs,omitempty" protobuf_key:"bytes,1,opt,name=key" protobuf_val:"bytes,2,opt,name=value"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
-func (x *ValueList) Reset() {
-	*x = ValueList{}
-	mi := &file_pulumist_proto_msgTypes[13]
+func (x *ImportedResource) Reset() {
+	*x = ImportedResource{}
+	mi := &file_pulumist_proto_msgTypes[24]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *ValueList) String() string {
+func (x *ImportedResource) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*ValueList) ProtoMessage() {}
+func (*ImportedResource) ProtoMessage() {}
 
-func (x *ValueList) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[13]
+func (x *ImportedResource) ProtoReflect() protoreflect.Message {
+	mi := &file_pulumist_proto_msgTypes[24]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1053,88 +2093,93 @@ func (x *ValueList) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use ValueList.ProtoReflect.Descriptor instead.
-func (*ValueList) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{13}
+// Deprecated: Use ImportedResource.ProtoReflect.Descriptor instead.
+func (*ImportedResource) Descriptor() ([]byte, []int) {
+	return file_pulumist_proto_rawDescGZIP(), []int{24}
 }
 
-func (x *ValueList) GetValues() []*Value {
+func (x *ImportedResource) GetType() string {
 	if x != nil {
-		return x.Values
+		return x.Type
 	}
-	return nil
+	return ""
 }
 
-type ValueMap struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	Fields        map[string]*Value      `protobuf:"bytes,1,rep,name=fields,proto3" json:"fields,omitempty" protobuf_key:"bytes,1,opt,name=key" protobuf_val:"bytes,2,opt,name=value"`
-	unknownFields protoimpl.UnknownFields
-	sizeCache     protoimpl.SizeCache
+func (x *ImportedResource) GetName() string {
+	if x != nil {
+		return x.Name
+	}
+	return ""
 }
 
-func (x *ValueMap) Reset() {
-	*x = ValueMap{}
-	mi := &file_pulumist_proto_msgTypes[14]
-	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
-	ms.StoreMessageInfo(mi)
+func (x *ImportedResource) GetId() string {
+	if x != nil {
+		return x.Id
+	}
+	return ""
 }
 
-func (x *ValueMap) String() string {
-	return protoimpl.X.MessageStringOf(x)
+func (x *ImportedResource) GetUrn() string {
+	if x != nil {
+		return x.Urn
+	}
+	return ""
 }
 
-func (*ValueMap) ProtoMessage() {}
+func (x *ImportedResource) GetParent() string {
+	if x != nil {
+		return x.Parent
+	}
+	return ""
+}
 
-func (x *ValueMap) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[14]
+func (x *ImportedResource) GetProvider() string {
 	if x != nil {
-		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
-		if ms.LoadMessageInfo() == nil {
-			ms.StoreMessageInfo(mi)
-		}
-		return ms
+		return x.Provider
 	}
-	return mi.MessageOf(x)
+	return ""
 }
 
-// Deprecated: Use ValueMap.ProtoReflect.Descriptor instead.
-func (*ValueMap) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{14}
+func (x *ImportedResource) GetProtect() bool {
+	if x != nil {
+		return x.Protect
+	}
+	return false
 }
 
-func (x *ValueMap) GetFields() map[string]*Value {
+func (x *ImportedResource) GetInputs() map[string]*Value {
 	if x != nil {
-		return x.Fields
+		return x.Inputs
 	}
 	return nil
 }
 
-// Response message for Pulumi operations
-type PulumiResponse struct {
+// A resource that could not be imported
+type ImportFailure struct {
 	state         protoimpl.MessageState `protogen:"open.v1"`
-	Success       bool                   `protobuf:"varint,1,opt,name=success,proto3" json:"success,omitempty"`
-	Error         string                 `protobuf:"bytes,2,opt,name=error,proto3" json:"error,omitempty"`
-	Data          []byte                 `protobuf:"bytes,3,opt,name=data,proto3" json:"data,omitempty"`
-	Outputs       []*OutputItem          `protobuf:"bytes,4,rep,name=outputs,proto3" json:"outputs,omitempty"`
+	Type          string                 `protobuf:"bytes,1,opt,name=type,proto3" json:"type,omitempty"`
+	Name          string                 `protobuf:"bytes,2,opt,name=name,proto3" json:"name,omitempty"`
+	Id            string                 `protobuf:"bytes,3,opt,name=id,proto3" json:"id,omitempty"`
+	Message       string                 `protobuf:"bytes,4,opt,name=message,proto3" json:"message,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
-func (x *PulumiResponse) Reset() {
-	*x = PulumiResponse{}
-	mi := &file_pulumist_proto_msgTypes[15]
+func (x *ImportFailure) Reset() {
+	*x = ImportFailure{}
+	mi := &file_pulumist_proto_msgTypes[25]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *PulumiResponse) String() string {
+func (x *ImportFailure) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*PulumiResponse) ProtoMessage() {}
+func (*ImportFailure) ProtoMessage() {}
 
-func (x *PulumiResponse) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[15]
+func (x *ImportFailure) ProtoReflect() protoreflect.Message {
+	mi := &file_pulumist_proto_msgTypes[25]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1145,37 +2190,37 @@ func (x *PulumiResponse) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use PulumiResponse.ProtoReflect.Descriptor instead.
-func (*PulumiResponse) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{15}
+// Deprecated: Use ImportFailure.ProtoReflect.Descriptor instead.
+func (*ImportFailure) Descriptor() ([]byte, []int) {
+	return file_pulumist_proto_rawDescGZIP(), []int{25}
 }
 
-func (x *PulumiResponse) GetSuccess() bool {
+func (x *ImportFailure) GetType() string {
 	if x != nil {
-		return x.Success
+		return x.Type
 	}
-	return false
+	return ""
 }
 
-func (x *PulumiResponse) GetError() string {
+func (x *ImportFailure) GetName() string {
 	if x != nil {
-		return x.Error
+		return x.Name
 	}
 	return ""
 }
 
-func (x *PulumiResponse) GetData() []byte {
+func (x *ImportFailure) GetId() string {
 	if x != nil {
-		return x.Data
+		return x.Id
 	}
-	return nil
+	return ""
 }
 
-func (x *PulumiResponse) GetOutputs() []*OutputItem {
+func (x *ImportFailure) GetMessage() string {
 	if x != nil {
-		return x.Outputs
+		return x.Message
 	}
-	return nil
+	return ""
 }
 
 type OutputItem struct {
@@ -1189,7 +2234,7 @@ type OutputItem struct {
 
 func (x *OutputItem) Reset() {
 	*x = OutputItem{}
-	mi := &file_pulumist_proto_msgTypes[16]
+	mi := &file_pulumist_proto_msgTypes[26]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -1201,7 +2246,7 @@ func (x *OutputItem) String() string {
 func (*OutputItem) ProtoMessage() {}
 
 func (x *OutputItem) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[16]
+	mi := &file_pulumist_proto_msgTypes[26]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1214,7 +2259,7 @@ func (x *OutputItem) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use OutputItem.ProtoReflect.Descriptor instead.
 func (*OutputItem) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{16}
+	return file_pulumist_proto_rawDescGZIP(), []int{26}
 }
 
 func (x *OutputItem) GetResourceName() string {
@@ -1251,14 +2296,16 @@ type Event struct {
 	//	*Event_Diagnostic
 	//	*Event_Policy
 	//	*Event_Progress
-	Event         isEvent_Event `protobuf_oneof:"event"`
+	Event isEvent_Event `protobuf_oneof:"event"`
+	// Operation that emitted this event (PulumiRequest.operation_id)
+	OperationId   uint64 `protobuf:"varint,9,opt,name=operation_id,json=operationId,proto3" json:"operation_id,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
 func (x *Event) Reset() {
 	*x = Event{}
-	mi := &file_pulumist_proto_msgTypes[17]
+	mi := &file_pulumist_proto_msgTypes[27]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -1270,7 +2317,7 @@ func (x *Event) String() string {
 func (*Event) ProtoMessage() {}
 
 func (x *Event) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[17]
+	mi := &file_pulumist_proto_msgTypes[27]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1283,7 +2330,7 @@ func (x *Event) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use Event.ProtoReflect.Descriptor instead.
 func (*Event) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{17}
+	return file_pulumist_proto_rawDescGZIP(), []int{27}
 }
 
 func (x *Event) GetEvent() isEvent_Event {
@@ -1365,6 +2412,13 @@ func (x *Event) GetProgress() *ProgressEvent {
 	return nil
 }
 
+func (x *Event) GetOperationId() uint64 {
+	if x != nil {
+		return x.OperationId
+	}
+	return 0
+}
+
 type isEvent_Event interface {
 	isEvent_Event()
 }
@@ -1426,7 +2480,7 @@ type PreludeEvent struct {
 
 func (x *PreludeEvent) Reset() {
 	*x = PreludeEvent{}
-	mi := &file_pulumist_proto_msgTypes[18]
+	mi := &file_pulumist_proto_msgTypes[28]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -1438,7 +2492,7 @@ func (x *PreludeEvent) String() string {
 func (*PreludeEvent) ProtoMessage() {}
 
 func (x *PreludeEvent) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[18]
+	mi := &file_pulumist_proto_msgTypes[28]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1451,7 +2505,7 @@ func (x *PreludeEvent) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use PreludeEvent.ProtoReflect.Descriptor instead.
 func (*PreludeEvent) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{18}
+	return file_pulumist_proto_rawDescGZIP(), []int{28}
 }
 
 func (x *PreludeEvent) GetConfig() map[string]string {
@@ -1472,7 +2526,7 @@ type SummaryEvent struct {
 
 func (x *SummaryEvent) Reset() {
 	*x = SummaryEvent{}
-	mi := &file_pulumist_proto_msgTypes[19]
+	mi := &file_pulumist_proto_msgTypes[29]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -1484,7 +2538,7 @@ func (x *SummaryEvent) String() string {
 func (*SummaryEvent) ProtoMessage() {}
 
 func (x *SummaryEvent) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[19]
+	mi := &file_pulumist_proto_msgTypes[29]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1497,7 +2551,7 @@ func (x *SummaryEvent) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use SummaryEvent.ProtoReflect.Descriptor instead.
 func (*SummaryEvent) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{19}
+	return file_pulumist_proto_rawDescGZIP(), []int{29}
 }
 
 func (x *SummaryEvent) GetMayChange() bool {
@@ -1531,7 +2585,7 @@ type ResourcePreEvent struct {
 
 func (x *ResourcePreEvent) Reset() {
 	*x = ResourcePreEvent{}
-	mi := &file_pulumist_proto_msgTypes[20]
+	mi := &file_pulumist_proto_msgTypes[30]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -1543,7 +2597,7 @@ func (x *ResourcePreEvent) String() string {
 func (*ResourcePreEvent) ProtoMessage() {}
 
 func (x *ResourcePreEvent) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[20]
+	mi := &file_pulumist_proto_msgTypes[30]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1556,7 +2610,7 @@ func (x *ResourcePreEvent) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use ResourcePreEvent.ProtoReflect.Descriptor instead.
 func (*ResourcePreEvent) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{20}
+	return file_pulumist_proto_rawDescGZIP(), []int{30}
 }
 
 func (x *ResourcePreEvent) GetMetadata() *ResourceMetadata {
@@ -1583,7 +2637,7 @@ type ResOutputsEvent struct {
 
 func (x *ResOutputsEvent) Reset() {
 	*x = ResOutputsEvent{}
-	mi := &file_pulumist_proto_msgTypes[21]
+	mi := &file_pulumist_proto_msgTypes[31]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -1595,7 +2649,7 @@ func (x *ResOutputsEvent) String() string {
 func (*ResOutputsEvent) ProtoMessage() {}
 
 func (x *ResOutputsEvent) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[21]
+	mi := &file_pulumist_proto_msgTypes[31]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1608,7 +2662,7 @@ func (x *ResOutputsEvent) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use ResOutputsEvent.ProtoReflect.Descriptor instead.
 func (*ResOutputsEvent) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{21}
+	return file_pulumist_proto_rawDescGZIP(), []int{31}
 }
 
 func (x *ResOutputsEvent) GetMetadata() *ResourceMetadata {
@@ -1636,7 +2690,7 @@ type ResOpFailedEvent struct {
 
 func (x *ResOpFailedEvent) Reset() {
 	*x = ResOpFailedEvent{}
-	mi := &file_pulumist_proto_msgTypes[22]
+	mi := &file_pulumist_proto_msgTypes[32]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -1648,7 +2702,7 @@ func (x *ResOpFailedEvent) String() string {
 func (*ResOpFailedEvent) ProtoMessage() {}
 
 func (x *ResOpFailedEvent) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[22]
+	mi := &file_pulumist_proto_msgTypes[32]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1661,7 +2715,7 @@ func (x *ResOpFailedEvent) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use ResOpFailedEvent.ProtoReflect.Descriptor instead.
 func (*ResOpFailedEvent) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{22}
+	return file_pulumist_proto_rawDescGZIP(), []int{32}
 }
 
 func (x *ResOpFailedEvent) GetMetadata() *ResourceMetadata {
@@ -1698,7 +2752,7 @@ type DiagnosticEvent struct {
 
 func (x *DiagnosticEvent) Reset() {
 	*x = DiagnosticEvent{}
-	mi := &file_pulumist_proto_msgTypes[23]
+	mi := &file_pulumist_proto_msgTypes[33]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -1710,7 +2764,7 @@ func (x *DiagnosticEvent) String() string {
 func (*DiagnosticEvent) ProtoMessage() {}
 
 func (x *DiagnosticEvent) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[23]
+	mi := &file_pulumist_proto_msgTypes[33]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1723,7 +2777,7 @@ func (x *DiagnosticEvent) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use DiagnosticEvent.ProtoReflect.Descriptor instead.
 func (*DiagnosticEvent) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{23}
+	return file_pulumist_proto_rawDescGZIP(), []int{33}
 }
 
 func (x *DiagnosticEvent) GetUrn() string {
@@ -1773,7 +2827,7 @@ type PolicyEvent struct {
 
 func (x *PolicyEvent) Reset() {
 	*x = PolicyEvent{}
-	mi := &file_pulumist_proto_msgTypes[24]
+	mi := &file_pulumist_proto_msgTypes[34]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -1785,7 +2839,7 @@ func (x *PolicyEvent) String() string {
 func (*PolicyEvent) ProtoMessage() {}
 
 func (x *PolicyEvent) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[24]
+	mi := &file_pulumist_proto_msgTypes[34]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1798,7 +2852,7 @@ func (x *PolicyEvent) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use PolicyEvent.ProtoReflect.Descriptor instead.
 func (*PolicyEvent) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{24}
+	return file_pulumist_proto_rawDescGZIP(), []int{34}
 }
 
 func (x *PolicyEvent) GetResourceUrn() string {
@@ -1839,7 +2893,7 @@ type PolicyConfig struct {
 
 func (x *PolicyConfig) Reset() {
 	*x = PolicyConfig{}
-	mi := &file_pulumist_proto_msgTypes[25]
+	mi := &file_pulumist_proto_msgTypes[35]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -1851,7 +2905,7 @@ func (x *PolicyConfig) String() string {
 func (*PolicyConfig) ProtoMessage() {}
 
 func (x *PolicyConfig) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[25]
+	mi := &file_pulumist_proto_msgTypes[35]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1864,7 +2918,7 @@ func (x *PolicyConfig) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use PolicyConfig.ProtoReflect.Descriptor instead.
 func (*PolicyConfig) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{25}
+	return file_pulumist_proto_rawDescGZIP(), []int{35}
 }
 
 func (x *PolicyConfig) GetName() string {
@@ -1890,7 +2944,7 @@ type ProgressEvent struct {
 
 func (x *ProgressEvent) Reset() {
 	*x = ProgressEvent{}
-	mi := &file_pulumist_proto_msgTypes[26]
+	mi := &file_pulumist_proto_msgTypes[36]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -1902,7 +2956,7 @@ func (x *ProgressEvent) String() string {
 func (*ProgressEvent) ProtoMessage() {}
 
 func (x *ProgressEvent) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[26]
+	mi := &file_pulumist_proto_msgTypes[36]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1915,7 +2969,7 @@ func (x *ProgressEvent) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use ProgressEvent.ProtoReflect.Descriptor instead.
 func (*ProgressEvent) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{26}
+	return file_pulumist_proto_rawDescGZIP(), []int{36}
 }
 
 func (x *ProgressEvent) GetMessage() string {
@@ -1938,7 +2992,7 @@ type ResourceMetadata struct {
 
 func (x *ResourceMetadata) Reset() {
 	*x = ResourceMetadata{}
-	mi := &file_pulumist_proto_msgTypes[27]
+	mi := &file_pulumist_proto_msgTypes[37]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -1950,7 +3004,7 @@ func (x *ResourceMetadata) String() string {
 func (*ResourceMetadata) ProtoMessage() {}
 
 func (x *ResourceMetadata) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[27]
+	mi := &file_pulumist_proto_msgTypes[37]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1963,7 +3017,7 @@ func (x *ResourceMetadata) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use ResourceMetadata.ProtoReflect.Descriptor instead.
 func (*ResourceMetadata) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{27}
+	return file_pulumist_proto_rawDescGZIP(), []int{37}
 }
 
 func (x *ResourceMetadata) GetOp() string {
@@ -2011,7 +3065,7 @@ type RegisterCallbackRequest struct {
 
 func (x *RegisterCallbackRequest) Reset() {
 	*x = RegisterCallbackRequest{}
-	mi := &file_pulumist_proto_msgTypes[28]
+	mi := &file_pulumist_proto_msgTypes[38]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -2023,7 +3077,7 @@ func (x *RegisterCallbackRequest) String() string {
 func (*RegisterCallbackRequest) ProtoMessage() {}
 
 func (x *RegisterCallbackRequest) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[28]
+	mi := &file_pulumist_proto_msgTypes[38]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -2036,7 +3090,7 @@ func (x *RegisterCallbackRequest) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use RegisterCallbackRequest.ProtoReflect.Descriptor instead.
 func (*RegisterCallbackRequest) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{28}
+	return file_pulumist_proto_rawDescGZIP(), []int{38}
 }
 
 func (x *RegisterCallbackRequest) GetCallbackType() int32 {
@@ -2055,7 +3109,7 @@ type RegisterCallbackResponse struct {
 
 func (x *RegisterCallbackResponse) Reset() {
 	*x = RegisterCallbackResponse{}
-	mi := &file_pulumist_proto_msgTypes[29]
+	mi := &file_pulumist_proto_msgTypes[39]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -2067,7 +3121,7 @@ func (x *RegisterCallbackResponse) String() string {
 func (*RegisterCallbackResponse) ProtoMessage() {}
 
 func (x *RegisterCallbackResponse) ProtoReflect() protoreflect.Message {
-	mi := &file_pulumist_proto_msgTypes[29]
+	mi := &file_pulumist_proto_msgTypes[39]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -2080,7 +3134,7 @@ func (x *RegisterCallbackResponse) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use RegisterCallbackResponse.ProtoReflect.Descriptor instead.
 func (*RegisterCallbackResponse) Descriptor() ([]byte, []int) {
-	return file_pulumist_proto_rawDescGZIP(), []int{29}
+	return file_pulumist_proto_rawDescGZIP(), []int{39}
 }
 
 func (x *RegisterCallbackResponse) GetSuccess() bool {
@@ -2094,7 +3148,7 @@ var File_pulumist_proto protoreflect.FileDescriptor
 
 const file_pulumist_proto_rawDesc = "" +
 	"\n" +
-	"\x0epulumist.proto\x12\bpulumist\"\xe0\x02\n" +
+	"\x0epulumist.proto\x12\bpulumist\"\x97\x05\n" +
 	"\rPulumiRequest\x12\x1f\n" +
 	"\vworking_dir\x18\x01 \x01(\tR\n" +
 	"workingDir\x12\x1d\n" +
@@ -2103,10 +3157,41 @@ const file_pulumist_proto_rawDesc = "" +
 	"\fproject_name\x18\x03 \x01(\tR\vprojectName\x120\n" +
 	"\tresources\x18\x04 \x03(\v2\x12.pulumist.ResourceR\tresources\x12;\n" +
 	"\x06config\x18\x05 \x03(\v2#.pulumist.PulumiRequest.ConfigEntryR\x06config\x12B\n" +
-	"\rpulumi_config\x18\x06 \x01(\v2\x1d.pulumist.PulumiConfigurationR\fpulumiConfig\x1a9\n" +
+	"\rpulumi_config\x18\x06 \x01(\v2\x1d.pulumist.PulumiConfigurationR\fpulumiConfig\x12!\n" +
+	"\foperation_id\x18\a \x01(\x04R\voperationId\x12'\n" +
+	"\x0ftimeout_seconds\x18\b \x01(\rR\x0etimeoutSeconds\x12!\n" +
+	"\fpreview_only\x18\t \x01(\bR\vpreviewOnly\x122\n" +
+	"\aimports\x18\n" +
+	" \x03(\v2\x18.pulumist.ImportResourceR\aimports\x12\x1e\n" +
+	"\n" +
+	"deployment\x18\v \x01(\fR\n" +
+	"deployment\x12/\n" +
+	"\aoutputs\x18\f \x03(\v2\x15.pulumist.StackOutputR\aoutputs\x12*\n" +
+	"\ainvokes\x18\r \x03(\v2\x10.pulumist.InvokeR\ainvokes\x1aP\n" +
 	"\vConfigEntry\x12\x10\n" +
-	"\x03key\x18\x01 \x01(\tR\x03key\x12\x14\n" +
-	"\x05value\x18\x02 \x01(\tR\x05value:\x028\x01\"\xde\x02\n" +
+	"\x03key\x18\x01 \x01(\tR\x03key\x12+\n" +
+	"\x05value\x18\x02 \x01(\v2\x15.pulumist.ConfigValueR\x05value:\x028\x01\"\xc8\x01\n" +
+	"\x06Invoke\x12\x12\n" +
+	"\x04name\x18\x01 \x01(\tR\x04name\x12\x14\n" +
+	"\x05token\x18\x02 \x01(\tR\x05token\x12.\n" +
+	"\x04args\x18\x03 \x03(\v2\x1a.pulumist.Invoke.ArgsEntryR\x04args\x12\x1a\n" +
+	"\bprovider\x18\x04 \x01(\tR\bprovider\x1aH\n" +
+	"\tArgsEntry\x12\x10\n" +
+	"\x03key\x18\x01 \x01(\tR\x03key\x12%\n" +
+	"\x05value\x18\x02 \x01(\v2\x0f.pulumist.ValueR\x05value:\x028\x01\"`\n" +
+	"\vStackOutput\x12\x12\n" +
+	"\x04name\x18\x01 \x01(\tR\x04name\x12%\n" +
+	"\x05value\x18\x02 \x01(\v2\x0f.pulumist.ValueR\x05value\x12\x16\n" +
+	"\x06secret\x18\x03 \x01(\bR\x06secret\"|\n" +
+	"\x0eImportResource\x12\x12\n" +
+	"\x04type\x18\x01 \x01(\tR\x04type\x12\x12\n" +
+	"\x04name\x18\x02 \x01(\tR\x04name\x12\x0e\n" +
+	"\x02id\x18\x03 \x01(\tR\x02id\x12\x16\n" +
+	"\x06parent\x18\x04 \x01(\tR\x06parent\x12\x1a\n" +
+	"\bprovider\x18\x05 \x01(\tR\bprovider\";\n" +
+	"\vConfigValue\x12\x14\n" +
+	"\x05value\x18\x01 \x01(\tR\x05value\x12\x16\n" +
+	"\x06secret\x18\x02 \x01(\bR\x06secret\"\xde\x02\n" +
 	"\x13PulumiConfiguration\x12D\n" +
 	"\x10secrets_provider\x18\x01 \x01(\v2\x19.pulumist.SecretsProviderR\x0fsecretsProvider\x121\n" +
 	"\abackend\x18\x02 \x01(\v2\x17.pulumist.BackendConfigR\abackend\x12P\n" +
@@ -2148,7 +3233,7 @@ const file_pulumist_proto_rawDesc = "" +
 	"\x04path\x18\x01 \x01(\tR\x04path\"=\n" +
 	"\fCloudBackend\x12\x10\n" +
 	"\x03url\x18\x01 \x01(\tR\x03url\x12\x1b\n" +
-	"\tapi_token\x18\x02 \x01(\tR\bapiToken\"\x9e\x01\n" +
+	"\tapi_token\x18\x02 \x01(\tR\bapiToken\"\xba\x01\n" +
 	"\tS3Backend\x12\x16\n" +
 	"\x06bucket\x18\x01 \x01(\tR\x06bucket\x12\x16\n" +
 	"\x06region\x18\x02 \x01(\tR\x06region\x12\x1d\n" +
@@ -2156,13 +3241,14 @@ const file_pulumist_proto_rawDesc = "" +
 	"access_key\x18\x03 \x01(\tR\taccessKey\x12\x1d\n" +
 	"\n" +
 	"secret_key\x18\x04 \x01(\tR\tsecretKey\x12#\n" +
-	"\rsession_token\x18\x05 \x01(\tR\fsessionToken\"\x95\x01\n" +
+	"\rsession_token\x18\x05 \x01(\tR\fsessionToken\x12\x1a\n" +
+	"\bendpoint\x18\x06 \x01(\tR\bendpoint\"\x95\x01\n" +
 	"\x10AzureBlobBackend\x12'\n" +
 	"\x0fstorage_account\x18\x01 \x01(\tR\x0estorageAccount\x12\x1c\n" +
 	"\tcontainer\x18\x02 \x01(\tR\tcontainer\x12\x1d\n" +
 	"\n" +
 	"access_key\x18\x03 \x01(\tR\taccessKey\x12\x1b\n" +
-	"\tsas_token\x18\x04 \x01(\tR\bsasToken\"\x81\x02\n" +
+	"\tsas_token\x18\x04 \x01(\tR\bsasToken\"\x8e\a\n" +
 	"\bResource\x12\x12\n" +
 	"\x04type\x18\x01 \x01(\tR\x04type\x12\x12\n" +
 	"\x04name\x18\x02 \x01(\tR\x04name\x12B\n" +
@@ -2171,10 +3257,41 @@ const file_pulumist_proto_rawDesc = "" +
 	"properties\x12\x1d\n" +
 	"\n" +
 	"depends_on\x18\x04 \x03(\tR\tdependsOn\x12\x1a\n" +
-	"\bprovider\x18\x05 \x01(\tR\bprovider\x1aN\n" +
+	"\bprovider\x18\x05 \x01(\tR\bprovider\x12A\n" +
+	"\x0fcustom_timeouts\x18\x06 \x01(\v2\x18.pulumist.CustomTimeoutsR\x0ecustomTimeouts\x12\x16\n" +
+	"\x06parent\x18\a \x01(\tR\x06parent\x12\x18\n" +
+	"\aprotect\x18\b \x01(\bR\aprotect\x12%\n" +
+	"\x0eignore_changes\x18\t \x03(\tR\rignoreChanges\x12(\n" +
+	"\x10retain_on_delete\x18\n" +
+	" \x01(\bR\x0eretainOnDelete\x12,\n" +
+	"\x12replace_on_changes\x18\v \x03(\tR\x10replaceOnChanges\x122\n" +
+	"\x15delete_before_replace\x18\f \x01(\bR\x13deleteBeforeReplace\x12:\n" +
+	"\x19additional_secret_outputs\x18\r \x03(\tR\x17additionalSecretOutputs\x12!\n" +
+	"\fdeleted_with\x18\x0e \x01(\tR\vdeletedWith\x12)\n" +
+	"\aaliases\x18\x0f \x03(\v2\x0f.pulumist.AliasR\aaliases\x12\x1c\n" +
+	"\tcomponent\x18\x10 \x01(\bR\tcomponent\x12U\n" +
+	"\x11component_outputs\x18\x11 \x03(\v2(.pulumist.Resource.ComponentOutputsEntryR\x10componentOutputs\x12\x0e\n" +
+	"\x02id\x18\x12 \x01(\tR\x02id\x1aN\n" +
 	"\x0fPropertiesEntry\x12\x10\n" +
 	"\x03key\x18\x01 \x01(\tR\x03key\x12%\n" +
-	"\x05value\x18\x02 \x01(\v2\x0f.pulumist.ValueR\x05value:\x028\x01\"\xa6\x02\n" +
+	"\x05value\x18\x02 \x01(\v2\x0f.pulumist.ValueR\x05value:\x028\x01\x1aT\n" +
+	"\x15ComponentOutputsEntry\x12\x10\n" +
+	"\x03key\x18\x01 \x01(\tR\x03key\x12%\n" +
+	"\x05value\x18\x02 \x01(\v2\x0f.pulumist.ValueR\x05value:\x028\x01\"\xc5\x01\n" +
+	"\x05Alias\x12\x12\n" +
+	"\x04name\x18\x01 \x01(\tR\x04name\x12\x12\n" +
+	"\x04type\x18\x02 \x01(\tR\x04type\x12\x16\n" +
+	"\x06parent\x18\x03 \x01(\tR\x06parent\x12\x1d\n" +
+	"\n" +
+	"parent_urn\x18\x04 \x01(\tR\tparentUrn\x12\x1b\n" +
+	"\tno_parent\x18\x05 \x01(\bR\bnoParent\x12\x14\n" +
+	"\x05stack\x18\x06 \x01(\tR\x05stack\x12\x18\n" +
+	"\aproject\x18\a \x01(\tR\aproject\x12\x10\n" +
+	"\x03urn\x18\b \x01(\tR\x03urn\"X\n" +
+	"\x0eCustomTimeouts\x12\x16\n" +
+	"\x06create\x18\x01 \x01(\tR\x06create\x12\x16\n" +
+	"\x06update\x18\x02 \x01(\tR\x06update\x12\x16\n" +
+	"\x06delete\x18\x03 \x01(\tR\x06delete\"\xa6\x02\n" +
 	"\x05Value\x12#\n" +
 	"\fstring_value\x18\x01 \x01(\tH\x00R\vstringValue\x12\x1d\n" +
 	"\tint_value\x18\x02 \x01(\x03H\x00R\bintValue\x12#\n" +
@@ -2193,18 +3310,68 @@ const file_pulumist_proto_rawDesc = "" +
 	"\x06fields\x18\x01 \x03(\v2\x1e.pulumist.ValueMap.FieldsEntryR\x06fields\x1aJ\n" +
 	"\vFieldsEntry\x12\x10\n" +
 	"\x03key\x18\x01 \x01(\tR\x03key\x12%\n" +
-	"\x05value\x18\x02 \x01(\v2\x0f.pulumist.ValueR\x05value:\x028\x01\"\x84\x01\n" +
+	"\x05value\x18\x02 \x01(\v2\x0f.pulumist.ValueR\x05value:\x028\x01\"\x9f\x02\n" +
 	"\x0ePulumiResponse\x12\x18\n" +
 	"\asuccess\x18\x01 \x01(\bR\asuccess\x12\x14\n" +
 	"\x05error\x18\x02 \x01(\tR\x05error\x12\x12\n" +
 	"\x04data\x18\x03 \x01(\fR\x04data\x12.\n" +
-	"\aoutputs\x18\x04 \x03(\v2\x14.pulumist.OutputItemR\aoutputs\"y\n" +
+	"\aoutputs\x18\x04 \x03(\v2\x14.pulumist.OutputItemR\aoutputs\x12\x1c\n" +
+	"\tcancelled\x18\x05 \x01(\bR\tcancelled\x12\x1b\n" +
+	"\ttimed_out\x18\x06 \x01(\bR\btimedOut\x12+\n" +
+	"\x11pending_resources\x18\a \x03(\tR\x10pendingResources\x121\n" +
+	"\x06result\x18\b \x01(\v2\x19.pulumist.OperationResultR\x06result\"\xa4\x05\n" +
+	"\x0fOperationResult\x12\x16\n" +
+	"\x06stdout\x18\x01 \x01(\tR\x06stdout\x12\x16\n" +
+	"\x06stderr\x18\x02 \x01(\tR\x06stderr\x12Y\n" +
+	"\x10resource_changes\x18\x03 \x03(\v2..pulumist.OperationResult.ResourceChangesEntryR\x0fresourceChanges\x12@\n" +
+	"\aoutputs\x18\x04 \x03(\v2&.pulumist.OperationResult.OutputsEntryR\aoutputs\x12\x16\n" +
+	"\x06status\x18\x05 \x01(\tR\x06status\x12\x18\n" +
+	"\amessage\x18\x06 \x01(\tR\amessage\x12)\n" +
+	"\x10duration_seconds\x18\a \x01(\x01R\x0fdurationSeconds\x12\x18\n" +
+	"\aupdated\x18\b \x03(\tR\aupdated\x12\x18\n" +
+	"\adeleted\x18\t \x03(\tR\adeleted\x125\n" +
+	"\tresources\x18\n" +
+	" \x03(\v2\x17.pulumist.ResourceStateR\tresources\x126\n" +
+	"\bimported\x18\v \x03(\v2\x1a.pulumist.ImportedResourceR\bimported\x123\n" +
+	"\bfailures\x18\f \x03(\v2\x17.pulumist.ImportFailureR\bfailures\x1aB\n" +
+	"\x14ResourceChangesEntry\x12\x10\n" +
+	"\x03key\x18\x01 \x01(\tR\x03key\x12\x14\n" +
+	"\x05value\x18\x02 \x01(\x05R\x05value:\x028\x01\x1aK\n" +
+	"\fOutputsEntry\x12\x10\n" +
+	"\x03key\x18\x01 \x01(\tR\x03key\x12%\n" +
+	"\x05value\x18\x02 \x01(\v2\x0f.pulumist.ValueR\x05value:\x028\x01\"\xe6\x01\n" +
+	"\rResourceState\x12\x12\n" +
+	"\x04name\x18\x01 \x01(\tR\x04name\x12\x12\n" +
+	"\x04type\x18\x02 \x01(\tR\x04type\x12\x10\n" +
+	"\x03urn\x18\x03 \x01(\tR\x03urn\x12\x0e\n" +
+	"\x02id\x18\x04 \x01(\tR\x02id\x12>\n" +
+	"\aoutputs\x18\x05 \x03(\v2$.pulumist.ResourceState.OutputsEntryR\aoutputs\x1aK\n" +
+	"\fOutputsEntry\x12\x10\n" +
+	"\x03key\x18\x01 \x01(\tR\x03key\x12%\n" +
+	"\x05value\x18\x02 \x01(\v2\x0f.pulumist.ValueR\x05value:\x028\x01\"\xb6\x02\n" +
+	"\x10ImportedResource\x12\x12\n" +
+	"\x04type\x18\x01 \x01(\tR\x04type\x12\x12\n" +
+	"\x04name\x18\x02 \x01(\tR\x04name\x12\x0e\n" +
+	"\x02id\x18\x03 \x01(\tR\x02id\x12\x10\n" +
+	"\x03urn\x18\x04 \x01(\tR\x03urn\x12\x16\n" +
+	"\x06parent\x18\x05 \x01(\tR\x06parent\x12\x1a\n" +
+	"\bprovider\x18\x06 \x01(\tR\bprovider\x12\x18\n" +
+	"\aprotect\x18\a \x01(\bR\aprotect\x12>\n" +
+	"\x06inputs\x18\b \x03(\v2&.pulumist.ImportedResource.InputsEntryR\x06inputs\x1aJ\n" +
+	"\vInputsEntry\x12\x10\n" +
+	"\x03key\x18\x01 \x01(\tR\x03key\x12%\n" +
+	"\x05value\x18\x02 \x01(\v2\x0f.pulumist.ValueR\x05value:\x028\x01\"a\n" +
+	"\rImportFailure\x12\x12\n" +
+	"\x04type\x18\x01 \x01(\tR\x04type\x12\x12\n" +
+	"\x04name\x18\x02 \x01(\tR\x04name\x12\x0e\n" +
+	"\x02id\x18\x03 \x01(\tR\x02id\x12\x18\n" +
+	"\amessage\x18\x04 \x01(\tR\amessage\"y\n" +
 	"\n" +
 	"OutputItem\x12#\n" +
 	"\rresource_name\x18\x01 \x01(\tR\fresourceName\x12\x1f\n" +
 	"\voutput_name\x18\x02 \x01(\tR\n" +
 	"outputName\x12%\n" +
-	"\x05value\x18\x03 \x01(\v2\x0f.pulumist.ValueR\x05value\"\xed\x03\n" +
+	"\x05value\x18\x03 \x01(\v2\x0f.pulumist.ValueR\x05value\"\x90\x04\n" +
 	"\x05Event\x122\n" +
 	"\aprelude\x18\x01 \x01(\v2\x16.pulumist.PreludeEventH\x00R\aprelude\x122\n" +
 	"\asummary\x18\x02 \x01(\v2\x16.pulumist.SummaryEventH\x00R\asummary\x12?\n" +
@@ -2215,7 +3382,8 @@ const file_pulumist_proto_rawDesc = "" +
 	"diagnostic\x18\x06 \x01(\v2\x19.pulumist.DiagnosticEventH\x00R\n" +
 	"diagnostic\x12/\n" +
 	"\x06policy\x18\a \x01(\v2\x15.pulumist.PolicyEventH\x00R\x06policy\x125\n" +
-	"\bprogress\x18\b \x01(\v2\x17.pulumist.ProgressEventH\x00R\bprogressB\a\n" +
+	"\bprogress\x18\b \x01(\v2\x17.pulumist.ProgressEventH\x00R\bprogress\x12!\n" +
+	"\foperation_id\x18\t \x01(\x04R\voperationIdB\a\n" +
 	"\x05event\"\x85\x01\n" +
 	"\fPreludeEvent\x12:\n" +
 	"\x06config\x18\x01 \x03(\v2\".pulumist.PreludeEvent.ConfigEntryR\x06config\x1a9\n" +
@@ -2279,89 +3447,127 @@ func file_pulumist_proto_rawDescGZIP() []byte {
 	return file_pulumist_proto_rawDescData
 }
 
-var file_pulumist_proto_msgTypes = make([]protoimpl.MessageInfo, 37)
+var file_pulumist_proto_msgTypes = make([]protoimpl.MessageInfo, 53)
 var file_pulumist_proto_goTypes = []any{
 	(*PulumiRequest)(nil),            // 0: pulumist.PulumiRequest
-	(*PulumiConfiguration)(nil),      // 1: pulumist.PulumiConfiguration
-	(*SecretsProvider)(nil),          // 2: pulumist.SecretsProvider
-	(*PassphraseProvider)(nil),       // 3: pulumist.PassphraseProvider
-	(*CloudKmsProvider)(nil),         // 4: pulumist.CloudKmsProvider
-	(*LocalProvider)(nil),            // 5: pulumist.LocalProvider
-	(*BackendConfig)(nil),            // 6: pulumist.BackendConfig
-	(*LocalBackend)(nil),             // 7: pulumist.LocalBackend
-	(*CloudBackend)(nil),             // 8: pulumist.CloudBackend
-	(*S3Backend)(nil),                // 9: pulumist.S3Backend
-	(*AzureBlobBackend)(nil),         // 10: pulumist.AzureBlobBackend
-	(*Resource)(nil),                 // 11: pulumist.Resource
-	(*Value)(nil),                    // 12: pulumist.Value
-	(*ValueList)(nil),                // 13: pulumist.ValueList
-	(*ValueMap)(nil),                 // 14: pulumist.ValueMap
-	(*PulumiResponse)(nil),           // 15: pulumist.PulumiResponse
-	(*OutputItem)(nil),               // 16: pulumist.OutputItem
-	(*Event)(nil),                    // 17: pulumist.Event
-	(*PreludeEvent)(nil),             // 18: pulumist.PreludeEvent
-	(*SummaryEvent)(nil),             // 19: pulumist.SummaryEvent
-	(*ResourcePreEvent)(nil),         // 20: pulumist.ResourcePreEvent
-	(*ResOutputsEvent)(nil),          // 21: pulumist.ResOutputsEvent
-	(*ResOpFailedEvent)(nil),         // 22: pulumist.ResOpFailedEvent
-	(*DiagnosticEvent)(nil),          // 23: pulumist.DiagnosticEvent
-	(*PolicyEvent)(nil),              // 24: pulumist.PolicyEvent
-	(*PolicyConfig)(nil),             // 25: pulumist.PolicyConfig
-	(*ProgressEvent)(nil),            // 26: pulumist.ProgressEvent
-	(*ResourceMetadata)(nil),         // 27: pulumist.ResourceMetadata
-	(*RegisterCallbackRequest)(nil),  // 28: pulumist.RegisterCallbackRequest
-	(*RegisterCallbackResponse)(nil), // 29: pulumist.RegisterCallbackResponse
-	nil,                              // 30: pulumist.PulumiRequest.ConfigEntry
-	nil,                              // 31: pulumist.PulumiConfiguration.EnvironmentEntry
-	nil,                              // 32: pulumist.CloudKmsProvider.CredentialsEntry
-	nil,                              // 33: pulumist.Resource.PropertiesEntry
-	nil,                              // 34: pulumist.ValueMap.FieldsEntry
-	nil,                              // 35: pulumist.PreludeEvent.ConfigEntry
-	nil,                              // 36: pulumist.SummaryEvent.ResourceChangesEntry
+	(*Invoke)(nil),                   // 1: pulumist.Invoke
+	(*StackOutput)(nil),              // 2: pulumist.StackOutput
+	(*ImportResource)(nil),           // 3: pulumist.ImportResource
+	(*ConfigValue)(nil),              // 4: pulumist.ConfigValue
+	(*PulumiConfiguration)(nil),      // 5: pulumist.PulumiConfiguration
+	(*SecretsProvider)(nil),          // 6: pulumist.SecretsProvider
+	(*PassphraseProvider)(nil),       // 7: pulumist.PassphraseProvider
+	(*CloudKmsProvider)(nil),         // 8: pulumist.CloudKmsProvider
+	(*LocalProvider)(nil),            // 9: pulumist.LocalProvider
+	(*BackendConfig)(nil),            // 10: pulumist.BackendConfig
+	(*LocalBackend)(nil),             // 11: pulumist.LocalBackend
+	(*CloudBackend)(nil),             // 12: pulumist.CloudBackend
+	(*S3Backend)(nil),                // 13: pulumist.S3Backend
+	(*AzureBlobBackend)(nil),         // 14: pulumist.AzureBlobBackend
+	(*Resource)(nil),                 // 15: pulumist.Resource
+	(*Alias)(nil),                    // 16: pulumist.Alias
+	(*CustomTimeouts)(nil),           // 17: pulumist.CustomTimeouts
+	(*Value)(nil),                    // 18: pulumist.Value
+	(*ValueList)(nil),                // 19: pulumist.ValueList
+	(*ValueMap)(nil),                 // 20: pulumist.ValueMap
+	(*PulumiResponse)(nil),           // 21: pulumist.PulumiResponse
+	(*OperationResult)(nil),          // 22: pulumist.OperationResult
+	(*ResourceState)(nil),            // 23: pulumist.ResourceState
+	(*ImportedResource)(nil),         // 24: pulumist.ImportedResource
+	(*ImportFailure)(nil),            // 25: pulumist.ImportFailure
+	(*OutputItem)(nil),               // 26: pulumist.OutputItem
+	(*Event)(nil),                    // 27: pulumist.Event
+	(*PreludeEvent)(nil),             // 28: pulumist.PreludeEvent
+	(*SummaryEvent)(nil),             // 29: pulumist.SummaryEvent
+	(*ResourcePreEvent)(nil),         // 30: pulumist.ResourcePreEvent
+	(*ResOutputsEvent)(nil),          // 31: pulumist.ResOutputsEvent
+	(*ResOpFailedEvent)(nil),         // 32: pulumist.ResOpFailedEvent
+	(*DiagnosticEvent)(nil),          // 33: pulumist.DiagnosticEvent
+	(*PolicyEvent)(nil),              // 34: pulumist.PolicyEvent
+	(*PolicyConfig)(nil),             // 35: pulumist.PolicyConfig
+	(*ProgressEvent)(nil),            // 36: pulumist.ProgressEvent
+	(*ResourceMetadata)(nil),         // 37: pulumist.ResourceMetadata
+	(*RegisterCallbackRequest)(nil),  // 38: pulumist.RegisterCallbackRequest
+	(*RegisterCallbackResponse)(nil), // 39: pulumist.RegisterCallbackResponse
+	nil,                              // 40: pulumist.PulumiRequest.ConfigEntry
+	nil,                              // 41: pulumist.Invoke.ArgsEntry
+	nil,                              // 42: pulumist.PulumiConfiguration.EnvironmentEntry
+	nil,                              // 43: pulumist.CloudKmsProvider.CredentialsEntry
+	nil,                              // 44: pulumist.Resource.PropertiesEntry
+	nil,                              // 45: pulumist.Resource.ComponentOutputsEntry
+	nil,                              // 46: pulumist.ValueMap.FieldsEntry
+	nil,                              // 47: pulumist.OperationResult.ResourceChangesEntry
+	nil,                              // 48: pulumist.OperationResult.OutputsEntry
+	nil,                              // 49: pulumist.ResourceState.OutputsEntry
+	nil,                              // 50: pulumist.ImportedResource.InputsEntry
+	nil,                              // 51: pulumist.PreludeEvent.ConfigEntry
+	nil,                              // 52: pulumist.SummaryEvent.ResourceChangesEntry
 }
 var file_pulumist_proto_depIdxs = []int32{
-	11, // 0: pulumist.PulumiRequest.resources:type_name -> pulumist.Resource
-	30, // 1: pulumist.PulumiRequest.config:type_name -> pulumist.PulumiRequest.ConfigEntry
-	1,  // 2: pulumist.PulumiRequest.pulumi_config:type_name -> pulumist.PulumiConfiguration
-	2,  // 3: pulumist.PulumiConfiguration.secrets_provider:type_name -> pulumist.SecretsProvider
-	6,  // 4: pulumist.PulumiConfiguration.backend:type_name -> pulumist.BackendConfig
-	31, // 5: pulumist.PulumiConfiguration.environment:type_name -> pulumist.PulumiConfiguration.EnvironmentEntry
-	3,  // 6: pulumist.SecretsProvider.passphrase:type_name -> pulumist.PassphraseProvider
-	4,  // 7: pulumist.SecretsProvider.cloud_kms:type_name -> pulumist.CloudKmsProvider
-	5,  // 8: pulumist.SecretsProvider.local:type_name -> pulumist.LocalProvider
-	32, // 9: pulumist.CloudKmsProvider.credentials:type_name -> pulumist.CloudKmsProvider.CredentialsEntry
-	7,  // 10: pulumist.BackendConfig.local:type_name -> pulumist.LocalBackend
-	8,  // 11: pulumist.BackendConfig.cloud:type_name -> pulumist.CloudBackend
-	9,  // 12: pulumist.BackendConfig.s3:type_name -> pulumist.S3Backend
-	10, // 13: pulumist.BackendConfig.azure_blob:type_name -> pulumist.AzureBlobBackend
-	33, // 14: pulumist.Resource.properties:type_name -> pulumist.Resource.PropertiesEntry
-	13, // 15: pulumist.Value.list_value:type_name -> pulumist.ValueList
-	14, // 16: pulumist.Value.map_value:type_name -> pulumist.ValueMap
-	12, // 17: pulumist.ValueList.values:type_name -> pulumist.Value
-	34, // 18: pulumist.ValueMap.fields:type_name -> pulumist.ValueMap.FieldsEntry
-	16, // 19: pulumist.PulumiResponse.outputs:type_name -> pulumist.OutputItem
-	12, // 20: pulumist.OutputItem.value:type_name -> pulumist.Value
-	18, // 21: pulumist.Event.prelude:type_name -> pulumist.PreludeEvent
-	19, // 22: pulumist.Event.summary:type_name -> pulumist.SummaryEvent
-	20, // 23: pulumist.Event.resource_pre:type_name -> pulumist.ResourcePreEvent
-	21, // 24: pulumist.Event.resource_outputs:type_name -> pulumist.ResOutputsEvent
-	22, // 25: pulumist.Event.resource_failed:type_name -> pulumist.ResOpFailedEvent
-	23, // 26: pulumist.Event.diagnostic:type_name -> pulumist.DiagnosticEvent
-	24, // 27: pulumist.Event.policy:type_name -> pulumist.PolicyEvent
-	26, // 28: pulumist.Event.progress:type_name -> pulumist.ProgressEvent
-	35, // 29: pulumist.PreludeEvent.config:type_name -> pulumist.PreludeEvent.ConfigEntry
-	36, // 30: pulumist.SummaryEvent.resource_changes:type_name -> pulumist.SummaryEvent.ResourceChangesEntry
-	27, // 31: pulumist.ResourcePreEvent.metadata:type_name -> pulumist.ResourceMetadata
-	27, // 32: pulumist.ResOutputsEvent.metadata:type_name -> pulumist.ResourceMetadata
-	27, // 33: pulumist.ResOpFailedEvent.metadata:type_name -> pulumist.ResourceMetadata
-	25, // 34: pulumist.PolicyEvent.policy_config:type_name -> pulumist.PolicyConfig
-	12, // 35: pulumist.Resource.PropertiesEntry.value:type_name -> pulumist.Value
-	12, // 36: pulumist.ValueMap.FieldsEntry.value:type_name -> pulumist.Value
-	37, // [37:37] is the sub-list for method output_type
-	37, // [37:37] is the sub-list for method input_type
-	37, // [37:37] is the sub-list for extension type_name
-	37, // [37:37] is the sub-list for extension extendee
-	0,  // [0:37] is the sub-list for field type_name
+	15, // 0: pulumist.PulumiRequest.resources:type_name -> pulumist.Resource
+	40, // 1: pulumist.PulumiRequest.config:type_name -> pulumist.PulumiRequest.ConfigEntry
+	5,  // 2: pulumist.PulumiRequest.pulumi_config:type_name -> pulumist.PulumiConfiguration
+	3,  // 3: pulumist.PulumiRequest.imports:type_name -> pulumist.ImportResource
+	2,  // 4: pulumist.PulumiRequest.outputs:type_name -> pulumist.StackOutput
+	1,  // 5: pulumist.PulumiRequest.invokes:type_name -> pulumist.Invoke
+	41, // 6: pulumist.Invoke.args:type_name -> pulumist.Invoke.ArgsEntry
+	18, // 7: pulumist.StackOutput.value:type_name -> pulumist.Value
+	6,  // 8: pulumist.PulumiConfiguration.secrets_provider:type_name -> pulumist.SecretsProvider
+	10, // 9: pulumist.PulumiConfiguration.backend:type_name -> pulumist.BackendConfig
+	42, // 10: pulumist.PulumiConfiguration.environment:type_name -> pulumist.PulumiConfiguration.EnvironmentEntry
+	7,  // 11: pulumist.SecretsProvider.passphrase:type_name -> pulumist.PassphraseProvider
+	8,  // 12: pulumist.SecretsProvider.cloud_kms:type_name -> pulumist.CloudKmsProvider
+	9,  // 13: pulumist.SecretsProvider.local:type_name -> pulumist.LocalProvider
+	43, // 14: pulumist.CloudKmsProvider.credentials:type_name -> pulumist.CloudKmsProvider.CredentialsEntry
+	11, // 15: pulumist.BackendConfig.local:type_name -> pulumist.LocalBackend
+	12, // 16: pulumist.BackendConfig.cloud:type_name -> pulumist.CloudBackend
+	13, // 17: pulumist.BackendConfig.s3:type_name -> pulumist.S3Backend
+	14, // 18: pulumist.BackendConfig.azure_blob:type_name -> pulumist.AzureBlobBackend
+	44, // 19: pulumist.Resource.properties:type_name -> pulumist.Resource.PropertiesEntry
+	17, // 20: pulumist.Resource.custom_timeouts:type_name -> pulumist.CustomTimeouts
+	16, // 21: pulumist.Resource.aliases:type_name -> pulumist.Alias
+	45, // 22: pulumist.Resource.component_outputs:type_name -> pulumist.Resource.ComponentOutputsEntry
+	19, // 23: pulumist.Value.list_value:type_name -> pulumist.ValueList
+	20, // 24: pulumist.Value.map_value:type_name -> pulumist.ValueMap
+	18, // 25: pulumist.ValueList.values:type_name -> pulumist.Value
+	46, // 26: pulumist.ValueMap.fields:type_name -> pulumist.ValueMap.FieldsEntry
+	26, // 27: pulumist.PulumiResponse.outputs:type_name -> pulumist.OutputItem
+	22, // 28: pulumist.PulumiResponse.result:type_name -> pulumist.OperationResult
+	47, // 29: pulumist.OperationResult.resource_changes:type_name -> pulumist.OperationResult.ResourceChangesEntry
+	48, // 30: pulumist.OperationResult.outputs:type_name -> pulumist.OperationResult.OutputsEntry
+	23, // 31: pulumist.OperationResult.resources:type_name -> pulumist.ResourceState
+	24, // 32: pulumist.OperationResult.imported:type_name -> pulumist.ImportedResource
+	25, // 33: pulumist.OperationResult.failures:type_name -> pulumist.ImportFailure
+	49, // 34: pulumist.ResourceState.outputs:type_name -> pulumist.ResourceState.OutputsEntry
+	50, // 35: pulumist.ImportedResource.inputs:type_name -> pulumist.ImportedResource.InputsEntry
+	18, // 36: pulumist.OutputItem.value:type_name -> pulumist.Value
+	28, // 37: pulumist.Event.prelude:type_name -> pulumist.PreludeEvent
+	29, // 38: pulumist.Event.summary:type_name -> pulumist.SummaryEvent
+	30, // 39: pulumist.Event.resource_pre:type_name -> pulumist.ResourcePreEvent
+	31, // 40: pulumist.Event.resource_outputs:type_name -> pulumist.ResOutputsEvent
+	32, // 41: pulumist.Event.resource_failed:type_name -> pulumist.ResOpFailedEvent
+	33, // 42: pulumist.Event.diagnostic:type_name -> pulumist.DiagnosticEvent
+	34, // 43: pulumist.Event.policy:type_name -> pulumist.PolicyEvent
+	36, // 44: pulumist.Event.progress:type_name -> pulumist.ProgressEvent
+	51, // 45: pulumist.PreludeEvent.config:type_name -> pulumist.PreludeEvent.ConfigEntry
+	52, // 46: pulumist.SummaryEvent.resource_changes:type_name -> pulumist.SummaryEvent.ResourceChangesEntry
+	37, // 47: pulumist.ResourcePreEvent.metadata:type_name -> pulumist.ResourceMetadata
+	37, // 48: pulumist.ResOutputsEvent.metadata:type_name -> pulumist.ResourceMetadata
+	37, // 49: pulumist.ResOpFailedEvent.metadata:type_name -> pulumist.ResourceMetadata
+	35, // 50: pulumist.PolicyEvent.policy_config:type_name -> pulumist.PolicyConfig
+	4,  // 51: pulumist.PulumiRequest.ConfigEntry.value:type_name -> pulumist.ConfigValue
+	18, // 52: pulumist.Invoke.ArgsEntry.value:type_name -> pulumist.Value
+	18, // 53: pulumist.Resource.PropertiesEntry.value:type_name -> pulumist.Value
+	18, // 54: pulumist.Resource.ComponentOutputsEntry.value:type_name -> pulumist.Value
+	18, // 55: pulumist.ValueMap.FieldsEntry.value:type_name -> pulumist.Value
+	18, // 56: pulumist.OperationResult.OutputsEntry.value:type_name -> pulumist.Value
+	18, // 57: pulumist.ResourceState.OutputsEntry.value:type_name -> pulumist.Value
+	18, // 58: pulumist.ImportedResource.InputsEntry.value:type_name -> pulumist.Value
+	59, // [59:59] is the sub-list for method output_type
+	59, // [59:59] is the sub-list for method input_type
+	59, // [59:59] is the sub-list for extension type_name
+	59, // [59:59] is the sub-list for extension extendee
+	0,  // [0:59] is the sub-list for field type_name
 }
 
 func init() { file_pulumist_proto_init() }
@@ -2369,18 +3575,18 @@ func file_pulumist_proto_init() {
 	if File_pulumist_proto != nil {
 		return
 	}
-	file_pulumist_proto_msgTypes[2].OneofWrappers = []any{
+	file_pulumist_proto_msgTypes[6].OneofWrappers = []any{
 		(*SecretsProvider_Passphrase)(nil),
 		(*SecretsProvider_CloudKms)(nil),
 		(*SecretsProvider_Local)(nil),
 	}
-	file_pulumist_proto_msgTypes[6].OneofWrappers = []any{
+	file_pulumist_proto_msgTypes[10].OneofWrappers = []any{
 		(*BackendConfig_Local)(nil),
 		(*BackendConfig_Cloud)(nil),
 		(*BackendConfig_S3)(nil),
 		(*BackendConfig_AzureBlob)(nil),
 	}
-	file_pulumist_proto_msgTypes[12].OneofWrappers = []any{
+	file_pulumist_proto_msgTypes[18].OneofWrappers = []any{
 		(*Value_StringValue)(nil),
 		(*Value_IntValue)(nil),
 		(*Value_DoubleValue)(nil),
@@ -2389,7 +3595,7 @@ func file_pulumist_proto_init() {
 		(*Value_MapValue)(nil),
 		(*Value_BytesValue)(nil),
 	}
-	file_pulumist_proto_msgTypes[17].OneofWrappers = []any{
+	file_pulumist_proto_msgTypes[27].OneofWrappers = []any{
 		(*Event_Prelude)(nil),
 		(*Event_Summary)(nil),
 		(*Event_ResourcePre)(nil),
@@ -2405,7 +3611,7 @@ func file_pulumist_proto_init() {
 			GoPackagePath: reflect.TypeOf(x{}).PkgPath(),
 			RawDescriptor: unsafe.Slice(unsafe.StringData(file_pulumist_proto_rawDesc), len(file_pulumist_proto_rawDesc)),
 			NumEnums:      0,
-			NumMessages:   37,
+			NumMessages:   53,
 			NumExtensions: 0,
 			NumServices:   0,
 		},
@@ -0,0 +1,273 @@
+package main
+
+import "C"
+import (
+	"context"
+	"fmt"
+	pb "github.com/evorine/pulumist/pulumist-go/generated"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto/events"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto/optimport"
+	"github.com/pulumi/pulumi/sdk/v3/go/common/apitype"
+	"github.com/pulumi/pulumi/sdk/v3/go/pulumi"
+	"google.golang.org/protobuf/proto"
+	"strings"
+	"sync"
+	"unsafe"
+)
+
+// PulumiDynamicImport adopts existing cloud resources into the stack state, like pulumi import --file.
+// The resources are read from the cloud and recorded in the state; nothing is created or modified.
+//
+// Parameters:
+//   - @param request: Pointer to protobuf-encoded PulumiRequest data with the resources to import in imports
+//   - @param length: Length of the request data in bytes
+//
+// Returns a pointer to C-allocated memory containing length-prefixed PulumiResponse.
+// The caller must free this memory using PulumiFree.
+//
+// The response result lists the imported resources, with their secret inputs still encrypted, and the resources
+// that could not be imported. Failures of individual resources do not fail the response.
+//
+//export PulumiDynamicImport
+func PulumiDynamicImport(requestBytes *C.char, requestLen C.int) *C.char {
+	// Convert C bytes to Go bytes safely
+	goBytes := C.GoBytes(unsafe.Pointer(requestBytes), requestLen)
+
+	// Deserialize protobuf request
+	var request pb.PulumiRequest
+	if err := proto.Unmarshal(goBytes, &request); err != nil {
+		return createFailedResponse(err)
+	}
+	if len(request.Imports) == 0 {
+		return createFailedResponse(fmt.Errorf("no resources to import"))
+	}
+
+	// Create context for cancellation and the operation deadline
+	ctx, finish := startOperation(request.OperationId, request.TimeoutSeconds)
+	defer finish()
+
+	// Ensure that the working directory exists
+	workDir, err := ensureWorkingDirectory(request.ProjectName)
+	if err != nil {
+		return createFailedResponse(fmt.Errorf("failed to ensure working directory: %w", err))
+	}
+
+	opts, err := workspaceOptions(workDir, request.PulumiConfig)
+	if err != nil {
+		return createFailedResponse(err)
+	}
+
+	// Import into a new stack as well, so the first operation on a stack can adopt existing infrastructure.
+	// Import does not run the program.
+	stack, err := auto.UpsertStackInlineSource(ctx, request.StackName, request.ProjectName,
+		func(ctx *pulumi.Context) error { return nil },
+		opts...,
+	)
+	if err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
+	}
+	attachStack(request.OperationId, stack)
+
+	// Providers may need configuration (e.g. credentials or region) to read resources
+	if err := applyStackConfig(ctx, stack, request.Config); err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
+	}
+
+	before, err := readDeployment(ctx, stack)
+	if err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
+	}
+
+	tracker := newPendingResourceTracker()
+	failed := newImportFailureTracker()
+	forwarder := newEngineEventForwarder(request.OperationId)
+	defer forwarder.wait()
+	importResult, importErr := stack.ImportResources(ctx,
+		optimport.Resources(importResources(request.Imports)),
+		optimport.NameTable(importNameTable(request.Imports, before)),
+		optimport.GenerateCode(false),
+		optimport.Protect(protectImported),
+		optimport.EventStreams(tracker.events, failed.events, forwarder.events),
+	)
+	if ctx.Err() != nil {
+		return createOperationFailedResponse(ctx, importErr, tracker)
+	}
+
+	after, err := readDeployment(ctx, stack)
+	if err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
+	}
+
+	imported, failures := matchImportedResources(request.Imports, after, failed)
+
+	// An error that cannot be attributed to a resource, e.g. a missing provider plugin, fails the whole import
+	if importErr != nil && len(failures) == 0 {
+		return createOperationFailedResponse(ctx, importErr, nil)
+	}
+
+	return createResultResponse(&pb.OperationResult{
+		Stdout:   importResult.StdOut,
+		Stderr:   importResult.StdErr,
+		Imported: imported,
+		Failures: failures,
+	})
+}
+
+// protectImported is passed to the import and reported with every imported resource. Imported resources are
+// protected like pulumi import does by default, so a deployment that does not declare them cannot delete them.
+const protectImported = true
+
+func importResources(imports []*pb.ImportResource) []*optimport.ImportResource {
+	resources := make([]*optimport.ImportResource, len(imports))
+	for i, entry := range imports {
+		resources[i] = &optimport.ImportResource{
+			Type:     entry.Type,
+			Name:     entry.Name,
+			ID:       entry.Id,
+			Parent:   entry.Parent,
+			Provider: entry.Provider,
+		}
+	}
+	return resources
+}
+
+// importNameTable maps the parent and provider names referenced by the imports to the URNs of the resources
+// already in the stack. Names of resources in the same import are resolved by Pulumi itself.
+func importNameTable(imports []*pb.ImportResource, deployment apitype.DeploymentV3) map[string]string {
+	importing := make(map[string]struct{}, len(imports))
+	referenced := make(map[string]struct{})
+	for _, entry := range imports {
+		importing[entry.Name] = struct{}{}
+		if entry.Parent != "" {
+			referenced[entry.Parent] = struct{}{}
+		}
+		if entry.Provider != "" {
+			referenced[entry.Provider] = struct{}{}
+		}
+	}
+
+	nameTable := make(map[string]string)
+	for _, resource := range deployment.Resources {
+		name := urnName(string(resource.URN))
+		if _, ok := referenced[name]; !ok {
+			continue
+		}
+		if _, ok := importing[name]; ok {
+			continue
+		}
+		nameTable[name] = string(resource.URN)
+	}
+	return nameTable
+}
+
+// matchImportedResources looks up every requested import in the stack state. Imports that are not in the
+// state failed; their message is taken from the error diagnostics the engine reported for the resource.
+func matchImportedResources(imports []*pb.ImportResource, deployment apitype.DeploymentV3, failed *importFailureTracker) ([]*pb.ImportedResource, []*pb.ImportFailure) {
+	var imported []*pb.ImportedResource
+	var failures []*pb.ImportFailure
+
+	for _, entry := range imports {
+		var match *apitype.ResourceV3
+		for i := range deployment.Resources {
+			resource := &deployment.Resources[i]
+			if string(resource.Type) == entry.Type && urnName(string(resource.URN)) == entry.Name && string(resource.ID) == entry.Id {
+				match = resource
+				break
+			}
+		}
+
+		if match == nil {
+			failures = append(failures, &pb.ImportFailure{
+				Type:    entry.Type,
+				Name:    entry.Name,
+				Id:      entry.Id,
+				Message: failed.message(entry.Type, entry.Name),
+			})
+			continue
+		}
+
+		inputs := make(map[string]*pb.Value, len(match.Inputs))
+		for key, value := range match.Inputs {
+			inputs[key] = convertInterfaceToProtoValue(value)
+		}
+
+		imported = append(imported, &pb.ImportedResource{
+			Type:     entry.Type,
+			Name:     entry.Name,
+			Id:       entry.Id,
+			Urn:      string(match.URN),
+			Parent:   entry.Parent,
+			Provider: entry.Provider,
+			Protect:  protectImported,
+			Inputs:   inputs,
+		})
+	}
+
+	return imported, failures
+}
+
+// importFailureTracker follows the engine event stream of an import and collects the error diagnostics of
+// every resource whose import failed, keyed by URN.
+//
+// Like pendingResourceTracker, pass tracker.events to the operation's EventStreams option and use a tracker
+// for a single operation only.
+type importFailureTracker struct {
+	events chan events.EngineEvent
+	done   chan struct{}
+
+	mu          sync.Mutex
+	failed      map[string]struct{}
+	diagnostics map[string][]string
+}
+
+func newImportFailureTracker() *importFailureTracker {
+	tracker := &importFailureTracker{
+		events:      make(chan events.EngineEvent),
+		done:        make(chan struct{}),
+		failed:      make(map[string]struct{}),
+		diagnostics: make(map[string][]string),
+	}
+
+	go func() {
+		defer close(tracker.done)
+		for event := range tracker.events {
+			switch {
+			case event.ResOpFailedEvent != nil:
+				tracker.fail(event.ResOpFailedEvent.Metadata.URN, "")
+			case event.DiagnosticEvent != nil && event.DiagnosticEvent.Severity == "error" && event.DiagnosticEvent.URN != "":
+				tracker.fail(event.DiagnosticEvent.URN, strings.TrimSpace(event.DiagnosticEvent.Message))
+			}
+		}
+	}()
+
+	return tracker
+}
+
+func (t *importFailureTracker) fail(urn string, diagnostic string) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	t.failed[urn] = struct{}{}
+	if diagnostic != "" {
+		t.diagnostics[urn] = append(t.diagnostics[urn], diagnostic)
+	}
+}
+
+// message returns the error diagnostics of the failed resource with the given type and name, or a generic
+// message if the engine reported none. It waits until the automation API has closed the event stream.
+func (t *importFailureTracker) message(resourceType string, name string) string {
+	<-t.done
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	for urn := range t.failed {
+		if urnType(urn) == resourceType && urnName(urn) == name {
+			if diagnostics := t.diagnostics[urn]; len(diagnostics) > 0 {
+				return strings.Join(diagnostics, "\n")
+			}
+			return "resource import failed"
+		}
+	}
+	return "resource was not imported"
+}
@@ -0,0 +1,120 @@
+package main
+
+import "C"
+import (
+	"fmt"
+	pb "github.com/evorine/pulumist/pulumist-go/generated"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto/optpreview"
+	"github.com/pulumi/pulumi/sdk/v3/go/pulumi"
+	"google.golang.org/protobuf/proto"
+	"os"
+	"path"
+	"path/filepath"
+	"time"
+	"unsafe"
+)
+
+// PulumiDynamicInvoke calls a single provider function, e.g. to look up an existing resource or the current
+// account, outside of a deployment. Provider functions can only be called from a program, so the function is
+// called by the program of a preview of a throwaway stack. That stack lives in a temporary directory with a local
+// backend and a copy of the stack configuration, and is removed afterwards; the named stack is never selected.
+//
+// Parameters:
+//   - @param request: Pointer to protobuf-encoded PulumiRequest data with the function to call in invokes
+//   - @param length: Length of the request data in bytes
+//
+// Returns a pointer to C-allocated memory containing length-prefixed PulumiResponse.
+// The caller must free this memory using PulumiFree.
+//
+// The result of the function is returned in the outputs of the operation result.
+//
+//export PulumiDynamicInvoke
+func PulumiDynamicInvoke(requestBytes *C.char, requestLen C.int) *C.char {
+	// Convert C bytes to Go bytes safely
+	goBytes := C.GoBytes(unsafe.Pointer(requestBytes), requestLen)
+
+	// Deserialize protobuf request
+	var request pb.PulumiRequest
+	if err := proto.Unmarshal(goBytes, &request); err != nil {
+		return createFailedResponse(err)
+	}
+	if len(request.Invokes) != 1 {
+		return createFailedResponse(fmt.Errorf("expected one function to invoke, got %d", len(request.Invokes)))
+	}
+	invoke := request.Invokes[0]
+	if invoke.Provider != "" {
+		return createFailedResponse(fmt.Errorf("invoke %s: functions invoked outside of a deployment use the default provider", invoke.Name))
+	}
+
+	// Create context for cancellation and the operation deadline
+	ctx, finish := startOperation(request.OperationId, request.TimeoutSeconds)
+	defer finish()
+
+	// The working directory and the state of the throwaway stack are removed with the temporary directory
+	tempDir, err := os.MkdirTemp("", "pulumist-invoke-")
+	if err != nil {
+		return createFailedResponse(fmt.Errorf("failed to create invoke directory: %w", err))
+	}
+	defer os.RemoveAll(tempDir)
+
+	opts, err := workspaceOptions(tempDir, invokeConfiguration(request.PulumiConfig, filepath.Join(tempDir, "state")))
+	if err != nil {
+		return createFailedResponse(err)
+	}
+
+	// The program only calls the function; the preview runs it in this process, so the result can be captured
+	var result map[string]interface{}
+	program := func(ctx *pulumi.Context) error {
+		var err error
+		result, err = invokeFunction(ctx, invoke, nil)
+		return err
+	}
+
+	stackName := fmt.Sprintf("%s-invoke-%d", path.Base(request.StackName), request.OperationId)
+	stack, err := auto.UpsertStackInlineSource(ctx, stackName, request.ProjectName, program, opts...)
+	if err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
+	}
+	attachStack(request.OperationId, stack)
+
+	// Providers may need configuration (e.g. credentials or region) to call the function
+	if err := applyStackConfig(ctx, stack, request.Config); err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
+	}
+
+	var previewOpts []optpreview.Option
+	if logging := debugLogging(request.PulumiConfig); logging != nil {
+		previewOpts = append(previewOpts, optpreview.DebugLogging(*logging))
+	}
+	started := time.Now()
+	preview, err := stack.Preview(ctx, previewOpts...)
+	if err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
+	}
+
+	outputs := make(map[string]*pb.Value, len(result))
+	for name, value := range result {
+		outputs[name] = convertInterfaceToProtoValue(value)
+	}
+
+	return createResultResponse(&pb.OperationResult{
+		Stdout:          preview.StdOut,
+		Stderr:          preview.StdErr,
+		Outputs:         outputs,
+		DurationSeconds: time.Since(started).Seconds(),
+	})
+}
+
+// invokeConfiguration returns a copy of config that keeps the secrets provider, environment and Pulumi home but
+// stores the stack state in the local backend at statePath.
+func invokeConfiguration(config *pb.PulumiConfiguration, statePath string) *pb.PulumiConfiguration {
+	invokeConfig := &pb.PulumiConfiguration{}
+	if config != nil {
+		invokeConfig = proto.Clone(config).(*pb.PulumiConfiguration)
+	}
+	invokeConfig.Backend = &pb.BackendConfig{
+		Backend: &pb.BackendConfig_Local{Local: &pb.LocalBackend{Path: statePath}},
+	}
+	return invokeConfig
+}
@@ -0,0 +1,126 @@
+package main
+
+import "C"
+import (
+	"context"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto"
+	"sync"
+	"time"
+)
+
+// stackCancelTimeout bounds how long a backend-side cancel (pulumi cancel) may take.
+const stackCancelTimeout = 30 * time.Second
+
+// pendingCancelExpiry is how long a cancel request for an operation that has not started is kept. The request of
+// a cancelled operation reaches Go within milliseconds, so older entries belong to operations that had already
+// finished when the cancel arrived and are dropped.
+const pendingCancelExpiry = time.Minute
+
+// runningOperation is an in-flight operation that can be cancelled by the host.
+type runningOperation struct {
+	cancel context.CancelFunc
+	// stack is set once the operation has selected its stack, so cancellation can also be sent to the backend.
+	stack *auto.Stack
+}
+
+var (
+	// operationsMu guards operations and cancelledOperations.
+	operationsMu sync.Mutex
+	// Running operations keyed by PulumiRequest.operation_id.
+	operations = make(map[uint64]*runningOperation)
+	// Cancel requests that arrived before their operation started, with the time they arrived. The host may cancel
+	// an operation while its request is still crossing the FFI boundary, so these are applied when the operation
+	// registers.
+	cancelledOperations = make(map[uint64]time.Time)
+)
+
+// startOperation creates the context of an operation, which is cancelled when the host calls PulumiDynamicCancel
+// with the same operation ID or, if timeoutSeconds is not 0, once the timeout elapses.
+// The returned function must be called once the operation finishes.
+func startOperation(operationID uint64, timeoutSeconds uint32) (context.Context, func()) {
+	var ctx context.Context
+	var cancel context.CancelFunc
+	if timeoutSeconds > 0 {
+		ctx, cancel = context.WithTimeout(context.Background(), time.Duration(timeoutSeconds)*time.Second)
+	} else {
+		ctx, cancel = context.WithCancel(context.Background())
+	}
+
+	operationsMu.Lock()
+	defer operationsMu.Unlock()
+
+	if _, cancelled := cancelledOperations[operationID]; cancelled {
+		delete(cancelledOperations, operationID)
+		cancel()
+	}
+	operations[operationID] = &runningOperation{cancel: cancel}
+
+	return ctx, func() {
+		operationsMu.Lock()
+		delete(operations, operationID)
+		pruneCancelledOperations(time.Now())
+		operationsMu.Unlock()
+		cancel()
+	}
+}
+
+// pruneCancelledOperations drops the cancel requests older than pendingCancelExpiry.
+// A cancel that races with the end of its operation is recorded after the operation finished and would
+// otherwise never be removed. operationsMu must be held.
+func pruneCancelledOperations(now time.Time) {
+	for id, cancelledAt := range cancelledOperations {
+		if now.Sub(cancelledAt) > pendingCancelExpiry {
+			delete(cancelledOperations, id)
+		}
+	}
+}
+
+// attachStack records the stack an operation runs against, so that cancelling it also cancels the update on the backend.
+func attachStack(operationID uint64, stack auto.Stack) {
+	operationsMu.Lock()
+	defer operationsMu.Unlock()
+
+	if operation, ok := operations[operationID]; ok {
+		operation.stack = &stack
+	}
+}
+
+// PulumiDynamicCancel cancels the operation started with the given operation ID.
+//
+// Cancelling the context makes the automation API interrupt the running pulumi CLI, which stops scheduling new
+// steps and writes a checkpoint for the ones in flight rather than leaving the state half-written.
+// For backends that support it (Pulumi Cloud), the update is also cancelled on the backend via stack.Cancel.
+//
+// The operation returns a response with cancelled=true. Cancelling an unknown operation is recorded and applied
+// if an operation with that ID starts within pendingCancelExpiry.
+//
+// Parameters:
+//   - @param operationID: The operation_id of the PulumiRequest to cancel
+//
+//export PulumiDynamicCancel
+func PulumiDynamicCancel(operationID C.ulonglong) {
+	id := uint64(operationID)
+
+	operationsMu.Lock()
+	operation, running := operations[id]
+	if !running {
+		now := time.Now()
+		pruneCancelledOperations(now)
+		cancelledOperations[id] = now
+		operationsMu.Unlock()
+		return
+	}
+	stack := operation.stack
+	operationsMu.Unlock()
+
+	operation.cancel()
+
+	if stack != nil {
+		go func() {
+			// Self-managed backends do not support remote cancellation and return an error, which is safe to ignore.
+			ctx, cancel := context.WithTimeout(context.Background(), stackCancelTimeout)
+			defer cancel()
+			_ = stack.Cancel(ctx)
+		}()
+	}
+}
@@ -0,0 +1,73 @@
+package main
+
+import (
+	"github.com/pulumi/pulumi/sdk/v3/go/auto/events"
+	"sort"
+	"sync"
+	"time"
+)
+
+// pendingDrainTimeout bounds how long pendingURNs waits for the engine event stream to be closed.
+const pendingDrainTimeout = time.Second
+
+// pendingResourceTracker follows the engine event stream of a single stack operation and records
+// the resources whose steps have started but not yet finished.
+//
+// Pass tracker.events to the operation's EventStreams option. The automation API closes the channel
+// once the operation returns, so a tracker must not be reused across operations.
+type pendingResourceTracker struct {
+	events chan events.EngineEvent
+	done   chan struct{}
+
+	mu      sync.Mutex
+	pending map[string]struct{}
+}
+
+func newPendingResourceTracker() *pendingResourceTracker {
+	tracker := &pendingResourceTracker{
+		events:  make(chan events.EngineEvent),
+		done:    make(chan struct{}),
+		pending: make(map[string]struct{}),
+	}
+
+	go func() {
+		defer close(tracker.done)
+		for event := range tracker.events {
+			tracker.observe(event)
+		}
+	}()
+
+	return tracker
+}
+
+func (t *pendingResourceTracker) observe(event events.EngineEvent) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	switch {
+	case event.ResourcePreEvent != nil:
+		t.pending[event.ResourcePreEvent.Metadata.URN] = struct{}{}
+	case event.ResOutputsEvent != nil:
+		delete(t.pending, event.ResOutputsEvent.Metadata.URN)
+	case event.ResOpFailedEvent != nil:
+		delete(t.pending, event.ResOpFailedEvent.Metadata.URN)
+	}
+}
+
+// pendingURNs returns the sorted URNs of resources that were still in progress when the operation stopped.
+func (t *pendingResourceTracker) pendingURNs() []string {
+	select {
+	case <-t.done:
+	case <-time.After(pendingDrainTimeout):
+	}
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	urns := make([]string, 0, len(t.pending))
+	for urn := range t.pending {
+		urns = append(urns, urn)
+	}
+	sort.Strings(urns)
+	return urns
+}
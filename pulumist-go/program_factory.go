@@ -1,35 +1,37 @@
 package main
 
 import (
+	"encoding/json"
 	"fmt"
 	pb "github.com/evorine/pulumist/pulumist-go/generated"
 	"github.com/pulumi/pulumi/sdk/v3/go/pulumi"
+	"reflect"
 	"regexp"
+	"sort"
+	"strconv"
 	"strings"
 )
 
-// createDeploymentProgram creates a Pulumi program from dynamic resources
-func createDeploymentProgram(resources []*pb.Resource) pulumi.RunFunc {
+// createDeploymentProgram creates a Pulumi program from dynamic resources that exports the given stack outputs.
+// Events emitted by the program are tagged with operationID.
+func createDeploymentProgram(operationID uint64, resources []*pb.Resource, outputs []*pb.StackOutput, invokes []*pb.Invoke) pulumi.RunFunc {
 	return func(ctx *pulumi.Context) error {
 		resourceMap := make(map[string]pulumi.Resource)
 		resourceOutputs := make(map[string]pulumi.Output)
+		var components []registeredComponent
 
-		for _, res := range resources {
-			// Send pre-create event
-			emitEvent(&pb.Event{
-				Event: &pb.Event_ResourcePre{
-					ResourcePre: &pb.ResourcePreEvent{
-						Metadata: &pb.ResourceMetadata{
-							Op:   "create",
-							Urn:  fmt.Sprintf("urn:pulumi::%s::%s::%s::%s", ctx.Stack(), ctx.Project(), res.Type, res.Name),
-							Type: res.Type,
-							New:  true,
-						},
-						Planning: false,
-					},
-				},
-			})
+		// Read the outputs of other stacks referenced as ${stack:org/project/stack.output}
+		if err := readStackReferences(ctx, stackReferences(resources, outputs), resourceOutputs); err != nil {
+			return err
+		}
+
+		// Call the provider functions using the default providers first
+		// Their results can be referenced like resource outputs as ${invoke.field}
+		if err := callInvokes(ctx, invokes, "", resourceMap, resourceOutputs); err != nil {
+			return err
+		}
 
+		for _, res := range resources {
 			// Step 1: Convert protobuf properties to Go types
 			properties := make(map[string]interface{})
 			for k, v := range res.Properties {
@@ -59,9 +61,15 @@ func createDeploymentProgram(resources []*pb.Resource) pulumi.RunFunc {
 					if depRes, ok := resourceMap[depName]; ok {
 						deps = append(deps, depRes)
 					} else {
-						// Dependency not found.
-						// TODO: Send diagnostic event
-						fmt.Printf("Warning: Dependency %s not found for resource %s\n", depName, res.Name)
+						// The host orders resources by their dependencies, so the dependency is not part of the program
+						emitEvent(operationID, &pb.Event{
+							Event: &pb.Event_Diagnostic{
+								Diagnostic: &pb.DiagnosticEvent{
+									Severity: "warning",
+									Message:  fmt.Sprintf("Dependency %s not found for resource %s", depName, res.Name),
+								},
+							},
+						})
 					}
 				}
 				if len(deps) > 0 {
@@ -69,9 +77,72 @@ func createDeploymentProgram(resources []*pb.Resource) pulumi.RunFunc {
 				}
 			}
 
-			// Handle custom provider
+			// Handle custom timeouts for create/update/delete steps
+			if res.CustomTimeouts != nil {
+				opts = append(opts, pulumi.Timeouts(&pulumi.CustomTimeouts{
+					Create: res.CustomTimeouts.Create,
+					Update: res.CustomTimeouts.Update,
+					Delete: res.CustomTimeouts.Delete,
+				}))
+			}
+
+			// Handle lifecycle options
+			if res.Protect {
+				opts = append(opts, pulumi.Protect(true))
+			}
+			if len(res.IgnoreChanges) > 0 {
+				opts = append(opts, pulumi.IgnoreChanges(res.IgnoreChanges))
+			}
+			if res.RetainOnDelete {
+				opts = append(opts, pulumi.RetainOnDelete(true))
+			}
+			if len(res.ReplaceOnChanges) > 0 {
+				opts = append(opts, pulumi.ReplaceOnChanges(res.ReplaceOnChanges))
+			}
+			if res.DeleteBeforeReplace {
+				opts = append(opts, pulumi.DeleteBeforeReplace(true))
+			}
+			if len(res.AdditionalSecretOutputs) > 0 {
+				opts = append(opts, pulumi.AdditionalSecretOutputs(res.AdditionalSecretOutputs))
+			}
+			if res.DeletedWith != "" {
+				deletedWith, ok := resourceMap[res.DeletedWith]
+				if !ok {
+					return fmt.Errorf("resource %s to delete %s with is not a resource in this program", res.DeletedWith, res.Name)
+				}
+				opts = append(opts, pulumi.DeletedWith(deletedWith))
+			}
+
+			// Handle aliases, so renamed or moved resources keep their identity
+			if len(res.Aliases) > 0 {
+				aliases := make([]pulumi.Alias, 0, len(res.Aliases))
+				for _, entry := range res.Aliases {
+					alias, err := resourceAlias(entry, resourceMap)
+					if err != nil {
+						return fmt.Errorf("alias of resource %s: %w", res.Name, err)
+					}
+					aliases = append(aliases, alias)
+				}
+				opts = append(opts, pulumi.Aliases(aliases))
+			}
+
+			// Handle explicit provider instances
+			// The host registers them before the resources using them
 			if res.Provider != "" {
-				// TODO: Implement provider resolution
+				provider, ok := resourceMap[res.Provider].(pulumi.ProviderResource)
+				if !ok {
+					return fmt.Errorf("provider %s of resource %s is not a provider resource (pulumi:providers:<pkg>) in this program", res.Provider, res.Name)
+				}
+				opts = append(opts, pulumi.Provider(provider))
+			}
+
+			// Handle parent resources
+			if res.Parent != "" {
+				parent, ok := resourceMap[res.Parent]
+				if !ok {
+					return fmt.Errorf("parent %s of resource %s is not a resource in this program", res.Parent, res.Name)
+				}
+				opts = append(opts, pulumi.Parent(parent))
 			}
 
 			// Step 5: Register the resource with Pulumi
@@ -81,125 +152,333 @@ func createDeploymentProgram(resources []*pb.Resource) pulumi.RunFunc {
 			// 3. Send the inputs to the provider
 			// 4. Provider creates/updates the actual cloud resource
 			// 5. Return the resource state and outputs
-			var resource pulumi.CustomResourceState
-			err := ctx.RegisterResource(
-				res.Type,
-				res.Name,
-				inputs,
-				&resource, // Will be populated with resource state
-				opts...,
-			)
+			// Components have no provider and only group their children, which the host registers after them.
+			// Their outputs are registered once all resources are, as they usually reference the children.
+			if res.Component {
+				component := &dynamicComponentState{}
+				if err := ctx.RegisterComponentResource(res.Type, res.Name, component, opts...); err != nil {
+					return err
+				}
+				resourceMap[res.Name] = component
+				components = append(components, registeredComponent{resource: res, state: component})
+				continue
+			}
+
+			// Provider instances need a provider resource state so they can be passed to pulumi.Provider
+			var resource registeredResource = &dynamicResourceState{}
+			if strings.HasPrefix(res.Type, providerTypePrefix) {
+				resource = &dynamicProviderState{}
+			}
+			var err error
+			if res.Id != "" {
+				// Existing resources are read by ID and never created, updated or deleted by this stack.
+				// The inputs only qualify the lookup for providers that need more than the ID.
+				err = ctx.ReadResource(res.Type, res.Name, pulumi.ID(res.Id), inputs, resource, opts...)
+			} else {
+				err = ctx.RegisterResource(
+					res.Type,
+					res.Name,
+					inputs,
+					resource, // Will be populated with resource state
+					opts...,
+				)
+			}
 			if err != nil {
-				// Send failure event
-				emitEvent(&pb.Event{
-					Event: &pb.Event_ResourceFailed{
-						ResourceFailed: &pb.ResOpFailedEvent{
-							Metadata: &pb.ResourceMetadata{
-								Op:   "create",
-								Urn:  fmt.Sprintf("urn:pulumi::%s::%s::%s::%s", ctx.Stack(), ctx.Project(), res.Type, res.Name),
-								Type: res.Type,
-								New:  true,
-							},
-							Status: 1,
-							Steps:  0,
-						},
-					},
-				})
 				return err
 			}
 
-			// Send success event
-			emitEvent(&pb.Event{
-				Event: &pb.Event_ResourceOutputs{
-					ResourceOutputs: &pb.ResOutputsEvent{
-						Metadata: &pb.ResourceMetadata{
-							Op:   "create",
-							Urn:  fmt.Sprintf("urn:pulumi::%s::%s::%s::%s", ctx.Stack(), ctx.Project(), res.Type, res.Name),
-							Type: res.Type,
-							New:  true,
-						},
-						Planning: false,
-					},
-				},
-			})
-
 			// Store reference for dependencies
-			resourceMap[res.Name] = &resource
+			resourceMap[res.Name] = resource
 
 			// Step 6: Store outputs for reference resolution
 			// This is critical for ${resource.property} syntax to work.
-			//
-			// Properties storage strategy:
-			// 1. Store known outputs by name (resource.id, resource.name, etc.)
-			// 2. Create a map containing all properties
-			// 3. Handle provider-specific outputs
-
-			// First, store the universal ID that all resources have
-			// Every Pulumi resource has an ID after creation
 			resourceOutputs[res.Name+".id"] = resource.ID()
+			resourceOutputs[res.Name] = referenceableOutputs(resource, inputs)
 
-			// Create a special output that contains all the resource's properties
-			// This allows referencing any property like ${resource.propertyName}
-			//
-			// LIMITATION: Currently we're storing input properties as outputs.
-			// Ideally, we would:
-			// 1. Get actual outputs from the provider after creation
-			// 2. Store computed properties (like endpoints, URLs)
-			// 3. Handle properties that change during creation
-			//
-			// TODO: Use provider SDK to get real outputs
-			resourceAllOutputs := pulumi.All(resource.ID()).ApplyT(func(args []interface{}) (map[string]interface{}, error) {
-				// Return all the inputs as outputs so they can be referenced
-				outputMap := make(map[string]interface{})
-
-				// Add all input properties as potential outputs
-				// WARNING: These are inputs, not actual outputs from the provider
-				for key, value := range res.Properties {
-					outputMap[key] = convertProtoValueToInterface(value)
-				}
+			// Call the provider functions using this provider instance
+			// The host registers the provider before the resources referencing their results
+			if err := callInvokes(ctx, invokes, res.Name, resourceMap, resourceOutputs); err != nil {
+				return err
+			}
+		}
 
-				// Add the ID as a standard output
-				outputMap["id"] = args[0]
+		for _, invoke := range invokes {
+			if _, ok := resourceMap[invoke.Provider]; invoke.Provider != "" && !ok {
+				return fmt.Errorf("provider %s of invoke %s is not a provider resource (pulumi:providers:<pkg>) in this program", invoke.Provider, invoke.Name)
+			}
+		}
 
-				return outputMap, nil
-			}).(pulumi.MapOutput)
+		// Step 7: Register component outputs
+		// They can be referenced by stack outputs like the outputs of other resources
+		for _, component := range components {
+			registered := pulumi.Map{}
+			for key, value := range component.resource.ComponentOutputs {
+				registered[key] = convertInterfaceToPulumiValue(resolveValue(convertProtoValueToInterface(value), resourceOutputs))
+			}
+			if err := ctx.RegisterResourceOutputs(component.state, registered); err != nil {
+				return err
+			}
+			resourceOutputs[component.resource.Name] = registered.ToMapOutput()
+		}
 
-			// Step 6: Store outputs for reference resolution
-			// This is critical for ${resource.output} syntax to work
-
-			/* HANDLED DIFFERENTLY MAYBE? THE CODE ABOVE HANDLES THIS. TEST BEFORE REMOVING.
-
-			// Special case: Azure Resource Groups
-			// Resource groups are special because other Azure resources need
-			// the resource group name, but it's an input, not an output.
-			// We make it available as an output for convenience.
-			if res.Type == "azure-native:resources:ResourceGroup" {
-				if nameVal, ok := res.Properties["resourceGroupName"]; ok {
-					// Store as output so ${rg.resourceGroupName} works
-					resourceOutputs[res.Name+".resourceGroupName"] = pulumi.ToOutput(convertProtoValueToInterface(nameVal))
-				}
-				// Also store the name property for consistency
-				resourceOutputs[res.Name+".name"] = pulumi.String(res.Name).ToStringOutput()
+		// Step 8: Export stack outputs
+		// Output values may reference the resources registered above
+		for _, output := range outputs {
+			value := resolveValue(convertProtoValueToInterface(output.Value), resourceOutputs)
+			input := convertInterfaceToPulumiValue(value)
+			if output.Secret {
+				ctx.Export(output.Name, pulumi.ToSecret(input))
 			} else {
-				// For other resources, make all input properties available as outputs
-				// This is a convenience feature - in standard Pulumi, you'd need
-				// to explicitly export outputs. Here we make everything referenceable.
-				for key, value := range res.Properties {
-					outputKey := res.Name + "." + key
-					resourceOutputs[outputKey] = pulumi.ToOutput(convertProtoValueToInterface(value))
+				ctx.Export(output.Name, input)
+			}
+		}
+
+		return nil
+	}
+}
+
+// stackReferencePrefix starts references to the outputs of other stacks, e.g. ${stack:org/project/stack.output},
+// or ${stack:"org/project/stack.eu".output} for stack names containing "." or "[".
+const stackReferencePrefix = "stack:"
+
+// unquoteStackReference removes the quotes around the stack name of a stack reference name, so that
+// stack:"org/project/stack" and stack:org/project/stack name the same stack. Other names are returned unchanged.
+func unquoteStackReference(name string) string {
+	stack, ok := strings.CutPrefix(name, stackReferencePrefix)
+	if ok && len(stack) >= 2 && strings.HasPrefix(stack, `"`) && strings.HasSuffix(stack, `"`) {
+		return stackReferencePrefix + stack[1:len(stack)-1]
+	}
+	return name
+}
+
+// stackReferences collects the outputs of other stacks referenced by resource properties, component outputs and
+// stack outputs, by reference name ("stack:" followed by the unquoted stack name).
+func stackReferences(resources []*pb.Resource, outputs []*pb.StackOutput) map[string]map[string]struct{} {
+	references := make(map[string]map[string]struct{})
+	var collect func(value interface{})
+	collect = func(value interface{}) {
+		switch v := value.(type) {
+		case string:
+			for _, match := range referencePattern.FindAllStringSubmatch(v, -1) {
+				if !strings.HasPrefix(match[1], stackReferencePrefix) {
+					continue
 				}
+				// Invalid paths are kept as written by resolveReference
+				segments, err := parsePropertyPath(strings.TrimPrefix(match[2], "."))
+				if err != nil || segments[0].isIndex {
+					continue
+				}
+				name := unquoteStackReference(match[1])
+				if references[name] == nil {
+					references[name] = make(map[string]struct{})
+				}
+				references[name][segments[0].key] = struct{}{}
+			}
+		case map[string]interface{}:
+			for _, item := range v {
+				collect(item)
 			}
-			*/
+		case []interface{}:
+			for _, item := range v {
+				collect(item)
+			}
+		}
+	}
 
-			// Store the entire resource output map for complex references
-			resourceOutputs[res.Name] = resourceAllOutputs
+	for _, res := range resources {
+		for _, value := range res.Properties {
+			collect(convertProtoValueToInterface(value))
 		}
+		for _, value := range res.ComponentOutputs {
+			collect(convertProtoValueToInterface(value))
+		}
+	}
+	for _, output := range outputs {
+		collect(convertProtoValueToInterface(output.Value))
+	}
+	return references
+}
 
-		// TODO: Step 7: Export stack outputs
-		return nil
+// readStackReferences reads the referenced outputs of other stacks with pulumi.StackReference and stores them
+// for reference resolution under "stack:<stack>.<output>". Secret outputs stay secret. A stack that cannot be
+// read or an output it does not have fails the program.
+func readStackReferences(ctx *pulumi.Context, references map[string]map[string]struct{}, resourceOutputs map[string]pulumi.Output) error {
+	stacks := make([]string, 0, len(references))
+	for name := range references {
+		stacks = append(stacks, name)
 	}
+	sort.Strings(stacks)
+	for _, name := range stacks {
+		stack := strings.TrimPrefix(name, stackReferencePrefix)
+		reference, err := pulumi.NewStackReference(ctx, stack, nil)
+		if err != nil {
+			return fmt.Errorf("stack reference %s: %w", stack, err)
+		}
+		for _, output := range sortedKeys(references[name]) {
+			details, err := reference.GetOutputDetails(output)
+			if err != nil {
+				return fmt.Errorf("could not read stack %s: %w", stack, err)
+			}
+			switch {
+			case details.SecretValue != nil:
+				resourceOutputs[name+"."+output] = pulumi.ToSecret(pulumi.Any(details.SecretValue))
+			case details.Value != nil:
+				resourceOutputs[name+"."+output] = pulumi.Any(details.Value)
+			default:
+				return fmt.Errorf("stack %s has no output `%s`", stack, output)
+			}
+		}
+	}
+	return nil
+}
+
+// callInvokes calls the provider functions of the invokes using the provider instance named provider, or
+// the default providers when it is empty, and stores their results for reference resolution.
+func callInvokes(ctx *pulumi.Context, invokes []*pb.Invoke, provider string, resourceMap map[string]pulumi.Resource, resourceOutputs map[string]pulumi.Output) error {
+	for _, invoke := range invokes {
+		if invoke.Provider != provider {
+			continue
+		}
+		result, err := invokeFunction(ctx, invoke, resourceMap)
+		if err != nil {
+			return err
+		}
+		outputs := pulumi.Map{}
+		for key, value := range result {
+			outputs[key] = convertInterfaceToPulumiValue(value)
+		}
+		resourceOutputs[invoke.Name] = outputs.ToMapOutput()
+	}
+	return nil
+}
+
+// invokeFunction calls the provider function of an invoke with its literal arguments and returns its result.
+func invokeFunction(ctx *pulumi.Context, invoke *pb.Invoke, resourceMap map[string]pulumi.Resource) (map[string]interface{}, error) {
+	args := make(map[string]interface{}, len(invoke.Args))
+	for key, value := range invoke.Args {
+		args[key] = convertProtoValueToInterface(value)
+	}
+
+	var opts []pulumi.InvokeOption
+	if invoke.Provider != "" {
+		provider, ok := resourceMap[invoke.Provider].(pulumi.ProviderResource)
+		if !ok {
+			return nil, fmt.Errorf("provider %s of invoke %s is not a provider resource (pulumi:providers:<pkg>) in this program", invoke.Provider, invoke.Name)
+		}
+		opts = append(opts, pulumi.Provider(provider))
+	}
+
+	var result map[string]interface{}
+	if err := ctx.Invoke(invoke.Token, args, &result, opts...); err != nil {
+		return nil, fmt.Errorf("invoke %s (%s): %w", invoke.Name, invoke.Token, err)
+	}
+	return result, nil
+}
+
+// resourceAlias converts an alias, resolving the name of a previous parent in the program to its resource.
+// Unset fields default to the resource's current values.
+func resourceAlias(entry *pb.Alias, resourceMap map[string]pulumi.Resource) (pulumi.Alias, error) {
+	var alias pulumi.Alias
+	if entry.Urn != "" {
+		alias.URN = pulumi.URN(entry.Urn)
+		return alias, nil
+	}
+
+	if entry.Name != "" {
+		alias.Name = pulumi.String(entry.Name)
+	}
+	if entry.Type != "" {
+		alias.Type = pulumi.String(entry.Type)
+	}
+	if entry.Stack != "" {
+		alias.Stack = pulumi.String(entry.Stack)
+	}
+	if entry.Project != "" {
+		alias.Project = pulumi.String(entry.Project)
+	}
+
+	switch {
+	case entry.Parent != "":
+		parent, ok := resourceMap[entry.Parent]
+		if !ok {
+			return alias, fmt.Errorf("previous parent %s is not a resource in this program", entry.Parent)
+		}
+		alias.Parent = parent
+	case entry.ParentUrn != "":
+		alias.ParentURN = pulumi.URN(entry.ParentUrn)
+	case entry.NoParent:
+		alias.NoParent = pulumi.Bool(true)
+	}
+
+	return alias, nil
+}
+
+// dynamicComponentState is the state of a component resource, which groups its children in the state and in events.
+type dynamicComponentState struct {
+	pulumi.ResourceState
 }
 
+// registeredComponent is a component whose outputs are registered after all resources.
+type registeredComponent struct {
+	resource *pb.Resource
+	state    *dynamicComponentState
+}
+
+// providerTypePrefix starts the type of explicit provider instances, followed by the package name.
+const providerTypePrefix = "pulumi:providers:"
+
+// registeredResource is a resource registered by the program together with the outputs its provider returned.
+type registeredResource interface {
+	pulumi.CustomResource
+	outputs() pulumi.MapOutput
+}
+
+// dynamicResourceState is the state of a resource whose type is only known at runtime.
+// The SDK fills a field tagged `pulumi:""` with every output the provider returns, so
+// computed properties (endpoints, server-generated names, ...) can be referenced.
+type dynamicResourceState struct {
+	pulumi.CustomResourceState
+
+	Outputs pulumi.MapOutput `pulumi:""`
+}
+
+func (r *dynamicResourceState) outputs() pulumi.MapOutput {
+	return r.Outputs
+}
+
+// dynamicProviderState is the state of an explicit provider instance (pulumi:providers:<pkg>), which other
+// resources can use through pulumi.Provider.
+type dynamicProviderState struct {
+	pulumi.ProviderResourceState
+
+	Outputs pulumi.MapOutput `pulumi:""`
+}
+
+func (r *dynamicProviderState) outputs() pulumi.MapOutput {
+	return r.Outputs
+}
+
+// referenceableOutputs returns the properties that ${resource.property} references can read: the outputs
+// reported by the provider, plus the resolved inputs the provider does not echo back (e.g. the
+// resourceGroupName of an azure-native ResourceGroup), plus the resource's "id".
+func referenceableOutputs(resource registeredResource, inputs pulumi.Map) pulumi.MapOutput {
+	return pulumi.All(resource.ID(), inputs.ToMapOutput(), resource.outputs()).ApplyT(func(args []interface{}) (map[string]interface{}, error) {
+		outputMap := make(map[string]interface{})
+		for key, value := range args[1].(map[string]interface{}) {
+			outputMap[key] = value
+		}
+		for key, value := range args[2].(map[string]interface{}) {
+			outputMap[key] = value
+		}
+		outputMap["id"] = args[0]
+		return outputMap, nil
+	}).(pulumi.MapOutput)
+}
+
+// referencePattern matches ${resource.property} references. Group 1 is the resource name, or the stack reference
+// name with a quoted stack name as written, group 2 the property path starting at the "." or "[" that follows it.
+var referencePattern = regexp.MustCompile(`\$\{(stack:"[^"}]*"|[^.\[}]+)([.\[][^}]*)\}`)
+
 // resolveReferences resolves ${resource.property} references in properties.
 // This enables dynamic references between resources using interpolation syntax.
 //
@@ -208,18 +487,23 @@ func createDeploymentProgram(resources []*pb.Resource) pulumi.RunFunc {
 // 2. Extracts resource name and output name from the pattern
 // 3. Looks up the corresponding Pulumi Output in resourceOutputs map
 // 4. Replaces the string reference with the actual Output object
+// 5. Interpolates the referenced outputs into one string output when references are part of a longer string
 //
 // Example transformations:
 //
-//	"${my-rg.id}" -> resourceOutputs["my-rg.id"] (Pulumi Output)
-//	"${storage.endpoint.fqdn}" -> Nexted references are undefined behavior for now.
+//	"${my-rg.id}" -> resourceOutputs["my-rg.id"] (Pulumi Output, keeps the type of the value)
+//	"${db.host}:${db.port}" -> StringOutput combining both outputs, e.g. "db.example.com:5432"
+//	"${vm.networkProfile.networkInterfaces[0].id}" -> Output of the nested value
+//	"${kv.tags[\"cost-center\"]}" -> Output of the value under a quoted key
+//	"${stack:acme/network/prod.vnet.id}" -> Output of the nested value of another stack's output "vnet"
 //	"normal string" -> "normal string" (unchanged)
 //
-// This allows the host to specify dependencies without knowing Go types:
+// Property paths follow the grammar of outputs::parse_property_path on the Rust side. A path that does not
+// exist in the resource's outputs fails the operation.
 //
-// TODO: Add cycle detection to prevent infinite loops
-// TODO: Support nested object references like ${resource.output.field}
-// TODO: Support a proper string interpolation syntax: "${r1.host}:${r1.port}"
+// This allows the host to specify dependencies without knowing Go types.
+// The host sends resources in dependency order (see graph::order_resources), after rejecting dependency
+// cycles, so every referenced resource is registered before the resources referencing it.
 func resolveReferences(properties map[string]interface{}, resourceOutputs map[string]pulumi.Output) map[string]interface{} {
 	resolved := make(map[string]interface{})
 
@@ -235,59 +519,23 @@ func resolveReferences(properties map[string]interface{}, resourceOutputs map[st
 func resolveValue(value interface{}, resourceOutputs map[string]pulumi.Output) interface{} {
 	switch v := value.(type) {
 	case string:
-		// Look for ${resource.property} pattern
-		re := regexp.MustCompile(`\$\{([^.]+)\.([^}]+)\}`)
-		matches := re.FindAllStringSubmatch(v, -1)
-
+		// Look for ${resource.property} patterns
+		matches := referencePattern.FindAllStringSubmatchIndex(v, -1)
 		if len(matches) == 0 {
 			return v
 		}
 
-		// Process references found in the string
-		// Currently only supports full string replacement (not partial)
-		for _, match := range matches {
-			resourceName := match[1] // e.g., "my-rg"
-			propertyPath := match[2] // e.g., "name" or "properties.id"
-
-			// Strategy 1: Check for direct output (most common case)
-			// This handles outputs we explicitly stored like "my-rg.id"
-			outputKey := resourceName + "." + propertyPath
-			if output, exists := resourceOutputs[outputKey]; exists {
-				// Found it! Return the Pulumi Output directly
-				// This preserves the Output type for dependency tracking
+		// A string that is exactly one reference takes the referenced value as is, so
+		// numbers, booleans and objects keep their type
+		if len(matches) == 1 && matches[0][0] == 0 && matches[0][1] == len(v) {
+			match := matches[0]
+			if output, ok := resolveReference(v[match[2]:match[3]], v[match[4]:match[5]], resourceOutputs); ok {
 				return output
 			}
-
-			// Strategy 2: Handle nested property paths
-			// Example: ${storage.properties.endpoints.blob}
-			pathParts := strings.Split(propertyPath, ".")
-			if len(pathParts) > 1 {
-				// Try to get the root property first
-				rootKey := resourceName + "." + pathParts[0]
-				if rootOutput, exists := resourceOutputs[rootKey]; exists {
-					// TODO: Apply transformation to extract nested value
-					// This requires using Pulumi's Apply functions
-					return rootOutput
-				}
-			}
-
-			// Strategy 3: Check if we have the entire resource as output
-			// This happens when we store all resource properties as a map
-			if resourceOutput, exists := resourceOutputs[resourceName]; exists {
-				// Extract nested property using Apply
-				// This creates a new Output that depends on the resource
-				return resourceOutput.(pulumi.MapOutput).ApplyT(func(m map[string]interface{}) interface{} {
-					// Navigate to the nested property
-					return getNestedValue(m, propertyPath)
-				})
-			}
-
-			// Not found - this reference doesn't exist
-			// TODO: Send diagnostic event warning about unknown reference
-			// For now, leave the placeholder as-is
+			return v
 		}
 
-		return v
+		return interpolateReferences(v, matches, resourceOutputs)
 
 	case map[string]interface{}:
 		resolved := make(map[string]interface{})
@@ -308,41 +556,274 @@ func resolveValue(value interface{}, resourceOutputs map[string]pulumi.Output) i
 	}
 }
 
-// getNestedValue extracts a nested value from a map using dot notation.
-// This supports accessing nested properties in complex objects.
+// resolveReference returns the output a ${resourceName.propertyPath} reference points to. propertyPath starts
+// with the "." or "[" that follows the resource name.
+func resolveReference(resourceName string, propertyPath string, resourceOutputs map[string]pulumi.Output) (pulumi.Output, bool) {
+	reference := "${" + resourceName + propertyPath + "}"
+	propertyPath = strings.TrimPrefix(propertyPath, ".")
+
+	// Stack references: the first segment names an output of the other stack, read by readStackReferences.
+	// Applying the rest of the path keeps secret outputs secret.
+	if strings.HasPrefix(resourceName, stackReferencePrefix) {
+		segments, err := parsePropertyPath(propertyPath)
+		if err != nil || segments[0].isIndex {
+			return nil, false
+		}
+		output, exists := resourceOutputs[unquoteStackReference(resourceName)+"."+segments[0].key]
+		if !exists {
+			return nil, false
+		}
+		return output.ApplyT(func(value interface{}) (interface{}, error) {
+			nested, err := getNestedValue(map[string]interface{}{segments[0].key: value}, segments)
+			if err != nil {
+				return nil, fmt.Errorf("reference `%s`: %w", reference, err)
+			}
+			return nested, nil
+		}), true
+	}
+
+	// Strategy 1: Check for direct output (most common case)
+	// This handles outputs we explicitly stored like "my-rg.id"
+	outputKey := resourceName + "." + propertyPath
+	if output, exists := resourceOutputs[outputKey]; exists {
+		// Found it! Return the Pulumi Output directly
+		// This preserves the Output type for dependency tracking
+		return output, true
+	}
+
+	// Strategy 2: Extract the value from the resource's outputs
+	// This creates a new Output that depends on the resource
+	resourceOutput, exists := resourceOutputs[resourceName]
+	if !exists {
+		// Not found - this reference doesn't exist
+		// TODO: Send diagnostic event warning about unknown reference
+		return nil, false
+	}
+
+	segments, pathErr := parsePropertyPath(propertyPath)
+	return resourceOutput.(pulumi.MapOutput).ApplyT(func(m map[string]interface{}) (interface{}, error) {
+		if pathErr != nil {
+			return nil, fmt.Errorf("reference `%s`: %w", reference, pathErr)
+		}
+		value, err := getNestedValue(m, segments)
+		if err != nil {
+			return nil, fmt.Errorf("reference `%s`: %w", reference, err)
+		}
+		return value, nil
+	}), true
+}
+
+// interpolateReferences combines the text around the references in s with the values of the referenced
+// outputs into a single string output that depends on all of them. Unknown references are kept as written,
+// like outputs::resolve_output_references does on the Rust side.
+func interpolateReferences(s string, matches [][]int, resourceOutputs map[string]pulumi.Output) interface{} {
+	// literals[i] is the text before outputs[i], the last literal is the text after the last output
+	var literals []string
+	var outputs []interface{}
+
+	literal := ""
+	position := 0
+	for _, match := range matches {
+		literal += s[position:match[0]]
+		position = match[1]
+
+		output, ok := resolveReference(s[match[2]:match[3]], s[match[4]:match[5]], resourceOutputs)
+		if !ok {
+			literal += s[match[0]:match[1]]
+			continue
+		}
+		literals = append(literals, literal)
+		outputs = append(outputs, output)
+		literal = ""
+	}
+	literals = append(literals, literal+s[position:])
+
+	if len(outputs) == 0 {
+		return s
+	}
+
+	return pulumi.All(outputs...).ApplyT(func(values []interface{}) string {
+		var builder strings.Builder
+		for i, value := range values {
+			builder.WriteString(literals[i])
+			builder.WriteString(interpolationText(value))
+		}
+		builder.WriteString(literals[len(values)])
+		return builder.String()
+	}).(pulumi.StringOutput)
+}
+
+// interpolationText formats a referenced value for interpolation into a string: strings, including string
+// types such as pulumi.ID and pulumi.URN, are inserted as is, other values as JSON.
+func interpolationText(value interface{}) string {
+	if text, ok := value.(string); ok {
+		return text
+	}
+	if text := reflect.ValueOf(value); text.Kind() == reflect.String {
+		return text.String()
+	}
+	encoded, err := json.Marshal(value)
+	if err != nil {
+		return fmt.Sprint(value)
+	}
+	return string(encoded)
+}
+
+// pathSegment is one step of a reference's property path: an object key or an array index.
+type pathSegment struct {
+	key     string
+	index   int
+	isIndex bool
+}
+
+// parsePropertyPath parses a property path: keys separated by ".", array indexes like [0] and quoted keys
+// like ["cost-center"], e.g. networkProfile.networkInterfaces[0].id.
+func parsePropertyPath(path string) ([]pathSegment, error) {
+	invalid := func(format string, args ...interface{}) error {
+		return fmt.Errorf("invalid property path `%s`: %s", path, fmt.Sprintf(format, args...))
+	}
+
+	if path == "" {
+		return nil, invalid("the path is empty")
+	}
+
+	var segments []pathSegment
+	rest := path
+	for rest != "" {
+		if strings.HasPrefix(rest, "[") {
+			bracket := rest[1:]
+			if strings.HasPrefix(bracket, `"`) {
+				quoted := bracket[1:]
+				end := strings.Index(quoted, `"]`)
+				if end < 0 {
+					return nil, invalid("unterminated quoted key")
+				}
+				segments = append(segments, pathSegment{key: quoted[:end]})
+				rest = quoted[end+2:]
+				continue
+			}
+
+			end := strings.Index(bracket, "]")
+			if end < 0 {
+				return nil, invalid("unterminated array index")
+			}
+			index := bracket[:end]
+			if index == "" || strings.Trim(index, "0123456789") != "" {
+				return nil, invalid("`[%s]` is not an array index or quoted key", index)
+			}
+			position, err := strconv.Atoi(index)
+			if err != nil {
+				return nil, invalid("array index %s is too large", index)
+			}
+			segments = append(segments, pathSegment{index: position, isIndex: true})
+			rest = bracket[end+1:]
+			continue
+		}
+
+		key := rest
+		if len(segments) > 0 {
+			if !strings.HasPrefix(rest, ".") {
+				return nil, invalid("expected `.` or `[` before `%s`", rest)
+			}
+			key = rest[1:]
+		}
+		end := strings.IndexAny(key, `.[]"`)
+		if end < 0 {
+			end = len(key)
+		}
+		if end == 0 {
+			return nil, invalid("keys must not be empty, quote keys containing `.`, `[`, `]` or `\"`")
+		}
+		segments = append(segments, pathSegment{key: key[:end]})
+		rest = key[end:]
+	}
+
+	return segments, nil
+}
+
+// formatPropertyPath formats a property path in the grammar accepted by parsePropertyPath.
+func formatPropertyPath(segments []pathSegment) string {
+	var builder strings.Builder
+	for _, segment := range segments {
+		switch {
+		case segment.isIndex:
+			fmt.Fprintf(&builder, "[%d]", segment.index)
+		case segment.key != "" && !strings.ContainsAny(segment.key, `.[]"`):
+			if builder.Len() > 0 {
+				builder.WriteString(".")
+			}
+			builder.WriteString(segment.key)
+		default:
+			fmt.Fprintf(&builder, `["%s"]`, segment.key)
+		}
+	}
+	return builder.String()
+}
+
+// getNestedValue extracts a nested value from a resource's outputs by following a property path.
 //
 // Examples:
 //   - "name" -> object["name"]
 //   - "address.city" -> object["address"]["city"]
-//   - "config.database.host" -> object["config"]["database"]["host"]
-//
-// Returns the value at the path, or nil if not found.
+//   - "nics[0].id" -> object["nics"][0]["id"]
+//   - `tags["cost-center"]` -> object["tags"]["cost-center"]
 //
-// TODO: Currently, we only support single-level: ${resource.output}
-func getNestedValue(object map[string]interface{}, path string) interface{} {
-	// Split the path into components
-	parts := strings.Split(path, ".")
-	current := object
-
-	// Navigate through nested maps
-	for i, part := range parts {
-		if val, ok := current[part]; ok {
-			if i == len(parts)-1 {
-				// Found the target value
-				return val
-			}
-			// Not the last part, try to navigate deeper
-			if nextMap, ok := val.(map[string]interface{}); ok {
-				current = nextMap
-			} else {
-				// Value exists but is not a map, can't navigate deeper
-				return nil
+// Returns an error naming the first segment that does not exist if the path cannot be followed.
+func getNestedValue(object map[string]interface{}, segments []pathSegment) (interface{}, error) {
+	var current interface{} = object
+
+	for i, segment := range segments {
+		parent := "the resource"
+		if i > 0 {
+			parent = "`" + formatPropertyPath(segments[:i]) + "`"
+		}
+
+		switch value := current.(type) {
+		case map[string]interface{}:
+			if segment.isIndex {
+				return nil, fmt.Errorf("%s is an object, not an array with index %d", parent, segment.index)
 			}
-		} else {
-			// Property not found at this level
-			return nil
+			next, ok := value[segment.key]
+			if !ok {
+				kind := "key"
+				if i == 0 {
+					kind = "output"
+				}
+				return nil, fmt.Errorf("%s has no %s `%s`", parent, kind, segment.key)
+			}
+			current = next
+
+		case []interface{}:
+			if !segment.isIndex {
+				return nil, fmt.Errorf("%s is an array, not an object with key `%s`", parent, segment.key)
+			}
+			if segment.index >= len(value) {
+				return nil, fmt.Errorf("%s has no index %d (length %d)", parent, segment.index, len(value))
+			}
+			current = value[segment.index]
+
+		default:
+			if segment.isIndex {
+				return nil, fmt.Errorf("%s is %s, not an array with index %d", parent, valueKind(current), segment.index)
+			}
+			return nil, fmt.Errorf("%s is %s, not an object with key `%s`", parent, valueKind(current), segment.key)
 		}
 	}
 
-	return nil
+	return current, nil
+}
+
+func valueKind(value interface{}) string {
+	switch value.(type) {
+	case nil:
+		return "null"
+	case bool:
+		return "a boolean"
+	case string:
+		return "a string"
+	case float64, int, int64:
+		return "a number"
+	default:
+		return fmt.Sprintf("a %T", value)
+	}
 }
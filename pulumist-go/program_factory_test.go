@@ -0,0 +1,33 @@
+package main
+
+import (
+	"context"
+	"github.com/pulumi/pulumi/sdk/v3/go/pulumi"
+	"github.com/pulumi/pulumi/sdk/v3/go/pulumi/internals"
+	"testing"
+)
+
+func TestInterpolateIDReference(t *testing.T) {
+	resourceOutputs := map[string]pulumi.Output{
+		"web.id": pulumi.ID("abc").ToIDOutput(),
+		"web": pulumi.Map{
+			"urn":  pulumi.URN("urn:pulumi:dev::proj::azure-native:web:WebApp::web"),
+			"port": pulumi.Int(8080),
+		}.ToMapOutput(),
+	}
+
+	s := "https://${web.id}/x?port=${web.port}&urn=${web.urn}"
+	interpolated, ok := resolveValue(s, resourceOutputs).(pulumi.StringOutput)
+	if !ok {
+		t.Fatalf("expected a string output for %q", s)
+	}
+
+	result, err := internals.UnsafeAwaitOutput(context.Background(), interpolated)
+	if err != nil {
+		t.Fatal(err)
+	}
+	expected := "https://abc/x?port=8080&urn=urn:pulumi:dev::proj::azure-native:web:WebApp::web"
+	if result.Value != expected {
+		t.Errorf("expected %q, got %q", expected, result.Value)
+	}
+}
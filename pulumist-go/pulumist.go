@@ -10,13 +10,19 @@ package main
 import "C"
 import (
 	"context"
+	"errors"
 	"fmt"
 	pb "github.com/evorine/pulumist/pulumist-go/generated" // Generated protobuf types
 	"github.com/pulumi/pulumi/sdk/v3/go/auto"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto/optdestroy"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto/optpreview"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto/optrefresh"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto/optup"
 	"github.com/pulumi/pulumi/sdk/v3/go/pulumi"
 	"google.golang.org/protobuf/proto"
 	"os"
 	"path/filepath"
+	"time"
 	"unsafe"
 )
 
@@ -83,9 +89,9 @@ func PulumiDynamicDestroy(requestBytes *C.char, requestLen C.int) *C.char {
 		return createFailedResponse(err)
 	}
 
-	// Create context for cancellation
-	// TODO: Accept timeout from request for long-running operations
-	ctx := context.Background()
+	// Create context for cancellation and the operation deadline
+	ctx, finish := startOperation(request.OperationId, request.TimeoutSeconds)
+	defer finish()
 
 	// Ensure that the working directory exists
 	workDir, err := ensureWorkingDirectory(request.ProjectName)
@@ -93,42 +99,48 @@ func PulumiDynamicDestroy(requestBytes *C.char, requestLen C.int) *C.char {
 		return createFailedResponse(fmt.Errorf("failed to ensure working directory: %w", err))
 	}
 
+	opts, err := workspaceOptions(workDir, request.PulumiConfig)
+	if err != nil {
+		return createFailedResponse(err)
+	}
+
 	// Get existing stack
 	stack, err := auto.SelectStackInlineSource(ctx, request.StackName, request.ProjectName,
 		func(ctx *pulumi.Context) error { return nil },
-		auto.WorkDir(workDir),
+		opts...,
 	)
 	if err != nil {
-		return createFailedResponse(err)
+		return createOperationFailedResponse(ctx, err, nil)
+	}
+	attachStack(request.OperationId, stack)
+
+	// Providers may need configuration (e.g. credentials or region) to delete resources
+	if err := applyStackConfig(ctx, stack, request.Config); err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
 	}
 
 	// Destroy resources
-	destroyResult, err := stack.Destroy(ctx)
+	tracker := newPendingResourceTracker()
+	forwarder := newEngineEventForwarder(request.OperationId)
+	defer forwarder.wait()
+	destroyOpts := []optdestroy.Option{optdestroy.EventStreams(tracker.events, forwarder.events)}
+	if logging := debugLogging(request.PulumiConfig); logging != nil {
+		destroyOpts = append(destroyOpts, optdestroy.DebugLogging(*logging))
+	}
+	started := time.Now()
+	destroyResult, err := stack.Destroy(ctx, destroyOpts...)
 	if err != nil {
-		return createFailedResponse(err)
+		return createOperationFailedResponse(ctx, err, tracker)
 	}
 
-	var outputs []*pb.OutputItem
-	outputs = append(outputs, &pb.OutputItem{
-		ResourceName: "stack",
-		OutputName:   "stdout",
-		Value:        convertInterfaceToProtoValue(destroyResult.StdOut),
-	})
-	outputs = append(outputs, &pb.OutputItem{
-		ResourceName: "stack",
-		OutputName:   "stderr",
-		Value:        convertInterfaceToProtoValue(destroyResult.StdErr),
+	return createResultResponse(&pb.OperationResult{
+		Stdout:          destroyResult.StdOut,
+		Stderr:          destroyResult.StdErr,
+		ResourceChanges: updateResourceChanges(destroyResult.Summary),
+		Status:          destroyResult.Summary.Result,
+		Message:         destroyResult.Summary.Message,
+		DurationSeconds: time.Since(started).Seconds(),
 	})
-	outputs = append(outputs, &pb.OutputItem{
-		ResourceName: "stack",
-		OutputName:   "summary",
-		Value: convertInterfaceToProtoValue(map[string]interface{}{
-			"message": destroyResult.Summary.Message,
-			"result":  destroyResult.Summary.Result,
-		}),
-	})
-
-	return createOkResponse(outputs)
 }
 
 // PulumiDynamicGetOutputs retrieves the current outputs from a stack.
@@ -152,8 +164,9 @@ func PulumiDynamicGetOutputs(requestBytes *C.char, requestLen C.int) *C.char {
 		return createFailedResponse(err)
 	}
 
-	// Create context for cancellation
-	ctx := context.Background()
+	// Create context for cancellation and the operation deadline
+	ctx, finish := startOperation(request.OperationId, request.TimeoutSeconds)
+	defer finish()
 
 	// Ensure that the working directory exists
 	workDir, err := ensureWorkingDirectory(request.ProjectName)
@@ -161,19 +174,24 @@ func PulumiDynamicGetOutputs(requestBytes *C.char, requestLen C.int) *C.char {
 		return createFailedResponse(fmt.Errorf("failed to ensure working directory: %w", err))
 	}
 
+	opts, err := workspaceOptions(workDir, request.PulumiConfig)
+	if err != nil {
+		return createFailedResponse(err)
+	}
+
 	// Get existing stack
 	stack, err := auto.SelectStackInlineSource(ctx, request.StackName, request.ProjectName,
 		func(ctx *pulumi.Context) error { return nil },
-		auto.WorkDir(workDir),
+		opts...,
 	)
 	if err != nil {
-		return createFailedResponse(err)
+		return createOperationFailedResponse(ctx, err, nil)
 	}
 
 	// Get outputs from the stack
 	outputs, err := stack.Outputs(ctx)
 	if err != nil {
-		return createFailedResponse(err)
+		return createOperationFailedResponse(ctx, err, nil)
 	}
 
 	var outputItems []*pb.OutputItem
@@ -189,7 +207,8 @@ func PulumiDynamicGetOutputs(requestBytes *C.char, requestLen C.int) *C.char {
 }
 
 // PulumiDynamicRefresh updates the stack state to match actual cloud resources.
-// Refresh only updates state, it doesn't modify resources.
+// Refresh only updates state, it doesn't modify resources. With preview_only set in the request
+// the changes are only reported, so drift can be detected without writing state.
 //
 // Parameters:
 //   - @param request: Pointer to protobuf-encoded PulumiRequest data
@@ -200,7 +219,46 @@ func PulumiDynamicGetOutputs(requestBytes *C.char, requestLen C.int) *C.char {
 //
 //export PulumiDynamicRefresh
 func PulumiDynamicRefresh(requestBytes *C.char, requestLen C.int) *C.char {
-	panic("not implemented")
+	// Convert C bytes to Go bytes safely
+	goBytes := C.GoBytes(unsafe.Pointer(requestBytes), requestLen)
+
+	// Deserialize protobuf request
+	var request pb.PulumiRequest
+	if err := proto.Unmarshal(goBytes, &request); err != nil {
+		return createFailedResponse(err)
+	}
+
+	// Create context for cancellation and the operation deadline
+	ctx, finish := startOperation(request.OperationId, request.TimeoutSeconds)
+	defer finish()
+
+	// Ensure that the working directory exists
+	workDir, err := ensureWorkingDirectory(request.ProjectName)
+	if err != nil {
+		return createFailedResponse(fmt.Errorf("failed to ensure working directory: %w", err))
+	}
+
+	opts, err := workspaceOptions(workDir, request.PulumiConfig)
+	if err != nil {
+		return createFailedResponse(err)
+	}
+
+	// Get existing stack. Refresh reads the resources from the state, so the program is not needed.
+	stack, err := auto.SelectStackInlineSource(ctx, request.StackName, request.ProjectName,
+		func(ctx *pulumi.Context) error { return nil },
+		opts...,
+	)
+	if err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
+	}
+	attachStack(request.OperationId, stack)
+
+	// Providers may need configuration (e.g. credentials or region) to read resources
+	if err := applyStackConfig(ctx, stack, request.Config); err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
+	}
+
+	return refreshStack(&request, stack, ctx)
 }
 
 func processPulumiRequest(requestBytes *C.char, requestLen C.int, isDryRun bool) *C.char {
@@ -213,9 +271,9 @@ func processPulumiRequest(requestBytes *C.char, requestLen C.int, isDryRun bool)
 		return createFailedResponse(err)
 	}
 
-	// Create context for cancellation
-	// TODO: Accept timeout from request for long-running operations
-	ctx := context.Background()
+	// Create context for cancellation and the operation deadline
+	ctx, finish := startOperation(request.OperationId, request.TimeoutSeconds)
+	defer finish()
 
 	// Ensure that the working directory exists
 	workDir, err := ensureWorkingDirectory(request.ProjectName)
@@ -226,155 +284,147 @@ func processPulumiRequest(requestBytes *C.char, requestLen C.int, isDryRun bool)
 	// Create the deployment function with dynamic resources
 	// This function will be called by Pulumi's engine to define infrastructure.
 	// It captures the resources from the request and registers them when executed.
-	deploymentProgram := createDeploymentProgram(request.Resources)
+	deploymentProgram := createDeploymentProgram(request.OperationId, request.Resources, request.Outputs, request.Invokes)
 
-	// Create the stack with the appropriate backend
-	opts := []auto.LocalWorkspaceOption{
-		auto.WorkDir(workDir),
+	// Create the stack with the configured backend, secrets provider and environment
+	opts, err := workspaceOptions(workDir, request.PulumiConfig)
+	if err != nil {
+		return createFailedResponse(err)
 	}
 
-	// Always use local with passphrase
-	// TODO: Support cloud based key management services (AWS KMS, Azure Key Vault etc.)
-	// The passphrase is read from PULUMI_CONFIG_PASSPHRASE env var.
-	opts = append(opts, auto.SecretsProvider("passphrase"))
-
 	// Create or update the stack (as inline source)
 	stack, err := auto.UpsertStackInlineSource(ctx, request.StackName, request.ProjectName, deploymentProgram, opts...)
 	if err != nil {
-		return createFailedResponse(err)
+		return createOperationFailedResponse(ctx, err, nil)
 	}
+	attachStack(request.OperationId, stack)
 
-	// Send start event
-	emitEvent(&pb.Event{
-		Event: &pb.Event_Prelude{
-			Prelude: &pb.PreludeEvent{
-				Config: make(map[string]string),
-			},
-		},
-	})
-
-	// Refresh first to detect drift
-	emitEvent(&pb.Event{
-		Event: &pb.Event_Diagnostic{
-			Diagnostic: &pb.DiagnosticEvent{
-				Severity: "info",
-				Message:  "Refreshing stack to detect drift...",
-			},
-		},
-	})
-	refreshResult, refreshErr := stack.Refresh(ctx)
-	if refreshErr != nil {
-		emitEvent(&pb.Event{
-			Event: &pb.Event_Diagnostic{
-				Diagnostic: &pb.DiagnosticEvent{
-					Severity: "warning",
-					Message:  fmt.Sprintf("Refresh warning: %v", refreshErr),
-				},
-			},
-		})
-	} else {
-		emitEvent(&pb.Event{
-			Event: &pb.Event_Diagnostic{
-				Diagnostic: &pb.DiagnosticEvent{
-					Severity: "info",
-					Message:  fmt.Sprintf("Refresh completed: %s", refreshResult.Summary.Message),
-				},
-			},
-		})
+	// Apply the stack configuration before the program runs, so it can be read through the config package
+	if err := applyStackConfig(ctx, stack, request.Config); err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
 	}
 
-	// Now that we have the stack ready, we can proceed with the preview or up operation.
+	// A preview must not write state, so only an update refreshes the stack first to detect drift
 	if isDryRun {
-		return previewStack(stack, ctx)
-	} else {
-		return deployStack(stack, ctx)
+		return previewStack(&request, stack, ctx)
+	}
+	if response := refreshBeforeUpdate(&request, stack, ctx); response != nil {
+		return response
+	}
+	return deployStack(&request, stack, ctx)
+}
+
+// refreshBeforeUpdate refreshes the stack so the update diffs against the actual cloud resources. Its engine
+// events are streamed to the host like those of the update. A failed refresh, reported by its diagnostic events,
+// does not stop the update; a response is only returned when the operation was cancelled or timed out.
+func refreshBeforeUpdate(request *pb.PulumiRequest, stack auto.Stack, ctx context.Context) *C.char {
+	tracker := newPendingResourceTracker()
+	forwarder := newEngineEventForwarder(request.OperationId)
+	refreshOpts := []optrefresh.Option{optrefresh.EventStreams(tracker.events, forwarder.events)}
+	if logging := debugLogging(request.PulumiConfig); logging != nil {
+		refreshOpts = append(refreshOpts, optrefresh.DebugLogging(*logging))
+	}
+	_, err := stack.Refresh(ctx, refreshOpts...)
+	forwarder.wait()
+	if ctx.Err() != nil {
+		return createOperationFailedResponse(ctx, err, tracker)
 	}
+	return nil
 }
 
 // Performs a dry-run preview of the provided stack.
 // This will show what changes would be made without actually applying them.
-func previewStack(stack auto.Stack, ctx context.Context) *C.char {
+func previewStack(request *pb.PulumiRequest, stack auto.Stack, ctx context.Context) *C.char {
 	// Preview the stack
-	preview, err := stack.Preview(ctx)
+	tracker := newPendingResourceTracker()
+	forwarder := newEngineEventForwarder(request.OperationId)
+	defer forwarder.wait()
+	previewOpts := []optpreview.Option{optpreview.EventStreams(tracker.events, forwarder.events)}
+	if logging := debugLogging(request.PulumiConfig); logging != nil {
+		previewOpts = append(previewOpts, optpreview.DebugLogging(*logging))
+	}
+	started := time.Now()
+	preview, err := stack.Preview(ctx, previewOpts...)
 
 	if err != nil {
-		return createFailedResponse(err)
+		return createOperationFailedResponse(ctx, err, tracker)
 	}
 
-	var outputs []*pb.OutputItem
-	outputs = append(outputs, &pb.OutputItem{
-		ResourceName: "stack",
-		OutputName:   "stdout",
-		Value:        convertInterfaceToProtoValue(preview.StdOut),
+	return createResultResponse(&pb.OperationResult{
+		Stdout:          preview.StdOut,
+		Stderr:          preview.StdErr,
+		ResourceChanges: resourceChangeCounts(preview.ChangeSummary),
+		DurationSeconds: time.Since(started).Seconds(),
 	})
-	outputs = append(outputs, &pb.OutputItem{
-		ResourceName: "stack",
-		OutputName:   "stderr",
-		Value:        convertInterfaceToProtoValue(preview.StdErr),
-	})
-	outputs = append(outputs, &pb.OutputItem{
-		ResourceName: "stack",
-		OutputName:   "summary",
-		Value:        convertInterfaceToProtoValue(preview.ChangeSummary),
-	})
-
-	return createOkResponse(outputs)
 }
 
 // deployStack applies the changes to the stack and returns the result.
-func deployStack(stack auto.Stack, ctx context.Context) *C.char {
+func deployStack(request *pb.PulumiRequest, stack auto.Stack, ctx context.Context) *C.char {
 	// Run deployment
-	upResult, err := stack.Up(ctx)
+	tracker := newPendingResourceTracker()
+	forwarder := newEngineEventForwarder(request.OperationId)
+	defer forwarder.wait()
+	upOpts := []optup.Option{optup.EventStreams(tracker.events, forwarder.events)}
+	if logging := debugLogging(request.PulumiConfig); logging != nil {
+		upOpts = append(upOpts, optup.DebugLogging(*logging))
+	}
+	started := time.Now()
+	upResult, err := stack.Up(ctx, upOpts...)
 
 	if err != nil {
-		return createFailedResponse(err)
+		return createOperationFailedResponse(ctx, err, tracker)
 	}
+	duration := time.Since(started)
 
-	// Send summary event
-	emitEvent(&pb.Event{
-		Event: &pb.Event_Summary{
-			Summary: &pb.SummaryEvent{
-				MayChange:       false,
-				DurationSeconds: 10, // Placeholder
-				ResourceChanges: map[string]int32{
-					"created": 1,
+	changes := updateResourceChanges(upResult.Summary)
+
+	outputs := make(map[string]*pb.Value, len(upResult.Outputs))
+	for name, output := range upResult.Outputs {
+		outputs[name] = convertInterfaceToProtoValue(output.Value)
+	}
+
+	// The update succeeded, so a state that cannot be read only costs the per-resource results
+	var resources []*pb.ResourceState
+	if deployment, err := readDeployment(ctx, stack); err != nil {
+		emitEvent(request.OperationId, &pb.Event{
+			Event: &pb.Event_Diagnostic{
+				Diagnostic: &pb.DiagnosticEvent{
+					Severity: "warning",
+					Message:  fmt.Sprintf("Could not read resource outputs: %v", err),
 				},
 			},
-		},
-	})
-
-	// Get outputs
-	stackOutputs, err := stack.Outputs(ctx)
-	if err != nil {
-		return createFailedResponse(err)
+		})
+	} else {
+		resources = resourceStates(deployment, request.Resources)
 	}
 
-	var outputs []*pb.OutputItem
-	outputs = append(outputs, &pb.OutputItem{
-		ResourceName: "stack",
-		OutputName:   "stdout",
-		Value:        convertInterfaceToProtoValue(upResult.StdOut),
-	})
-	outputs = append(outputs, &pb.OutputItem{
-		ResourceName: "stack",
-		OutputName:   "stderr",
-		Value:        convertInterfaceToProtoValue(upResult.StdErr),
-	})
-	outputs = append(outputs, &pb.OutputItem{
-		ResourceName: "stack",
-		OutputName:   "outputs",
-		Value:        convertInterfaceToProtoValue(stackOutputs),
-	})
-	outputs = append(outputs, &pb.OutputItem{
-		ResourceName: "stack",
-		OutputName:   "summary",
-		Value: convertInterfaceToProtoValue(map[string]interface{}{
-			"message": upResult.Summary.Message,
-			"result":  upResult.Summary.Result,
-		}),
+	return createResultResponse(&pb.OperationResult{
+		Stdout:          upResult.StdOut,
+		Stderr:          upResult.StdErr,
+		ResourceChanges: changes,
+		Outputs:         outputs,
+		Status:          upResult.Summary.Result,
+		Message:         upResult.Summary.Message,
+		DurationSeconds: duration.Seconds(),
+		Resources:       resources,
 	})
+}
 
-	return createOkResponse(outputs)
+// updateResourceChanges returns the resource counts per operation kind recorded in an update summary.
+func updateResourceChanges(summary auto.UpdateSummary) map[string]int32 {
+	if summary.ResourceChanges == nil {
+		return map[string]int32{}
+	}
+	return resourceChangeCounts(*summary.ResourceChanges)
+}
+
+// resourceChangeCounts converts resource counts keyed by operation kind (a string or an apitype.OpType) for OperationResult.
+func resourceChangeCounts[K ~string](changes map[K]int) map[string]int32 {
+	counts := make(map[string]int32, len(changes))
+	for op, count := range changes {
+		counts[string(op)] = int32(count)
+	}
+	return counts
 }
 
 func ensureWorkingDirectory(projectName string) (string, error) {
@@ -387,51 +437,64 @@ func ensureWorkingDirectory(projectName string) (string, error) {
 }
 
 // createFailedResponse creates a PulumiResponse which represents an error and returns it as a C-compatible byte array with a length prefix.
-//
-// Format:
-//
-//	[0:4]  - Length of protobuf data (4 bytes, little-endian uint32)
-//	[4:n]  - Protobuf-encoded PulumiResponse message
-//
-// This format is necessary because:
-// 1. C strings are null-terminated, but protobuf can contain null bytes
-// 2. FFI doesn't preserve array length information
-//
-// Memory management:
-// - This function allocates memory using C.CBytes
-// - The caller host MUST free this memory using PulumiFree
-// - Failure to free will cause memory leaks
-//
-// TODO: If proto.Marshal fails, currently we ignore it. Handle this better.
 func createFailedResponse(err error) *C.char {
-	response := &pb.PulumiResponse{
+	return createResponse(&pb.PulumiResponse{
 		Success: false,
 		Error:   err.Error(),
 		Outputs: []*pb.OutputItem{},
-	}
+	})
+}
 
-	// Serialize to protobuf binary format
-	respBytes, _ := proto.Marshal(response) // TODO: Handle marshal error
+// createOperationFailedResponse creates a failed PulumiResponse for an error returned while ctx was active.
+// If ctx was cancelled through PulumiDynamicCancel or hit its deadline the response is marked as cancelled or
+// timed out, since the error is then just the interrupted pulumi CLI reporting that it stopped.
+// tracker, if not nil, provides the resources that were still pending when the deadline hit.
+func createOperationFailedResponse(ctx context.Context, err error, tracker *pendingResourceTracker) *C.char {
+	var message string
+	response := &pb.PulumiResponse{
+		Success: false,
+		Outputs: []*pb.OutputItem{},
+	}
 
-	// Create length-prefixed response
-	// Format: [4 bytes length][protobuf data]
-	result := make([]byte, 4+len(respBytes))
+	switch {
+	case errors.Is(ctx.Err(), context.Canceled):
+		message = "operation was cancelled"
+		response.Cancelled = true
+	case errors.Is(ctx.Err(), context.DeadlineExceeded):
+		message = "operation timed out"
+		response.TimedOut = true
+		if tracker != nil {
+			response.PendingResources = tracker.pendingURNs()
+		}
+	default:
+		return createFailedResponse(err)
+	}
 
-	// Write length as 4-byte little-endian integer
-	// This matches Rust's expectation for reading the length
-	result[0] = byte(len(respBytes))
-	result[1] = byte(len(respBytes) >> 8)
-	result[2] = byte(len(respBytes) >> 16)
-	result[3] = byte(len(respBytes) >> 24)
+	if err != nil {
+		message = fmt.Sprintf("%s: %v", message, err)
+	}
+	response.Error = message
+	return createResponse(response)
+}
 
-	// Copy protobuf data
-	copy(result[4:], respBytes)
+// createOkResponse creates a PulumiResponse which represents a successful process and returns it as a C-compatible byte array with a length prefix.
+func createOkResponse(outputs []*pb.OutputItem) *C.char {
+	return createResponse(&pb.PulumiResponse{
+		Success: true,
+		Outputs: outputs,
+	})
+}
 
-	// Convert to C-allocated memory that can cross the FFI boundary
-	return (*C.char)(C.CBytes(result))
+// createResultResponse creates a successful PulumiResponse carrying the result of a preview, up, destroy, refresh, import or invoke.
+func createResultResponse(result *pb.OperationResult) *C.char {
+	return createResponse(&pb.PulumiResponse{
+		Success: true,
+		Outputs: []*pb.OutputItem{},
+		Result:  result,
+	})
 }
 
-// createOkResponse creates a PulumiResponse which represents a successful process and returns it as a C-compatible byte array with a length prefix.
+// createResponse serializes a PulumiResponse and returns it as a C-compatible byte array with a length prefix.
 //
 // Format:
 //
@@ -448,12 +511,7 @@ func createFailedResponse(err error) *C.char {
 // - Failure to free will cause memory leaks
 //
 // TODO: If proto.Marshal fails, currently we ignore it. Handle this better.
-func createOkResponse(outputs []*pb.OutputItem) *C.char {
-	response := &pb.PulumiResponse{
-		Success: true,
-		Outputs: outputs,
-	}
-
+func createResponse(response *pb.PulumiResponse) *C.char {
 	// Serialize to protobuf binary format
 	respBytes, _ := proto.Marshal(response) // TODO: Handle marshal error
 
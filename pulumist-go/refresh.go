@@ -0,0 +1,142 @@
+package main
+
+import "C"
+import (
+	"context"
+	pb "github.com/evorine/pulumist/pulumist-go/generated"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto/events"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto/optpreviewrefresh"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto/optrefresh"
+	"github.com/pulumi/pulumi/sdk/v3/go/common/apitype"
+	"reflect"
+	"sort"
+	"sync"
+	"time"
+)
+
+// refreshChangeTracker follows the engine event stream of a refresh and records the resources whose state
+// differs from the actual cloud resources: updated ones, and deleted ones that no longer exist.
+//
+// Like pendingResourceTracker, pass tracker.events to the operation's EventStreams option and use a tracker
+// for a single operation only.
+type refreshChangeTracker struct {
+	events chan events.EngineEvent
+	done   chan struct{}
+
+	mu      sync.Mutex
+	updated map[string]struct{}
+	deleted map[string]struct{}
+}
+
+func newRefreshChangeTracker() *refreshChangeTracker {
+	tracker := &refreshChangeTracker{
+		events:  make(chan events.EngineEvent),
+		done:    make(chan struct{}),
+		updated: make(map[string]struct{}),
+		deleted: make(map[string]struct{}),
+	}
+
+	go func() {
+		defer close(tracker.done)
+		for event := range tracker.events {
+			if event.ResOutputsEvent != nil {
+				tracker.observe(event.ResOutputsEvent.Metadata)
+			}
+		}
+	}()
+
+	return tracker
+}
+
+func (t *refreshChangeTracker) observe(metadata apitype.StepEventMetadata) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	switch metadata.Op {
+	case apitype.OpDelete:
+		t.deleted[metadata.URN] = struct{}{}
+	case apitype.OpUpdate:
+		t.updated[metadata.URN] = struct{}{}
+	case apitype.OpRefresh:
+		// Depending on the engine version refresh steps are reported as "refresh" and leave the
+		// outcome to the old and new states, so compare them when no diff is attached.
+		switch {
+		case metadata.New == nil:
+			t.deleted[metadata.URN] = struct{}{}
+		case len(metadata.Diffs) > 0 || len(metadata.DetailedDiff) > 0:
+			t.updated[metadata.URN] = struct{}{}
+		case metadata.Old != nil && !reflect.DeepEqual(metadata.Old.Outputs, metadata.New.Outputs):
+			t.updated[metadata.URN] = struct{}{}
+		}
+	}
+}
+
+// changedURNs returns the sorted URNs of the updated and deleted resources.
+func (t *refreshChangeTracker) changedURNs() (updated []string, deleted []string) {
+	select {
+	case <-t.done:
+	case <-time.After(pendingDrainTimeout):
+	}
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	return sortedKeys(t.updated), sortedKeys(t.deleted)
+}
+
+func sortedKeys(set map[string]struct{}) []string {
+	keys := make([]string, 0, len(set))
+	for key := range set {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	return keys
+}
+
+// refreshStack refreshes the stack state from the actual cloud resources and returns the result.
+// With request.PreviewOnly the changes are only computed, so drift can be detected without writing state.
+func refreshStack(request *pb.PulumiRequest, stack auto.Stack, ctx context.Context) *C.char {
+	tracker := newPendingResourceTracker()
+	changes := newRefreshChangeTracker()
+	forwarder := newEngineEventForwarder(request.OperationId)
+	defer forwarder.wait()
+
+	result := &pb.OperationResult{}
+	started := time.Now()
+
+	if request.PreviewOnly {
+		previewOpts := []optpreviewrefresh.Option{
+			optpreviewrefresh.EventStreams(tracker.events, changes.events, forwarder.events),
+		}
+		if logging := debugLogging(request.PulumiConfig); logging != nil {
+			previewOpts = append(previewOpts, optpreviewrefresh.DebugLogging(*logging))
+		}
+		preview, err := stack.PreviewRefresh(ctx, previewOpts...)
+		if err != nil {
+			return createOperationFailedResponse(ctx, err, tracker)
+		}
+
+		result.Stdout, result.Stderr = preview.StdOut, preview.StdErr
+		result.ResourceChanges = resourceChangeCounts(preview.ChangeSummary)
+	} else {
+		refreshOpts := []optrefresh.Option{
+			optrefresh.EventStreams(tracker.events, changes.events, forwarder.events),
+		}
+		if logging := debugLogging(request.PulumiConfig); logging != nil {
+			refreshOpts = append(refreshOpts, optrefresh.DebugLogging(*logging))
+		}
+		refresh, err := stack.Refresh(ctx, refreshOpts...)
+		if err != nil {
+			return createOperationFailedResponse(ctx, err, tracker)
+		}
+
+		result.Stdout, result.Stderr = refresh.StdOut, refresh.StdErr
+		result.ResourceChanges = updateResourceChanges(refresh.Summary)
+		result.Status = refresh.Summary.Result
+		result.Message = refresh.Summary.Message
+	}
+
+	result.DurationSeconds = time.Since(started).Seconds()
+	result.Updated, result.Deleted = changes.changedURNs()
+	return createResultResponse(result)
+}
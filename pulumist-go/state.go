@@ -0,0 +1,226 @@
+package main
+
+import "C"
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	pb "github.com/evorine/pulumist/pulumist-go/generated"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto"
+	"github.com/pulumi/pulumi/sdk/v3/go/common/apitype"
+	"github.com/pulumi/pulumi/sdk/v3/go/pulumi"
+	"google.golang.org/protobuf/proto"
+	"strings"
+	"unsafe"
+)
+
+// PulumiDynamicExportState exports the stack's deployment checkpoint, like pulumi stack export.
+//
+// Parameters:
+//   - @param request: Pointer to protobuf-encoded PulumiRequest data
+//   - @param length: Length of the request data in bytes
+//
+// Returns a pointer to C-allocated memory containing length-prefixed PulumiResponse, with the JSON-encoded
+// UntypedDeployment in data. The caller must free this memory using PulumiFree.
+//
+//export PulumiDynamicExportState
+func PulumiDynamicExportState(requestBytes *C.char, requestLen C.int) *C.char {
+	// Convert C bytes to Go bytes safely
+	goBytes := C.GoBytes(unsafe.Pointer(requestBytes), requestLen)
+
+	// Deserialize protobuf request
+	var request pb.PulumiRequest
+	if err := proto.Unmarshal(goBytes, &request); err != nil {
+		return createFailedResponse(err)
+	}
+
+	// Create context for cancellation and the operation deadline
+	ctx, finish := startOperation(request.OperationId, request.TimeoutSeconds)
+	defer finish()
+
+	// Ensure that the working directory exists
+	workDir, err := ensureWorkingDirectory(request.ProjectName)
+	if err != nil {
+		return createFailedResponse(fmt.Errorf("failed to ensure working directory: %w", err))
+	}
+
+	opts, err := workspaceOptions(workDir, request.PulumiConfig)
+	if err != nil {
+		return createFailedResponse(err)
+	}
+
+	// Get existing stack
+	stack, err := auto.SelectStackInlineSource(ctx, request.StackName, request.ProjectName,
+		func(ctx *pulumi.Context) error { return nil },
+		opts...,
+	)
+	if err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
+	}
+
+	deployment, err := stack.Export(ctx)
+	if err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
+	}
+
+	data, err := json.Marshal(deployment)
+	if err != nil {
+		return createFailedResponse(fmt.Errorf("failed to encode stack state: %w", err))
+	}
+
+	return createResponse(&pb.PulumiResponse{
+		Success: true,
+		Data:    data,
+		Outputs: []*pb.OutputItem{},
+	})
+}
+
+// PulumiDynamicImportState replaces the stack's deployment checkpoint, like pulumi stack import.
+// The stack is created if it does not exist, so state can be migrated to another stack or backend.
+//
+// Parameters:
+//   - @param request: Pointer to protobuf-encoded PulumiRequest data with the JSON-encoded UntypedDeployment in deployment
+//   - @param length: Length of the request data in bytes
+//
+// Returns a pointer to C-allocated memory containing length-prefixed PulumiResponse.
+// The caller must free this memory using PulumiFree.
+//
+//export PulumiDynamicImportState
+func PulumiDynamicImportState(requestBytes *C.char, requestLen C.int) *C.char {
+	// Convert C bytes to Go bytes safely
+	goBytes := C.GoBytes(unsafe.Pointer(requestBytes), requestLen)
+
+	// Deserialize protobuf request
+	var request pb.PulumiRequest
+	if err := proto.Unmarshal(goBytes, &request); err != nil {
+		return createFailedResponse(err)
+	}
+
+	var deployment apitype.UntypedDeployment
+	if err := json.Unmarshal(request.Deployment, &deployment); err != nil {
+		return createFailedResponse(fmt.Errorf("failed to decode stack state: %w", err))
+	}
+
+	// Create context for cancellation and the operation deadline
+	ctx, finish := startOperation(request.OperationId, request.TimeoutSeconds)
+	defer finish()
+
+	// Ensure that the working directory exists
+	workDir, err := ensureWorkingDirectory(request.ProjectName)
+	if err != nil {
+		return createFailedResponse(fmt.Errorf("failed to ensure working directory: %w", err))
+	}
+
+	opts, err := workspaceOptions(workDir, request.PulumiConfig)
+	if err != nil {
+		return createFailedResponse(err)
+	}
+
+	stack, err := auto.UpsertStackInlineSource(ctx, request.StackName, request.ProjectName,
+		func(ctx *pulumi.Context) error { return nil },
+		opts...,
+	)
+	if err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
+	}
+
+	if err := stack.Import(ctx, deployment); err != nil {
+		return createOperationFailedResponse(ctx, err, nil)
+	}
+
+	return createOkResponse([]*pb.OutputItem{})
+}
+
+// readDeployment exports the stack state and decodes its resources.
+func readDeployment(ctx context.Context, stack auto.Stack) (apitype.DeploymentV3, error) {
+	var deployment apitype.DeploymentV3
+
+	exported, err := stack.Export(ctx)
+	if err != nil {
+		return deployment, fmt.Errorf("failed to export stack state: %w", err)
+	}
+	if len(exported.Deployment) == 0 {
+		// A stack that has never been deployed has no state yet
+		return deployment, nil
+	}
+	if err := json.Unmarshal(exported.Deployment, &deployment); err != nil {
+		return deployment, fmt.Errorf("failed to decode stack state: %w", err)
+	}
+	return deployment, nil
+}
+
+// urnName returns the resource name part of a URN (urn:pulumi:<stack>::<project>::<type>::<name>).
+func urnName(urn string) string {
+	if i := strings.LastIndex(urn, "::"); i >= 0 {
+		return urn[i+2:]
+	}
+	return urn
+}
+
+// urnType returns the type of the resource a URN identifies, without the types of its parents.
+func urnType(urn string) string {
+	parts := strings.Split(urn, "::")
+	if len(parts) < 4 {
+		return ""
+	}
+	qualifiedType := parts[len(parts)-2]
+	return qualifiedType[strings.LastIndex(qualifiedType, "$")+1:]
+}
+
+// secretSignature is the key that marks an encrypted secret value in exported stack state.
+const secretSignature = "4dabf18193072939515e22adb298388d"
+
+// resourceStates returns the state of the requested resources, matched by name and type.
+// Secret output values are replaced by "[secret]", since the exported state only holds their ciphertext.
+func resourceStates(deployment apitype.DeploymentV3, resources []*pb.Resource) []*pb.ResourceState {
+	requested := make(map[string]string, len(resources))
+	for _, res := range resources {
+		requested[res.Name] = res.Type
+	}
+
+	var states []*pb.ResourceState
+	for _, resource := range deployment.Resources {
+		name := urnName(string(resource.URN))
+		// Resources pending deletion (e.g. replaced ones) share the name of their replacement
+		if resourceType, ok := requested[name]; !ok || resourceType != string(resource.Type) || resource.Delete {
+			continue
+		}
+
+		outputs := make(map[string]*pb.Value, len(resource.Outputs))
+		for key, value := range resource.Outputs {
+			outputs[key] = convertInterfaceToProtoValue(maskSecrets(value))
+		}
+
+		states = append(states, &pb.ResourceState{
+			Name:    name,
+			Type:    string(resource.Type),
+			Urn:     string(resource.URN),
+			Id:      string(resource.ID),
+			Outputs: outputs,
+		})
+	}
+	return states
+}
+
+// maskSecrets replaces the encrypted secrets in a state value with "[secret]".
+func maskSecrets(value interface{}) interface{} {
+	switch v := value.(type) {
+	case map[string]interface{}:
+		if _, secret := v[secretSignature]; secret {
+			return "[secret]"
+		}
+		masked := make(map[string]interface{}, len(v))
+		for key, item := range v {
+			masked[key] = maskSecrets(item)
+		}
+		return masked
+	case []interface{}:
+		masked := make([]interface{}, len(v))
+		for i, item := range v {
+			masked[i] = maskSecrets(item)
+		}
+		return masked
+	default:
+		return v
+	}
+}
@@ -0,0 +1,242 @@
+package main
+
+import (
+	"context"
+	"fmt"
+	pb "github.com/evorine/pulumist/pulumist-go/generated"
+	"github.com/pulumi/pulumi/sdk/v3/go/auto"
+	"github.com/pulumi/pulumi/sdk/v3/go/common/debug"
+	"net/url"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// workspaceOptions translates the request's PulumiConfiguration into the workspace options shared by every
+// operation (preview, up, destroy, refresh and outputs), so they all run against the same backend and secrets.
+//
+// Environment overrides, secrets provider credentials and backend settings are merged into a single EnvVars
+// option, because the workspace keeps only the last EnvVars it is given.
+func workspaceOptions(workDir string, config *pb.PulumiConfiguration) ([]auto.LocalWorkspaceOption, error) {
+	opts := []auto.LocalWorkspaceOption{
+		auto.WorkDir(workDir),
+	}
+	envVars := make(map[string]string)
+
+	for key, value := range config.GetEnvironment() {
+		envVars[key] = value
+	}
+
+	secretsProvider, err := secretsProviderURL(config.GetSecretsProvider(), envVars)
+	if err != nil {
+		return nil, err
+	}
+	opts = append(opts, auto.SecretsProvider(secretsProvider))
+
+	if backend := config.GetBackend(); backend != nil {
+		backendURL, err := backendURL(backend, envVars)
+		if err != nil {
+			return nil, err
+		}
+		envVars["PULUMI_BACKEND_URL"] = backendURL
+	}
+
+	if config.GetPulumiHome() != "" {
+		opts = append(opts, auto.PulumiHome(config.GetPulumiHome()))
+	}
+
+	if len(envVars) > 0 {
+		opts = append(opts, auto.EnvVars(envVars))
+	}
+
+	return opts, nil
+}
+
+// secretsProviderURL returns the secrets provider for auto.SecretsProvider and adds the credentials it needs to envVars.
+//
+// Without a configured provider the passphrase provider is used, reading PULUMI_CONFIG_PASSPHRASE from the host environment.
+func secretsProviderURL(provider *pb.SecretsProvider, envVars map[string]string) (string, error) {
+	switch p := provider.GetProvider().(type) {
+	case nil:
+		return "passphrase", nil
+	case *pb.SecretsProvider_Passphrase:
+		envVars["PULUMI_CONFIG_PASSPHRASE"] = p.Passphrase.GetPassphrase()
+		return "passphrase", nil
+	case *pb.SecretsProvider_Local:
+		// Pulumi has no unencrypted provider; an empty passphrase is the closest equivalent.
+		envVars["PULUMI_CONFIG_PASSPHRASE"] = ""
+		return "passphrase", nil
+	case *pb.SecretsProvider_CloudKms:
+		kms := p.CloudKms
+		for key, value := range kms.GetCredentials() {
+			envVars[key] = value
+		}
+
+		switch kms.GetProviderType() {
+		case "awskms":
+			// Key IDs, ARNs and aliases are all accepted, e.g. awskms://alias/pulumi?region=eu-west-1
+			providerURL := "awskms://" + kms.GetKeyId()
+			if region := kms.GetCredentials()["AWS_REGION"]; region != "" {
+				providerURL += "?region=" + url.QueryEscape(region)
+			}
+			return providerURL, nil
+		case "azurekeyvault":
+			// Key URLs look like https://<vault>.vault.azure.net/keys/<key>
+			return "azurekeyvault://" + strings.TrimPrefix(kms.GetKeyId(), "https://"), nil
+		case "gcpkms":
+			// Key names look like projects/<p>/locations/<l>/keyRings/<r>/cryptoKeys/<k>
+			return "gcpkms://" + kms.GetKeyId(), nil
+		default:
+			return "", fmt.Errorf("unsupported cloud KMS provider type %q", kms.GetProviderType())
+		}
+	default:
+		return "", fmt.Errorf("unsupported secrets provider %T", p)
+	}
+}
+
+// backendURL returns the backend URL for the stack state and adds the credentials it needs to envVars.
+//
+// Credentials are passed through the environment variables each backend reads, so they are never written
+// to the project or stack settings.
+func backendURL(backend *pb.BackendConfig, envVars map[string]string) (string, error) {
+	switch b := backend.GetBackend().(type) {
+	case *pb.BackendConfig_Local:
+		return localBackendURL(b.Local.GetPath())
+	case *pb.BackendConfig_Cloud:
+		if b.Cloud.GetApiToken() != "" {
+			envVars["PULUMI_ACCESS_TOKEN"] = b.Cloud.GetApiToken()
+		}
+		if b.Cloud.GetUrl() == "" {
+			return defaultCloudURL, nil
+		}
+		return b.Cloud.GetUrl(), nil
+	case *pb.BackendConfig_S3:
+		return s3BackendURL(b.S3, envVars)
+	case *pb.BackendConfig_AzureBlob:
+		return azureBlobBackendURL(b.AzureBlob, envVars)
+	default:
+		return "", fmt.Errorf("unsupported backend %T", b)
+	}
+}
+
+// defaultCloudURL is the Pulumi Cloud API used when no URL is configured.
+const defaultCloudURL = "https://api.pulumi.com"
+
+// localBackendURL returns a file:// URL for path, or for ~/.pulumi if path is empty.
+//
+// Relative paths are resolved against the host's working directory rather than the project working
+// directory the pulumi CLI runs in, and the directory is created if needed.
+func localBackendURL(path string) (string, error) {
+	if path == "" {
+		return "file://~", nil
+	}
+
+	absPath, err := filepath.Abs(path)
+	if err != nil {
+		return "", fmt.Errorf("failed to resolve local backend path %q: %w", path, err)
+	}
+	if err := os.MkdirAll(absPath, 0755); err != nil {
+		return "", fmt.Errorf("failed to create local backend directory: %w", err)
+	}
+
+	// Absolute paths start with "/" on Unix, giving file:///path. Windows paths become file://C:/path.
+	return "file://" + filepath.ToSlash(absPath), nil
+}
+
+// s3BackendURL returns an s3:// URL for the bucket. A custom endpoint switches to path-style addressing,
+// which S3-compatible servers such as MinIO expect, and disables TLS for http:// endpoints.
+func s3BackendURL(s3 *pb.S3Backend, envVars map[string]string) (string, error) {
+	if s3.GetBucket() == "" {
+		return "", fmt.Errorf("a bucket is required for the S3 backend")
+	}
+
+	query := url.Values{}
+	if s3.GetRegion() != "" {
+		query.Set("region", s3.GetRegion())
+		envVars["AWS_REGION"] = s3.GetRegion()
+	}
+	if endpoint := s3.GetEndpoint(); endpoint != "" {
+		query.Set("endpoint", endpoint)
+		query.Set("s3ForcePathStyle", "true")
+		if strings.HasPrefix(endpoint, "http://") {
+			query.Set("disableSSL", "true")
+		}
+	}
+
+	if s3.GetAccessKey() != "" {
+		envVars["AWS_ACCESS_KEY_ID"] = s3.GetAccessKey()
+	}
+	if s3.GetSecretKey() != "" {
+		envVars["AWS_SECRET_ACCESS_KEY"] = s3.GetSecretKey()
+	}
+	if s3.GetSessionToken() != "" {
+		envVars["AWS_SESSION_TOKEN"] = s3.GetSessionToken()
+	}
+
+	backendURL := "s3://" + s3.GetBucket()
+	if len(query) > 0 {
+		backendURL += "?" + query.Encode()
+	}
+	return backendURL, nil
+}
+
+// azureBlobBackendURL returns an azblob:// URL for the container. The storage account is authenticated
+// with the access key, a SAS token, or, if neither is given, the ambient Azure credentials.
+func azureBlobBackendURL(azure *pb.AzureBlobBackend, envVars map[string]string) (string, error) {
+	if azure.GetStorageAccount() == "" || azure.GetContainer() == "" {
+		return "", fmt.Errorf("a storage account and a container are required for the Azure Blob backend")
+	}
+
+	envVars["AZURE_STORAGE_ACCOUNT"] = azure.GetStorageAccount()
+	if azure.GetAccessKey() != "" {
+		envVars["AZURE_STORAGE_KEY"] = azure.GetAccessKey()
+	}
+	if azure.GetSasToken() != "" {
+		// SAS tokens are often copied with their leading "?"
+		envVars["AZURE_STORAGE_SAS_TOKEN"] = strings.TrimPrefix(azure.GetSasToken(), "?")
+	}
+
+	return "azblob://" + azure.GetContainer(), nil
+}
+
+// applyStackConfig sets the request's stack configuration with SetAllConfig. Keys are path-style, so a key
+// such as "app:db.hosts[0]" sets a nested value; secret values are encrypted with the stack's secrets provider.
+func applyStackConfig(ctx context.Context, stack auto.Stack, config map[string]*pb.ConfigValue) error {
+	if len(config) == 0 {
+		return nil
+	}
+
+	configMap := make(auto.ConfigMap, len(config))
+	for key, value := range config {
+		configMap[key] = auto.ConfigValue{
+			Value:  value.GetValue(),
+			Secret: value.GetSecret(),
+		}
+	}
+
+	if err := stack.SetAllConfigWithOptions(ctx, configMap, &auto.ConfigOptions{Path: true}); err != nil {
+		return fmt.Errorf("failed to set stack config: %w", err)
+	}
+	return nil
+}
+
+// debugLogging translates PulumiConfiguration.log_level into engine logging options for an operation.
+// Returns nil when the default logging should be kept.
+func debugLogging(config *pb.PulumiConfiguration) *debug.LoggingOptions {
+	var level uint
+	switch config.GetLogLevel() {
+	case "debug":
+		level = 9
+	case "info":
+		level = 3
+	default:
+		// warn and error are what the engine reports without verbose logging
+		return nil
+	}
+
+	return &debug.LoggingOptions{
+		LogLevel:    &level,
+		LogToStdErr: true,
+		Debug:       level >= 9,
+	}
+}
@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use crate::error::{PulumistError, Result};
+
+/// Handle for cancelling in-flight stack operations.
+///
+/// Pass a clone to an operation builder with `with_cancellation_token` and call
+/// [`cancel`](CancellationToken::cancel) from anywhere else. The Go side cancels the
+/// operation's context, which interrupts the Pulumi CLI so it checkpoints the steps in
+/// flight instead of being killed mid-write, and also cancels the update on backends
+/// that support it. The operation then fails with [`PulumistError::Cancelled`].
+///
+/// A token stays cancelled: operations started with an already cancelled token fail
+/// immediately without calling into Go.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<Mutex<TokenState>>,
+}
+
+#[derive(Default)]
+struct TokenState {
+    cancelled: bool,
+    operations: HashSet<u64>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels every operation currently running with this token.
+    pub fn cancel(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.cancelled {
+            return;
+        }
+        state.cancelled = true;
+
+        // Held under the lock so an operation cannot detach and finish in between
+        for operation_id in state.operations.drain() {
+            unsafe {
+                crate::PulumiDynamicCancel(operation_id);
+            }
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).cancelled
+    }
+
+    /// Cancels this token when the process receives Ctrl-C.
+    ///
+    /// Intended for CLI tools: the signal stops running operations cleanly and the
+    /// caller gets [`PulumistError::Cancelled`] back instead of the process dying
+    /// mid-deployment. Must be called from within a tokio runtime.
+    pub fn cancel_on_ctrl_c(&self) -> tokio::task::JoinHandle<()> {
+        let token = self.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                token.cancel();
+            }
+        })
+    }
+
+    /// Ties `operation_id` to this token, failing if it is already cancelled.
+    pub(crate) fn attach(&self, operation_id: u64) -> Result<()> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.cancelled {
+            return Err(PulumistError::Cancelled);
+        }
+        state.operations.insert(operation_id);
+        Ok(())
+    }
+
+    pub(crate) fn detach(&self, operation_id: u64) {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).operations.remove(&operation_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancelled_token_rejects_new_operations() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+
+        token.attach(1).unwrap();
+        token.detach(1);
+        assert!(!clone.is_cancelled());
+
+        clone.cancel();
+        assert!(token.is_cancelled());
+        assert!(matches!(token.attach(2), Err(PulumistError::Cancelled)));
+    }
+}
@@ -4,6 +4,8 @@
 //! without dealing with protobuf types directly.
 
 use std::collections::HashMap;
+use serde_json::Value;
+use crate::error::{PulumistError, Result};
 
 /// Configuration for Pulumi operations
 #[derive(Debug, Clone, Default)]
@@ -60,7 +62,7 @@ impl Default for SecretsConfig {
 /// Backend storage configuration
 #[derive(Debug, Clone)]
 pub enum BackendConfig {
-    /// Local file storage (default)
+    /// Local file storage (default), in `~/.pulumi` if no path is given
     Local {
         path: Option<String>,
     },
@@ -70,9 +72,11 @@ pub enum BackendConfig {
         region: String,
         access_key_id: Option<String>,
         secret_access_key: Option<String>,
+        /// Custom endpoint for S3-compatible servers such as MinIO
         endpoint: Option<String>,
     },
-    /// Azure Blob Storage backend
+    /// Azure Blob Storage backend, authenticated with the access key, the SAS token,
+    /// or the ambient Azure credentials if neither is set
     AzureBlob {
         storage_account: String,
         container: String,
@@ -352,13 +356,14 @@ impl PulumiConfig {
 
         let backend = match &self.backend {
             BackendConfig::Local { path } => {
-                path.as_ref().map(|p| PbBackendConfig {
+                // An empty path selects ~/.pulumi
+                Some(PbBackendConfig {
                     backend: Some(backend_config::Backend::Local(LocalBackend {
-                        path: p.clone(),
+                        path: path.clone().unwrap_or_default(),
                     })),
                 })
             }
-            BackendConfig::S3 { bucket, region, access_key_id, secret_access_key, .. } => {
+            BackendConfig::S3 { bucket, region, access_key_id, secret_access_key, endpoint } => {
                 Some(PbBackendConfig {
                     backend: Some(backend_config::Backend::S3(S3Backend {
                         bucket: bucket.clone(),
@@ -366,6 +371,7 @@ impl PulumiConfig {
                         access_key: access_key_id.clone().unwrap_or_default(),
                         secret_key: secret_access_key.clone().unwrap_or_default(),
                         session_token: String::new(),
+                        endpoint: endpoint.clone().unwrap_or_default(),
                     })),
                 })
             }
@@ -397,4 +403,124 @@ impl PulumiConfig {
             log_level: self.log_level.clone().unwrap_or_default(),
         })
     }
-}
\ No newline at end of file
+}
+
+/// Converts stack configuration into the path-style entries Go applies with `SetAllConfig`.
+///
+/// Structured values are flattened the way `pulumi config set --path` expects them:
+/// objects become `key.field` (or `key["field"]` for names that are not plain) and arrays
+/// `key[0]`. Values Pulumi config cannot hold, `null` and empty objects or arrays, are
+/// rejected instead of being dropped, as are keys that end up set twice.
+pub(crate) fn stack_config_to_protobuf(
+    config: &serde_json::Map<String, Value>,
+    secret_config: &serde_json::Map<String, Value>,
+) -> Result<HashMap<String, crate::proto::pulumist::ConfigValue>> {
+    let mut entries = HashMap::new();
+
+    for (configs, secret) in [(config, false), (secret_config, true)] {
+        for (key, value) in configs {
+            if key.is_empty() {
+                return Err(PulumistError::ConfigError("Config keys must not be empty".to_string()));
+            }
+
+            let mut leaves = Vec::new();
+            flatten_config_value(key.clone(), value, &mut leaves)?;
+
+            for (path, value) in leaves {
+                let entry = crate::proto::pulumist::ConfigValue { value, secret };
+                if entries.insert(path.clone(), entry).is_some() {
+                    return Err(PulumistError::ConfigError(format!("Config key '{}' is set more than once", path)));
+                }
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+fn flatten_config_value(path: String, value: &Value, leaves: &mut Vec<(String, String)>) -> Result<()> {
+    match value {
+        Value::String(s) => leaves.push((path, s.clone())),
+        Value::Number(n) => leaves.push((path, n.to_string())),
+        Value::Bool(b) => leaves.push((path, b.to_string())),
+        Value::Null => {
+            return Err(PulumistError::ConfigError(format!("Config value '{}' is null", path)));
+        }
+        Value::Array(items) if items.is_empty() => {
+            return Err(PulumistError::ConfigError(format!("Config value '{}' is an empty array", path)));
+        }
+        Value::Object(fields) if fields.is_empty() => {
+            return Err(PulumistError::ConfigError(format!("Config value '{}' is an empty object", path)));
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                flatten_config_value(format!("{}[{}]", path, index), item, leaves)?;
+            }
+        }
+        Value::Object(fields) => {
+            for (name, field) in fields {
+                let field_path = if name.contains('"') {
+                    return Err(PulumistError::ConfigError(format!(
+                        "Config field '{}' in '{}' contains a double quote", name, path
+                    )));
+                } else if name.is_empty() || name.contains(['.', '[', ']']) {
+                    format!("{}[\"{}\"]", path, name)
+                } else {
+                    format!("{}.{}", path, name)
+                };
+                flatten_config_value(field_path, field, leaves)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entries(config: Value, secret_config: Value) -> Result<Vec<(String, String, bool)>> {
+        let (Value::Object(config), Value::Object(secret_config)) = (config, secret_config) else {
+            panic!("config must be objects");
+        };
+        let mut entries: Vec<_> = stack_config_to_protobuf(&config, &secret_config)?
+            .into_iter()
+            .map(|(key, value)| (key, value.value, value.secret))
+            .collect();
+        entries.sort();
+        Ok(entries)
+    }
+
+    #[test]
+    fn test_stack_config_flattens_structured_values() {
+        let entries = entries(
+            json!({
+                "azure-native:location": "eastus",
+                "app:replicas": 3,
+                "app:debug": false,
+                "app:db": { "hosts": ["a", "b"], "dotted.name": 1.5 },
+            }),
+            json!({ "app:password": "hunter2" }),
+        ).unwrap();
+
+        assert_eq!(entries, vec![
+            ("app:db.hosts[0]".to_string(), "a".to_string(), false),
+            ("app:db.hosts[1]".to_string(), "b".to_string(), false),
+            ("app:db[\"dotted.name\"]".to_string(), "1.5".to_string(), false),
+            ("app:debug".to_string(), "false".to_string(), false),
+            ("app:password".to_string(), "hunter2".to_string(), true),
+            ("app:replicas".to_string(), "3".to_string(), false),
+            ("azure-native:location".to_string(), "eastus".to_string(), false),
+        ]);
+    }
+
+    #[test]
+    fn test_stack_config_rejects_unrepresentable_values() {
+        assert!(entries(json!({ "app:missing": null }), json!({})).is_err());
+        assert!(entries(json!({ "app:list": [] }), json!({})).is_err());
+        assert!(entries(json!({ "app:db": { "tags": {} } }), json!({})).is_err());
+        // The same leaf set through a structured value and a path-style key
+        assert!(entries(json!({ "app:db": { "host": "a" }, "app:db.host": "b" }), json!({})).is_err());
+    }
+}
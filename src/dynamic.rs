@@ -1,8 +1,12 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::os::raw::c_char;
 use prost::Message;
-use crate::{proto, FreeAllocation, PulumiDynamicDeploy, PulumiDynamicDestroy, PulumiDynamicGetOutputs, PulumiDynamicPreview, PulumiDynamicRefresh};
+use crate::config::PulumiConfig;
+use crate::error::{PulumistError, Result};
+use crate::results::{DestroyResult, ImportResult, PreviewResult, RefreshResult, UpResult};
+use crate::{proto, FreeAllocation, PulumiDynamicDeploy, PulumiDynamicDestroy, PulumiDynamicExportState, PulumiDynamicGetOutputs, PulumiDynamicImport, PulumiDynamicImportState, PulumiDynamicInvoke, PulumiDynamicPreview, PulumiDynamicRefresh};
 
 // Dynamic resource representation
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -14,46 +18,355 @@ pub struct DynamicResource {
     pub options: Option<ResourceOptions>,
 }
 
+impl DynamicResource {
+    /// An explicit provider instance with its own settings, e.g. another Azure subscription or AWS region.
+    /// Resources use it by naming it in [`ResourceOptions::provider`].
+    ///
+    /// ```
+    /// # use pulumist::dynamic::DynamicResource;
+    /// # use serde_json::json;
+    /// let west = DynamicResource::provider("aws", "aws-west", json!({ "region": "us-west-2" }));
+    /// assert_eq!(west.resource_type, "pulumi:providers:aws");
+    /// ```
+    pub fn provider(package: &str, name: &str, settings: Value) -> Self {
+        DynamicResource {
+            resource_type: format!("{}{}", PROVIDER_TYPE_PREFIX, package),
+            name: name.to_string(),
+            properties: settings,
+            options: None,
+        }
+    }
+
+    /// An existing resource read by its provider-specific ID, e.g. a shared VNet owned by another team.
+    /// Its outputs can be referenced like those of other resources, but it is never created, updated
+    /// or deleted.
+    pub fn get(resource_type: &str, name: &str, id: &str) -> Self {
+        DynamicResource {
+            resource_type: resource_type.to_string(),
+            name: name.to_string(),
+            properties: Value::Object(serde_json::Map::new()),
+            options: Some(ResourceOptions {
+                id: Some(id.to_string()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Whether this is an existing resource that is only read, see [`DynamicResource::get`].
+    pub fn is_read(&self) -> bool {
+        self.options.as_ref().is_some_and(|options| options.id.is_some())
+    }
+
+    /// Whether this is an explicit provider instance, see [`DynamicResource::provider`].
+    pub fn is_provider(&self) -> bool {
+        self.resource_type.starts_with(PROVIDER_TYPE_PREFIX)
+    }
+}
+
+/// Type prefix of provider resources, followed by the package name, e.g. `pulumi:providers:azure-native`
+pub const PROVIDER_TYPE_PREFIX: &str = "pulumi:providers:";
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct ResourceOptions {
+    /// Name of the resource to nest this resource under
     pub parent: Option<String>,
     #[serde(rename = "dependsOn")]
     pub depends_on: Option<Vec<String>>,
+    /// Name of the provider resource to use instead of the default provider, see [`DynamicResource::provider`]
     pub provider: Option<String>,
+    /// Fails any operation that would delete the resource, including replacements
+    pub protect: Option<bool>,
+    /// Properties whose changes are ignored when diffing, e.g. `"tags"` or `"sku.name"`
+    #[serde(rename = "ignoreChanges")]
+    pub ignore_changes: Option<Vec<String>>,
+    /// Removes the resource from the stack without deleting the cloud resource
+    #[serde(rename = "retainOnDelete")]
+    pub retain_on_delete: Option<bool>,
+    /// Properties whose changes force a replacement, `"*"` for any change
+    #[serde(rename = "replaceOnChanges")]
+    pub replace_on_changes: Option<Vec<String>>,
+    /// Deletes the old resource before creating its replacement, for resources with unique names
     #[serde(rename = "deleteBeforeReplace")]
     pub delete_before_replace: Option<bool>,
+    /// Outputs to store encrypted, in addition to the ones the provider marks as secret
+    #[serde(rename = "additionalSecretOutputs")]
+    pub additional_secret_outputs: Option<Vec<String>>,
+    #[serde(rename = "customTimeouts")]
+    pub custom_timeouts: Option<CustomTimeouts>,
+    /// Name of a resource whose deletion also deletes this one, so deleting this resource is skipped
+    #[serde(rename = "deletedWith")]
+    pub deleted_with: Option<String>,
+    /// Previous identities of the resource, so renaming it or moving it to another parent updates
+    /// the existing resource instead of replacing it
+    pub aliases: Option<Vec<Alias>>,
+    /// Provider-specific ID of an existing resource to read instead of managing it, see [`DynamicResource::get`]
+    pub id: Option<String>,
+}
+
+/// A previous identity of a resource. Unset fields default to the resource's current values, so
+/// `Alias { name: Some("old-name".into()), ..Default::default() }` covers a rename.
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct Alias {
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub resource_type: Option<String>,
+    /// Name of the previous parent resource in the same deployment
+    pub parent: Option<String>,
+    /// URN of the previous parent resource, for parents that are no longer deployed
+    #[serde(rename = "parentUrn")]
+    pub parent_urn: Option<String>,
+    /// The resource previously had no parent
+    #[serde(rename = "noParent", default)]
+    pub no_parent: bool,
+    pub stack: Option<String>,
+    pub project: Option<String>,
+    /// Full URN of the previous resource, the other fields are ignored when set
+    pub urn: Option<String>,
+}
+
+impl Alias {
+    fn to_protobuf(&self) -> proto::pulumist::Alias {
+        proto::pulumist::Alias {
+            name: self.name.clone().unwrap_or_default(),
+            r#type: self.resource_type.clone().unwrap_or_default(),
+            parent: self.parent.clone().unwrap_or_default(),
+            parent_urn: self.parent_urn.clone().unwrap_or_default(),
+            no_parent: self.no_parent,
+            stack: self.stack.clone().unwrap_or_default(),
+            project: self.project.clone().unwrap_or_default(),
+            urn: self.urn.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Per-resource step timeouts as Pulumi duration strings, e.g. `"10m"` or `"1h30m"`.
+/// Unset steps use the provider's default.
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct CustomTimeouts {
+    pub create: Option<String>,
+    pub update: Option<String>,
+    pub delete: Option<String>,
 }
 
 // Stack request for operations
 #[derive(Debug, Serialize)]
 pub struct StackRequest {
+    /// Routes this operation's events back to its caller, see [`crate::events::next_operation_id`].
+    #[serde(rename = "operationId")]
+    pub operation_id: u64,
     pub project: String,
     pub stack: String,
-    pub backend: Option<String>,
     pub config: serde_json::Map<String, Value>,
+    /// Config values stored encrypted with the stack's secrets provider, never serialized
+    #[serde(skip)]
+    pub secret_config: serde_json::Map<String, Value>,
     pub resources: Vec<DynamicResource>,
+    /// Deadline for the whole operation, `None` waits indefinitely
+    #[serde(rename = "timeoutSeconds")]
+    pub timeout_seconds: Option<u32>,
+    /// Refresh only: report the changes without writing them to the stack state
+    #[serde(rename = "previewOnly")]
+    pub preview_only: bool,
+    /// Deploy and preview only: stack outputs exported by the program
+    pub outputs: Vec<StackOutput>,
+    /// Deploy and preview only: component resources, registered along with `resources`
+    pub components: Vec<ComponentResource>,
+    /// Deploy and preview only: provider functions whose results `resources` and `outputs` may reference
+    pub invokes: Vec<Invoke>,
+    /// Secrets provider, backend and environment for the Go workspace
+    #[serde(skip)]
+    pub pulumi_config: PulumiConfig,
+}
+
+/// A logical group of resources, registered as a Pulumi component resource with its own type token,
+/// e.g. `acme:web:StaticSite`. Its children are parented to it unless they name another parent, so
+/// they show up under it in the stack state and in events.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ComponentResource {
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub name: String,
+    pub children: Vec<DynamicResource>,
+    /// Outputs registered on the component. Strings may reference any resource, e.g.
+    /// `"${site-storage.primaryEndpoints.web}"`, and stack outputs may reference these outputs.
+    pub outputs: serde_json::Map<String, Value>,
+    pub options: Option<ResourceOptions>,
+}
+
+impl ComponentResource {
+    pub fn new(resource_type: &str, name: &str) -> Self {
+        ComponentResource {
+            resource_type: resource_type.to_string(),
+            name: name.to_string(),
+            children: vec![],
+            outputs: serde_json::Map::new(),
+            options: None,
+        }
+    }
+
+    pub fn with_child(mut self, resource: DynamicResource) -> Self {
+        self.children.push(resource);
+        self
+    }
+
+    /// Registers `value` as the component output `name`.
+    pub fn with_output(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.outputs.insert(name.to_string(), value.into());
+        self
+    }
+}
+
+/// Appends the children of every component to `resources`, with the component as default parent, and
+/// the resource registering the component. Returns the outputs of every component by name.
+pub(crate) fn flatten_components(
+    mut resources: Vec<DynamicResource>,
+    components: Vec<ComponentResource>,
+) -> (Vec<DynamicResource>, HashMap<String, serde_json::Map<String, Value>>) {
+    let mut component_outputs = HashMap::new();
+    for component in components {
+        for mut child in component.children {
+            let options = child.options.get_or_insert_with(ResourceOptions::default);
+            if options.parent.is_none() {
+                options.parent = Some(component.name.clone());
+            }
+            resources.push(child);
+        }
+        // The outputs are registered after all resources, so they are no dependency of the component
+        resources.push(DynamicResource {
+            resource_type: component.resource_type,
+            name: component.name.clone(),
+            properties: Value::Object(serde_json::Map::new()),
+            options: component.options,
+        });
+        component_outputs.insert(component.name, component.outputs);
+    }
+    (resources, component_outputs)
+}
+
+/// A stack output exported by the deployment program.
+///
+/// String values may contain `${resource.property}` references, which are resolved like resource properties.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StackOutput {
+    pub name: String,
+    pub value: Value,
+    /// Stored encrypted with the stack's secrets provider
+    pub secret: bool,
+}
+
+/// A provider function call, e.g. `azure-native:authorization:getClientConfig`, made by the
+/// deployment program before the resources that use its result.
+///
+/// Resource properties and stack outputs reference fields of the result by the invoke's name, e.g.
+/// `"${client.tenantId}"`. The arguments are literal values, they cannot reference resources.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Invoke {
+    pub name: String,
+    pub token: String,
+    pub args: serde_json::Map<String, Value>,
+    /// Name of an explicit provider resource to call the function with, see [`DynamicResource::provider`]
+    pub provider: Option<String>,
+}
+
+impl Invoke {
+    pub fn new(name: &str, token: &str) -> Self {
+        Invoke {
+            name: name.to_string(),
+            token: token.to_string(),
+            args: serde_json::Map::new(),
+            provider: None,
+        }
+    }
+
+    pub fn with_arg(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.args.insert(name.to_string(), value.into());
+        self
+    }
+
+    pub fn with_provider(mut self, provider: &str) -> Self {
+        self.provider = Some(provider.to_string());
+        self
+    }
+
+    fn to_protobuf(&self, dynamic: &PulumiDynamic) -> Result<proto::pulumist::Invoke> {
+        if self.name.is_empty() || self.token.is_empty() {
+            return Err(PulumistError::ConfigError("invoke name and token must not be empty".to_string()));
+        }
+        Ok(proto::pulumist::Invoke {
+            name: self.name.clone(),
+            token: self.token.clone(),
+            args: dynamic.json_to_pb_map(&Value::Object(self.args.clone())),
+            provider: self.provider.clone().unwrap_or_default(),
+        })
+    }
+}
+
+/// A stack's deployment checkpoint in the format of `pulumi stack export`.
+///
+/// `deployment` holds the resources, pending operations and secrets provider state. Secret values
+/// stay encrypted with the stack's secrets provider.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UntypedDeployment {
+    /// Schema version of `deployment`, 3 for current Pulumi versions
+    pub version: i64,
+    pub deployment: Value,
+}
+
+impl UntypedDeployment {
+    /// The resources recorded in the checkpoint, empty for a stack that was never deployed.
+    pub fn resources(&self) -> &[Value] {
+        self.deployment.get("resources")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// An existing cloud resource to adopt into the stack, like an entry of Pulumi's import file.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportResource {
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub name: String,
+    /// Provider-specific ID of the cloud resource, e.g. an Azure resource ID
+    pub id: String,
+    /// Name of the parent resource, either already in the stack or imported alongside
+    pub parent: Option<String>,
+    /// Name of an explicit provider resource, either already in the stack or imported alongside
+    pub provider: Option<String>,
+}
+
+impl ImportResource {
+    pub fn new(resource_type: &str, name: &str, id: &str) -> Self {
+        Self {
+            resource_type: resource_type.to_string(),
+            name: name.to_string(),
+            id: id.to_string(),
+            parent: None,
+            provider: None,
+        }
+    }
 }
 
 // Import request for importing existing resources
 #[derive(Debug, Serialize)]
 pub struct ImportRequest {
+    #[serde(rename = "operationId")]
+    pub operation_id: u64,
     pub project: String,
     pub stack: String,
-    pub backend: Option<String>,
-    #[serde(rename = "resourceType")]
-    pub resource_type: String,
-    #[serde(rename = "resourceName")]
-    pub resource_name: String,
-    #[serde(rename = "resourceId")]
-    pub resource_id: String,
-    pub resources: Vec<DynamicResource>,
+    pub resources: Vec<ImportResource>,
     pub config: serde_json::Map<String, Value>,
-    pub outputs: serde_json::Map<String, Value>,
+    #[serde(skip)]
+    pub secret_config: serde_json::Map<String, Value>,
+    #[serde(rename = "timeoutSeconds")]
+    pub timeout_seconds: Option<u32>,
+    #[serde(skip)]
+    pub pulumi_config: PulumiConfig,
 }
 
-
 // Safe wrapper around FFI calls
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct PulumiDynamic;
 
 impl PulumiDynamic {
@@ -65,7 +378,7 @@ impl PulumiDynamic {
     fn call_go_function_pb(
         func: unsafe extern "C" fn(*const c_char, i32) -> *mut c_char,
         request: &proto::pulumist::PulumiRequest,
-    ) -> Result<proto::pulumist::PulumiResponse, String> {
+    ) -> Result<proto::pulumist::PulumiResponse> {
         let request_bytes = request.encode_to_vec();
         let request_len = request_bytes.len() as i32;
 
@@ -74,7 +387,7 @@ impl PulumiDynamic {
         };
 
         if response_ptr.is_null() {
-            return Err("Received null response from Go".to_string());
+            return Err(PulumistError::Ffi("Received null response from Go".to_string()));
         }
 
         // Read the length prefix (4 bytes little-endian)
@@ -90,12 +403,11 @@ impl PulumiDynamic {
             std::slice::from_raw_parts((response_ptr as *const u8).offset(4), response_len)
         };
 
-        let response = proto::pulumist::PulumiResponse::decode(response_bytes)
-            .map_err(|e| format!("Failed to decode protobuf response: {}", e))?;
+        let response = proto::pulumist::PulumiResponse::decode(response_bytes);
 
         unsafe { FreeAllocation(response_ptr); }
 
-        Ok(response)
+        Ok(response?)
     }
 
     /// Performs a preview (dry-run) of infrastructure changes.
@@ -107,65 +419,64 @@ impl PulumiDynamic {
     /// * `request` - Stack configuration including resources to preview
     ///
     /// # Returns
-    /// * `Ok(Value)` - JSON value with preview results
-    /// * `Err(String)` - Error message if preview fails
-    ///
-    /// # Production Improvements
-    /// - Add timeout support
-    /// - Return typed PreviewResponse instead of Value
-    /// - Add progress callback for long operations
-    pub fn preview(&self, request: StackRequest) -> Result<Value, String> {
-        // Convert StackRequest to protobuf
-        let pb_request = proto::pulumist::PulumiRequest {
-            working_dir: request.project.clone(),
-            stack_name: request.stack.clone(),
-            project_name: request.project.clone(),
-            resources: request.resources.into_iter().map(|r| {
-                proto::pulumist::Resource {
-                    r#type: r.resource_type,
-                    name: r.name,
-                    properties: self.json_to_pb_map(&r.properties),
-                    depends_on: r.options.as_ref()
-                        .and_then(|o| o.depends_on.clone())
-                        .unwrap_or_default(),
-                    provider: r.options.as_ref()
-                        .and_then(|o| o.provider.clone())
-                        .unwrap_or_default(),
-                }
-            }).collect(),
-            config: request.config.into_iter()
-                .map(|(k, v)| (k, v.as_str().unwrap_or("").to_string()))
-                .collect(),
-            pulumi_config: None,
-        };
+    /// * `Ok(PreviewResult)` - Change counts and output of the preview
+    /// * `Err(PulumistError)` - [`PulumistError::Cancelled`] if the preview was cancelled,
+    ///   otherwise the error reported by Pulumi
+    pub fn preview(&self, request: StackRequest) -> Result<PreviewResult> {
+        self.call_operation(PulumiDynamicPreview, request).map(PreviewResult::from)
+    }
 
-        let response = Self::call_go_function_pb(PulumiDynamicPreview, &pb_request)?;
-
-        if response.success {
-            // Convert outputs to JSON value
-            let mut result = serde_json::Map::new();
-            for output in response.outputs {
-                if let Some(value) = output.value {
-                    result.insert(
-                        format!("{}.{}", output.resource_name, output.output_name),
-                        self.pb_value_to_json(&value),
-                    );
-                }
-            }
-            Ok(Value::Object(result))
-        } else {
-            Err(response.error)
-        }
+    pub fn deploy(&self, request: StackRequest) -> Result<UpResult> {
+        self.call_operation(PulumiDynamicDeploy, request).map(UpResult::from)
+    }
+
+    // Runs a preview, up, destroy or refresh through a Go export and returns its result
+    fn call_operation(
+        &self,
+        func: unsafe extern "C" fn(*const c_char, i32) -> *mut c_char,
+        request: StackRequest,
+    ) -> Result<proto::pulumist::OperationResult> {
+        let pb_request = self.to_pb_request(request)?;
+        Self::call_operation_pb(func, &pb_request)
+    }
+
+    // Like call_operation, for requests that are not built from a StackRequest alone
+    fn call_operation_pb(
+        func: unsafe extern "C" fn(*const c_char, i32) -> *mut c_char,
+        pb_request: &proto::pulumist::PulumiRequest,
+    ) -> Result<proto::pulumist::OperationResult> {
+        let response = Self::call_go_function_pb(func, pb_request)?;
+        Self::check_response(&response, pb_request.timeout_seconds)?;
+        response.result
+            .ok_or_else(|| PulumistError::Ffi("Response is missing the operation result".to_string()))
+    }
+
+    // Runs a StackRequest through a Go export and converts its response
+    fn call(
+        &self,
+        func: unsafe extern "C" fn(*const c_char, i32) -> *mut c_char,
+        request: StackRequest,
+    ) -> Result<Value> {
+        let pb_request = self.to_pb_request(request)?;
+        let response = Self::call_go_function_pb(func, &pb_request)?;
+        self.response_to_json(response, pb_request.timeout_seconds)
     }
 
-    pub fn deploy(&self, request: StackRequest) -> Result<Value, String> {
-        // Convert StackRequest to protobuf
-        let pb_request = proto::pulumist::PulumiRequest {
+    // Helper to convert a StackRequest to its protobuf form
+    fn to_pb_request(&self, request: StackRequest) -> Result<proto::pulumist::PulumiRequest> {
+        let (resources, mut component_outputs) = flatten_components(request.resources, request.components);
+        Ok(proto::pulumist::PulumiRequest {
             working_dir: request.project.clone(),
             stack_name: request.stack.clone(),
             project_name: request.project.clone(),
-            resources: request.resources.into_iter().map(|r| {
+            // Resources are registered in order, so referenced resources must come first
+            resources: crate::graph::order_resources(resources, &request.invokes)?.into_iter().map(|r| {
+                let outputs = component_outputs.remove(&r.name);
                 proto::pulumist::Resource {
+                    component: outputs.is_some(),
+                    component_outputs: outputs
+                        .map(|outputs| self.json_to_pb_map(&Value::Object(outputs)))
+                        .unwrap_or_default(),
                     r#type: r.resource_type,
                     name: r.name,
                     properties: self.json_to_pb_map(&r.properties),
@@ -175,35 +486,106 @@ impl PulumiDynamic {
                     provider: r.options.as_ref()
                         .and_then(|o| o.provider.clone())
                         .unwrap_or_default(),
+                    parent: r.options.as_ref()
+                        .and_then(|o| o.parent.clone())
+                        .unwrap_or_default(),
+                    protect: r.options.as_ref()
+                        .and_then(|o| o.protect)
+                        .unwrap_or_default(),
+                    ignore_changes: r.options.as_ref()
+                        .and_then(|o| o.ignore_changes.clone())
+                        .unwrap_or_default(),
+                    retain_on_delete: r.options.as_ref()
+                        .and_then(|o| o.retain_on_delete)
+                        .unwrap_or_default(),
+                    replace_on_changes: r.options.as_ref()
+                        .and_then(|o| o.replace_on_changes.clone())
+                        .unwrap_or_default(),
+                    delete_before_replace: r.options.as_ref()
+                        .and_then(|o| o.delete_before_replace)
+                        .unwrap_or_default(),
+                    additional_secret_outputs: r.options.as_ref()
+                        .and_then(|o| o.additional_secret_outputs.clone())
+                        .unwrap_or_default(),
+                    deleted_with: r.options.as_ref()
+                        .and_then(|o| o.deleted_with.clone())
+                        .unwrap_or_default(),
+                    id: r.options.as_ref()
+                        .and_then(|o| o.id.clone())
+                        .unwrap_or_default(),
+                    aliases: r.options.as_ref()
+                        .and_then(|o| o.aliases.as_ref())
+                        .map(|aliases| aliases.iter().map(Alias::to_protobuf).collect())
+                        .unwrap_or_default(),
+                    custom_timeouts: r.options.as_ref()
+                        .and_then(|o| o.custom_timeouts.as_ref())
+                        .map(|t| proto::pulumist::CustomTimeouts {
+                            create: t.create.clone().unwrap_or_default(),
+                            update: t.update.clone().unwrap_or_default(),
+                            delete: t.delete.clone().unwrap_or_default(),
+                        }),
                 }
             }).collect(),
-            config: request.config.into_iter()
-                .map(|(k, v)| (k, v.as_str().unwrap_or("").to_string()))
-                .collect(),
-            pulumi_config: None,
-        };
-
-        let response = Self::call_go_function_pb(PulumiDynamicDeploy, &pb_request)?;
-
-        if response.success {
-            // Convert outputs to JSON value
-            let mut result = serde_json::Map::new();
-            for output in response.outputs {
-                if let Some(value) = output.value {
-                    result.insert(
-                        format!("{}.{}", output.resource_name, output.output_name),
-                        self.pb_value_to_json(&value),
-                    );
+            config: crate::config::stack_config_to_protobuf(&request.config, &request.secret_config)?,
+            pulumi_config: request.pulumi_config.to_protobuf(),
+            operation_id: request.operation_id,
+            timeout_seconds: request.timeout_seconds.unwrap_or_default(),
+            preview_only: request.preview_only,
+            imports: vec![],
+            deployment: vec![],
+            outputs: request.outputs.into_iter().map(|output| {
+                if output.name.is_empty() {
+                    return Err(PulumistError::ConfigError("stack output name must not be empty".to_string()));
                 }
+                Ok(proto::pulumist::StackOutput {
+                    name: output.name,
+                    value: Some(self.json_to_pb_value(&output.value)),
+                    secret: output.secret,
+                })
+            }).collect::<Result<_>>()?,
+            invokes: request.invokes.iter()
+                .map(|invoke| invoke.to_protobuf(self))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    // Helper to flatten response outputs into a "resource.output" keyed JSON object
+    fn response_to_json(&self, response: proto::pulumist::PulumiResponse, timeout_seconds: u32) -> Result<Value> {
+        Self::check_response(&response, timeout_seconds)?;
+
+        let mut result = serde_json::Map::new();
+        for output in response.outputs {
+            if let Some(value) = output.value {
+                result.insert(
+                    format!("{}.{}", output.resource_name, output.output_name),
+                    Self::pb_value_to_json(&value),
+                );
             }
-            Ok(Value::Object(result))
-        } else {
-            Err(response.error)
         }
+        Ok(Value::Object(result))
+    }
+
+    // Helper to turn a failed response into the matching error
+    fn check_response(response: &proto::pulumist::PulumiResponse, timeout_seconds: u32) -> Result<()> {
+        if response.cancelled {
+            return Err(PulumistError::Cancelled);
+        }
+        if response.timed_out {
+            return Err(PulumistError::Timeout {
+                timeout_seconds,
+                pending_resources: response.pending_resources.iter()
+                    .map(|urn| crate::events::resource_name_from_urn(urn).to_string())
+                    .collect(),
+            });
+        }
+        if !response.success {
+            return Err(PulumistError::StackOperation(response.error.clone()));
+        }
+        Ok(())
     }
 
     // Helper to convert JSON to protobuf map
-    fn json_to_pb_map(&self, value: &Value) -> std::collections::HashMap<String, proto::pulumist::Value> {
+    pub(crate) fn json_to_pb_map(&self, value: &Value) -> std::collections::HashMap<String, proto::pulumist::Value> {
         let mut map = std::collections::HashMap::new();
         if let Value::Object(obj) = value {
             for (k, v) in obj {
@@ -256,7 +638,7 @@ impl PulumiDynamic {
     }
 
     // Helper to convert protobuf value to JSON
-    fn pb_value_to_json(&self, value: &proto::pulumist::Value) -> Value {
+    pub(crate) fn pb_value_to_json(value: &proto::pulumist::Value) -> Value {
         use proto::pulumist::value::Value as PbValue;
 
         match &value.value {
@@ -269,11 +651,11 @@ impl PulumiDynamic {
             },
             Some(PbValue::BoolValue(b)) => Value::Bool(*b),
             Some(PbValue::ListValue(list)) => Value::Array(
-                list.values.iter().map(|v| self.pb_value_to_json(v)).collect()
+                list.values.iter().map(Self::pb_value_to_json).collect()
             ),
             Some(PbValue::MapValue(map)) => Value::Object(
                 map.fields.iter()
-                    .map(|(k, v)| (k.clone(), self.pb_value_to_json(v)))
+                    .map(|(k, v)| (k.clone(), Self::pb_value_to_json(v)))
                     .collect()
             ),
             Some(PbValue::BytesValue(bytes)) => {
@@ -296,151 +678,85 @@ impl PulumiDynamic {
     /// * `request` - Stack configuration to destroy
     ///
     /// # Returns
-    /// * `Ok(Value)` - JSON value with destruction results
-    /// * `Err(String)` - Error message if destruction fails
+    /// * `Ok(DestroyResult)` - Change counts and output of the destroy
+    /// * `Err(PulumistError)` - Error reported by Pulumi if destruction fails
     ///
     /// # Safety
     /// This permanently deletes infrastructure. Always preview first
     /// and ensure you have backups if needed.
-    pub fn destroy(&self, request: StackRequest) -> Result<Value, String> {
-        // Convert StackRequest to protobuf
-        let pb_request = proto::pulumist::PulumiRequest {
-            working_dir: request.project.clone(),
-            stack_name: request.stack.clone(),
-            project_name: request.project.clone(),
-            resources: request.resources.into_iter().map(|r| {
-                proto::pulumist::Resource {
-                    r#type: r.resource_type,
-                    name: r.name,
-                    properties: self.json_to_pb_map(&r.properties),
-                    depends_on: r.options.as_ref()
-                        .and_then(|o| o.depends_on.clone())
-                        .unwrap_or_default(),
-                    provider: r.options.as_ref()
-                        .and_then(|o| o.provider.clone())
-                        .unwrap_or_default(),
-                }
-            }).collect(),
-            config: request.config.into_iter()
-                .map(|(k, v)| (k, v.as_str().unwrap_or("").to_string()))
-                .collect(),
-            pulumi_config: None,
-        };
-
-        let response = Self::call_go_function_pb(PulumiDynamicDestroy, &pb_request)?;
-
-        if response.success {
-            // Convert outputs to JSON value
-            let mut result = serde_json::Map::new();
-            for output in response.outputs {
-                if let Some(value) = output.value {
-                    result.insert(
-                        format!("{}.{}", output.resource_name, output.output_name),
-                        self.pb_value_to_json(&value),
-                    );
-                }
-            }
-            Ok(Value::Object(result))
-        } else {
-            Err(response.error)
-        }
+    pub fn destroy(&self, request: StackRequest) -> Result<DestroyResult> {
+        self.call_operation(PulumiDynamicDestroy, request).map(DestroyResult::from)
     }
 
-    pub fn get_outputs(&self, request: StackRequest) -> Result<Value, String> {
-        // Convert StackRequest to protobuf
-        let pb_request = proto::pulumist::PulumiRequest {
-            working_dir: request.project.clone(),
-            stack_name: request.stack.clone(),
-            project_name: request.project.clone(),
-            resources: request.resources.into_iter().map(|r| {
-                proto::pulumist::Resource {
-                    r#type: r.resource_type,
-                    name: r.name,
-                    properties: self.json_to_pb_map(&r.properties),
-                    depends_on: r.options.as_ref()
-                        .and_then(|o| o.depends_on.clone())
-                        .unwrap_or_default(),
-                    provider: r.options.as_ref()
-                        .and_then(|o| o.provider.clone())
-                        .unwrap_or_default(),
-                }
-            }).collect(),
-            config: request.config.into_iter()
-                .map(|(k, v)| (k, v.as_str().unwrap_or("").to_string()))
-                .collect(),
-            pulumi_config: None,
-        };
+    pub fn get_outputs(&self, request: StackRequest) -> Result<Value> {
+        self.call(PulumiDynamicGetOutputs, request)
+    }
 
-        let response = Self::call_go_function_pb(PulumiDynamicGetOutputs, &pb_request)?;
-
-        if response.success {
-            // Convert outputs to JSON value
-            let mut result = serde_json::Map::new();
-            for output in response.outputs {
-                if let Some(value) = output.value {
-                    result.insert(
-                        format!("{}.{}", output.resource_name, output.output_name),
-                        self.pb_value_to_json(&value),
-                    );
-                }
-            }
-            Ok(Value::Object(result))
-        } else {
-            Err(response.error)
-        }
+    pub fn refresh(&self, request: StackRequest) -> Result<RefreshResult> {
+        let preview_only = request.preview_only;
+        let result = self.call_operation(PulumiDynamicRefresh, request)?;
+        Ok(RefreshResult { preview_only, ..RefreshResult::from(result) })
     }
 
-    pub fn refresh(&self, request: StackRequest) -> Result<Value, String> {
-        // Convert StackRequest to protobuf
-        let pb_request = proto::pulumist::PulumiRequest {
-            working_dir: request.project.clone(),
-            stack_name: request.stack.clone(),
-            project_name: request.project.clone(),
-            resources: request.resources.into_iter().map(|r| {
-                proto::pulumist::Resource {
-                    r#type: r.resource_type,
-                    name: r.name,
-                    properties: self.json_to_pb_map(&r.properties),
-                    depends_on: r.options.as_ref()
-                        .and_then(|o| o.depends_on.clone())
-                        .unwrap_or_default(),
-                    provider: r.options.as_ref()
-                        .and_then(|o| o.provider.clone())
-                        .unwrap_or_default(),
-                }
-            }).collect(),
-            config: request.config.into_iter()
-                .map(|(k, v)| (k, v.as_str().unwrap_or("").to_string()))
-                .collect(),
-            pulumi_config: None,
-        };
+    /// Adopts existing cloud resources into the stack state without modifying them.
+    ///
+    /// Resources that fail to import are reported in [`ImportResult::failures`] rather than
+    /// failing the whole import.
+    pub fn import(&self, request: ImportRequest) -> Result<ImportResult> {
+        let imports = request.resources.iter()
+            .map(|r| proto::pulumist::ImportResource {
+                r#type: r.resource_type.clone(),
+                name: r.name.clone(),
+                id: r.id.clone(),
+                parent: r.parent.clone().unwrap_or_default(),
+                provider: r.provider.clone().unwrap_or_default(),
+            })
+            .collect();
+
+        let mut pb_request = self.to_pb_request(StackRequest {
+            operation_id: request.operation_id,
+            project: request.project,
+            stack: request.stack,
+            config: request.config,
+            secret_config: request.secret_config,
+            resources: vec![],
+            timeout_seconds: request.timeout_seconds,
+            preview_only: false,
+            outputs: vec![],
+            components: vec![],
+            invokes: vec![],
+            pulumi_config: request.pulumi_config,
+        })?;
+        pb_request.imports = imports;
+
+        Self::call_operation_pb(PulumiDynamicImport, &pb_request).map(ImportResult::from)
+    }
 
-        let response = Self::call_go_function_pb(PulumiDynamicRefresh, &pb_request)?;
-
-        if response.success {
-            // Convert outputs to JSON value
-            let mut result = serde_json::Map::new();
-            for output in response.outputs {
-                if let Some(value) = output.value {
-                    result.insert(
-                        format!("{}.{}", output.resource_name, output.output_name),
-                        self.pb_value_to_json(&value),
-                    );
-                }
-            }
-            Ok(Value::Object(result))
-        } else {
-            Err(response.error)
-        }
+    /// Exports the stack's deployment checkpoint, like `pulumi stack export`.
+    pub fn export_stack(&self, request: StackRequest) -> Result<UntypedDeployment> {
+        let pb_request = self.to_pb_request(request)?;
+        let response = Self::call_go_function_pb(PulumiDynamicExportState, &pb_request)?;
+        Self::check_response(&response, pb_request.timeout_seconds)?;
+        Ok(serde_json::from_slice(&response.data)?)
     }
 
-    pub fn import(&self, _request: ImportRequest) -> Result<Value, String> {
-        todo!("Import functionality not yet implemented")
+    /// Calls the single provider function in `request.invokes` and returns its result. The function is
+    /// called by the program of a preview of a temporary stack, so the requested stack is not touched.
+    pub fn invoke(&self, request: StackRequest) -> Result<serde_json::Map<String, Value>> {
+        let result = self.call_operation(PulumiDynamicInvoke, request)?;
+        Ok(result.outputs.iter()
+            .map(|(name, value)| (name.clone(), Self::pb_value_to_json(value)))
+            .collect())
     }
 
-    pub fn export_stack(&self, request: StackRequest) -> Result<Value, String> {
-        // Export is the same as get_outputs
-        self.get_outputs(request)
+    /// Replaces the stack's deployment checkpoint, like `pulumi stack import`.
+    /// The stack is created if it does not exist.
+    pub fn import_stack(&self, request: StackRequest, deployment: &UntypedDeployment) -> Result<()> {
+        let mut pb_request = self.to_pb_request(request)?;
+        pb_request.deployment = serde_json::to_vec(deployment)?;
+
+        let response = Self::call_go_function_pb(PulumiDynamicImportState, &pb_request)?;
+        Self::check_response(&response, pb_request.timeout_seconds)
     }
 }
 
@@ -449,6 +765,23 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    fn request(resources: Vec<DynamicResource>) -> StackRequest {
+        StackRequest {
+            operation_id: 1,
+            project: "test-project".to_string(),
+            stack: "dev".to_string(),
+            config: serde_json::Map::new(),
+            secret_config: serde_json::Map::new(),
+            resources,
+            timeout_seconds: None,
+            preview_only: false,
+            outputs: vec![],
+            components: vec![],
+            invokes: vec![],
+            pulumi_config: PulumiConfig::default(),
+        }
+    }
+
     #[test]
     fn test_dynamic_resource_creation() {
         // Example of creating an Azure resource group dynamically
@@ -462,21 +795,226 @@ mod tests {
                     "Team": "Platform"
                 }
             }),
-            options: None,
+            options: Some(ResourceOptions {
+                protect: Some(true),
+                ignore_changes: Some(vec!["tags".to_string()]),
+                custom_timeouts: Some(CustomTimeouts {
+                    create: Some("30m".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
         };
 
         let mut config = serde_json::Map::new();
         config.insert("azure-native:location".to_string(), json!("eastus"));
 
         let request = StackRequest {
-            project: "test-project".to_string(),
-            stack: "dev".to_string(),
-            backend: Some("azblob".to_string()),
+            operation_id: crate::events::next_operation_id(),
             config,
-            resources: vec![resource],
+            timeout_seconds: Some(600),
+            ..request(vec![resource])
+        };
+        let operation_id = request.operation_id;
+
+        let pb_request = PulumiDynamic::new().to_pb_request(request).unwrap();
+        assert_eq!(pb_request.operation_id, operation_id);
+        assert_eq!(pb_request.timeout_seconds, 600);
+        assert_eq!(pb_request.config["azure-native:location"].value, "eastus");
+
+        let [group] = pb_request.resources.as_slice() else {
+            panic!("expected the resource group, got {:?}", pb_request.resources);
+        };
+        assert_eq!(group.r#type, "azure-native:resources:ResourceGroup");
+        assert_eq!(
+            PulumiDynamic::pb_value_to_json(&group.properties["tags"]),
+            json!({ "Environment": "Dev", "Team": "Platform" }),
+        );
+        assert!(group.protect && !group.retain_on_delete);
+        assert_eq!(group.ignore_changes, vec!["tags"]);
+        let timeouts = group.custom_timeouts.as_ref().unwrap();
+        assert_eq!((timeouts.create.as_str(), timeouts.delete.as_str()), ("30m", ""));
+    }
+
+    #[test]
+    fn test_pulumi_config_reaches_request() {
+        let request = StackRequest {
+            pulumi_config: PulumiConfig::builder()
+                .passphrase("hunter2")
+                .local_backend(Some("/tmp/state".to_string()))
+                .pulumi_home("/tmp/pulumi-home")
+                .log_level("debug")
+                .build(),
+            ..request(vec![])
+        };
+
+        let pb_config = PulumiDynamic::new().to_pb_request(request).unwrap().pulumi_config.unwrap();
+        assert!(pb_config.secrets_provider.is_some());
+        assert!(pb_config.backend.is_some());
+        assert_eq!(pb_config.pulumi_home, "/tmp/pulumi-home");
+        assert_eq!(pb_config.log_level, "debug");
+    }
+
+    #[test]
+    fn test_stack_outputs_reach_request() {
+        let with_outputs = |outputs| StackRequest { outputs, ..request(vec![]) };
+        let dynamic = PulumiDynamic::new();
+
+        let pb_request = dynamic.to_pb_request(with_outputs(vec![
+            StackOutput { name: "endpoint".to_string(), value: json!("${storage.primaryEndpoints.blob}"), secret: false },
+            StackOutput { name: "key".to_string(), value: json!("hunter2"), secret: true },
+        ])).unwrap();
+        assert_eq!(pb_request.outputs.len(), 2);
+        assert_eq!(
+            PulumiDynamic::pb_value_to_json(pb_request.outputs[0].value.as_ref().unwrap()),
+            json!("${storage.primaryEndpoints.blob}"),
+        );
+        assert!(!pb_request.outputs[0].secret);
+        assert!(pb_request.outputs[1].secret);
+
+        let unnamed = vec![StackOutput { name: String::new(), value: json!(1), secret: false }];
+        assert!(matches!(dynamic.to_pb_request(with_outputs(unnamed)), Err(PulumistError::ConfigError(_))));
+    }
+
+    #[test]
+    fn test_resource_options_reach_request() {
+        let rg = DynamicResource {
+            resource_type: "azure-native:resources:ResourceGroup".to_string(),
+            name: "rg".to_string(),
+            properties: json!({}),
+            options: None,
+        };
+        let vault = DynamicResource {
+            resource_type: "azure-native:keyvault:Vault".to_string(),
+            name: "vault".to_string(),
+            properties: json!({ "resourceGroupName": "${rg.name}" }),
+            options: Some(ResourceOptions {
+                protect: Some(true),
+                ignore_changes: Some(vec!["tags".to_string()]),
+                retain_on_delete: Some(true),
+                replace_on_changes: Some(vec!["location".to_string()]),
+                delete_before_replace: Some(true),
+                additional_secret_outputs: Some(vec!["properties.vaultUri".to_string()]),
+                deleted_with: Some("rg".to_string()),
+                aliases: Some(vec![Alias { name: Some("old-vault".to_string()), no_parent: true, ..Default::default() }]),
+                ..Default::default()
+            }),
+        };
+        let pb_request = PulumiDynamic::new().to_pb_request(request(vec![vault, rg])).unwrap();
+        let names: Vec<&str> = pb_request.resources.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["rg", "vault"]);
+
+        let vault = &pb_request.resources[1];
+        assert!(vault.protect && vault.retain_on_delete && vault.delete_before_replace);
+        assert_eq!(vault.ignore_changes, vec!["tags"]);
+        assert_eq!(vault.replace_on_changes, vec!["location"]);
+        assert_eq!(vault.additional_secret_outputs, vec!["properties.vaultUri"]);
+        assert_eq!(vault.deleted_with, "rg");
+        assert_eq!(vault.aliases.len(), 1);
+        assert_eq!(vault.aliases[0].name, "old-vault");
+        assert!(vault.aliases[0].no_parent && vault.aliases[0].urn.is_empty());
+        assert!(!pb_request.resources[0].protect);
+    }
+
+    #[test]
+    fn test_alias_fields_are_optional() {
+        let alias: Alias = serde_json::from_value(json!({ "name": "old" })).unwrap();
+        assert_eq!(alias.name.as_deref(), Some("old"));
+        assert!(!alias.no_parent && alias.parent.is_none());
+    }
+
+    #[test]
+    fn test_components_reach_request() {
+        let storage = DynamicResource {
+            resource_type: "azure-native:storage:StorageAccount".to_string(),
+            name: "site-storage".to_string(),
+            properties: json!({}),
+            options: None,
+        };
+        let site = ComponentResource::new("acme:web:StaticSite", "site")
+            .with_child(storage)
+            .with_output("endpoint", "${site-storage.primaryEndpoints.web}");
+        let request = StackRequest { components: vec![site], ..request(vec![]) };
+
+        let pb_request = PulumiDynamic::new().to_pb_request(request).unwrap();
+        let [site, storage] = pb_request.resources.as_slice() else {
+            panic!("expected the component and its child, got {:?}", pb_request.resources);
+        };
+        assert!(site.component && site.properties.is_empty());
+        assert_eq!(site.r#type, "acme:web:StaticSite");
+        assert_eq!(
+            PulumiDynamic::pb_value_to_json(&site.component_outputs["endpoint"]),
+            json!("${site-storage.primaryEndpoints.web}"),
+        );
+        assert!(!storage.component);
+        assert_eq!(storage.parent, "site");
+    }
+
+    #[test]
+    fn test_invokes_reach_request() {
+        let vault = DynamicResource {
+            resource_type: "azure-native:keyvault:Vault".to_string(),
+            name: "vault".to_string(),
+            properties: json!({ "tenantId": "${west-client.tenantId}" }),
+            options: None,
+        };
+        let request = StackRequest {
+            invokes: vec![
+                Invoke::new("west-client", "azure-native:authorization:getClientConfig").with_provider("west"),
+                Invoke::new("rg", "azure-native:resources:getResourceGroup").with_arg("resourceGroupName", "shared"),
+            ],
+            ..request(vec![vault, DynamicResource::provider("azure-native", "west", json!({}))])
+        };
+
+        let pb_request = PulumiDynamic::new().to_pb_request(request).unwrap();
+        let names: Vec<&str> = pb_request.resources.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["west", "vault"]);
+        let [client, rg] = pb_request.invokes.as_slice() else {
+            panic!("expected two invokes, got {:?}", pb_request.invokes);
+        };
+        assert_eq!((client.provider.as_str(), client.token.as_str()), ("west", "azure-native:authorization:getClientConfig"));
+        assert!(rg.provider.is_empty());
+        assert_eq!(PulumiDynamic::pb_value_to_json(&rg.args["resourceGroupName"]), json!("shared"));
+    }
+
+    #[test]
+    fn test_timed_out_response_lists_pending_resources() {
+        let response = proto::pulumist::PulumiResponse {
+            error: "operation timed out".to_string(),
+            timed_out: true,
+            pending_resources: vec![
+                "urn:pulumi:dev::demo::azure-native:storage:StorageAccount::demo-storage".to_string(),
+            ],
+            ..Default::default()
         };
 
-        // This would call the Go function in a real scenario
-        println!("Request: {}", serde_json::to_string_pretty(&request).unwrap());
+        match PulumiDynamic::new().response_to_json(response, 30) {
+            Err(PulumistError::Timeout { timeout_seconds, pending_resources }) => {
+                assert_eq!(timeout_seconds, 30);
+                assert_eq!(pending_resources, vec!["demo-storage"]);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let idle = PulumistError::Timeout { timeout_seconds: 30, pending_resources: vec![] };
+        assert_eq!(idle.to_string(), "Operation timed out after 30s");
+        let busy = PulumistError::Timeout { timeout_seconds: 30, pending_resources: vec!["a".to_string(), "b".to_string()] };
+        assert_eq!(busy.to_string(), "Operation timed out after 30s (pending resources: a, b)");
+    }
+
+    #[test]
+    fn test_untyped_deployment_matches_stack_export_format() {
+        let exported = r#"{"version":3,"deployment":{"manifest":{"time":"2024-01-01T00:00:00Z"},"resources":[{"urn":"urn:pulumi:dev::proj::pulumi:pulumi:Stack::proj-dev","type":"pulumi:pulumi:Stack"}]}}"#;
+
+        let deployment: UntypedDeployment = serde_json::from_str(exported).unwrap();
+        assert_eq!(deployment.version, 3);
+        assert_eq!(deployment.resources().len(), 1);
+        assert_eq!(
+            serde_json::to_value(&deployment).unwrap(),
+            serde_json::from_str::<Value>(exported).unwrap(),
+        );
+
+        let empty = UntypedDeployment { version: 3, deployment: Value::Null };
+        assert!(empty.resources().is_empty());
     }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+use crate::config::{BackendConfig, PulumiConfig, RuntimeOptions};
 use crate::error::Result;
 use crate::stack::Stack;
 use crate::dynamic::PulumiDynamic;
@@ -13,7 +14,7 @@ impl PulumiEngine {
         })
     }
     
-    pub fn create_stack(&self, name: &str) -> StackBuilder {
+    pub fn create_stack(&self, name: &str) -> StackBuilder<'_> {
         StackBuilder::new(name, &self.dynamic)
     }
 }
@@ -21,8 +22,9 @@ impl PulumiEngine {
 pub struct StackBuilder<'a> {
     name: String,
     project: Option<String>,
-    backend: Option<String>,
     config: serde_json::Map<String, serde_json::Value>,
+    secret_config: serde_json::Map<String, serde_json::Value>,
+    pulumi_config: PulumiConfig,
     dynamic: &'a PulumiDynamic,
 }
 
@@ -31,8 +33,9 @@ impl<'a> StackBuilder<'a> {
         Self {
             name: name.to_string(),
             project: None,
-            backend: None,
             config: serde_json::Map::new(),
+            secret_config: serde_json::Map::new(),
+            pulumi_config: PulumiConfig::default(),
             dynamic,
         }
     }
@@ -42,22 +45,63 @@ impl<'a> StackBuilder<'a> {
         self
     }
     
-    pub fn with_azure_backend(mut self) -> Self {
-        self.backend = Some("azblob".to_string());
+    /// Sets where the stack state is stored. Defaults to the local `~/.pulumi` directory.
+    pub fn with_backend(mut self, backend: BackendConfig) -> Self {
+        self.pulumi_config.backend = backend;
         self
     }
     
+    /// Stores the stack state in an Azure Blob Storage container, authenticated with the
+    /// ambient Azure credentials. Use [`with_backend`](Self::with_backend) to pass an access key or SAS token.
+    pub fn with_azure_backend(self, storage_account: &str, container: &str) -> Self {
+        self.with_backend(BackendConfig::AzureBlob {
+            storage_account: storage_account.to_string(),
+            container: container.to_string(),
+            access_key: None,
+            sas_token: None,
+        })
+    }
+    
+    /// Sets a stack config value, e.g. `"azure-native:location"`.
+    ///
+    /// Objects and arrays are stored as structured config, so `with_config("app:db", json!({"hosts": ["a"]}))`
+    /// is read back by the program as `app:db.hosts[0]`. Keys may also use that path syntax directly.
     pub fn with_config(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.secret_config.remove(key);
         self.config.insert(key.to_string(), value.into());
         self
     }
     
+    /// Sets a stack config value that is encrypted with the stack's secrets provider, see [`with_config`](Self::with_config).
+    pub fn with_secret_config(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.config.remove(key);
+        self.secret_config.insert(key.to_string(), value.into());
+        self
+    }
+    
+    /// Sets the secrets provider, backend, environment and runtime options used by every operation on the stack.
+    pub fn with_pulumi_config(mut self, config: PulumiConfig) -> Self {
+        self.pulumi_config = config;
+        self
+    }
+    
+    /// Sets the runtime options, such as the operation timeout, used by every operation on the stack.
+    pub fn with_runtime_options(mut self, runtime: RuntimeOptions) -> Self {
+        self.pulumi_config.runtime = runtime;
+        self
+    }
+    
+    /// Builds the stack, failing with [`PulumistError::ConfigError`](crate::error::PulumistError::ConfigError)
+    /// if a config value cannot be stored in Pulumi config, such as `null` or an empty object.
     pub fn build(self) -> Result<Stack> {
+        crate::config::stack_config_to_protobuf(&self.config, &self.secret_config)?;
+        
         Stack::new(
             self.name,
             self.project.unwrap_or_else(|| "pulumist-project".to_string()),
-            self.backend,
             self.config,
+            self.secret_config,
+            self.pulumi_config,
             self.dynamic.clone(),
         )
     }
@@ -8,17 +8,50 @@ pub enum PulumistError {
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
     
+    #[error("Protobuf decode error: {0}")]
+    Protobuf(#[from] prost::DecodeError),
+    
     #[error("Stack operation failed: {0}")]
     StackOperation(String),
     
+    #[error("Operation was cancelled")]
+    Cancelled,
+    
+    #[error("Operation timed out after {timeout_seconds}s{}", pending_list(pending_resources))]
+    Timeout {
+        timeout_seconds: u32,
+        /// Names of the resources whose steps had started but not finished
+        pending_resources: Vec<String>,
+    },
+    
     #[error("Provider error: {0}")]
     Provider(String),
     
     #[error("Configuration error: {0}")]
     ConfigError(String),
     
+    #[error("Reference error: {0}")]
+    Reference(String),
+    
+    /// Every problem found by [`validate_resources`](crate::validation::validate_resources)
+    #[error("Validation failed: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    Validation(Vec<crate::validation::Issue>),
+    
+    /// Resources that depend on each other, the first resource is repeated at the end
+    #[error("Dependency cycle between resources: {}", .0.join(" -> "))]
+    DependencyCycle(Vec<String>),
+    
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
 
-pub type Result<T> = std::result::Result<T, PulumistError>;
\ No newline at end of file
+pub type Result<T> = std::result::Result<T, PulumistError>;
+
+/// Formats the resources still in flight for the timeout message, empty when there were none
+fn pending_list(pending_resources: &[String]) -> String {
+    if pending_resources.is_empty() {
+        String::new()
+    } else {
+        format!(" (pending resources: {})", pending_resources.join(", "))
+    }
+}
@@ -1,88 +1,175 @@
-use std::ffi::CStr;
+use std::collections::HashMap;
 use std::os::raw::c_char;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc::{channel, Sender, Receiver};
 use std::sync::Mutex;
+use prost::Message;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use crate::error::{PulumistError, Result};
+use crate::proto;
 
 lazy_static::lazy_static! {
-    static ref EVENT_SENDER: Mutex<Option<Sender<Value>>> = Mutex::new(None);
+    /// Event senders keyed by the operation ID they were created for.
+    static ref EVENT_SENDERS: Mutex<HashMap<u64, Sender<DeploymentEvent>>> = Mutex::new(HashMap::new());
+}
+
+static NEXT_OPERATION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Allocates a process-unique operation ID.
+///
+/// The ID is sent to Go as `PulumiRequest.operation_id` and comes back on every
+/// event the operation emits, so concurrent operations each receive only their own events.
+pub fn next_operation_id() -> u64 {
+    NEXT_OPERATION_ID.fetch_add(1, Ordering::Relaxed)
 }
 
 /// FFI callback function that receives events from Go
-pub unsafe extern "C" fn event_callback(event_json: *const c_char) {
-    if event_json.is_null() {
+///
+/// Go sends every event as a length-prefixed protobuf `Event`
+/// (see `emitEvent` in `event_stream.go`) and frees the buffer as soon as
+/// this callback returns, so the frame is decoded before returning.
+///
+/// # Safety
+/// `event_data` must be null or point to a complete length-prefixed frame.
+pub unsafe extern "C" fn event_callback(event_data: *const c_char) {
+    if event_data.is_null() {
         return;
     }
 
-    let event_str: &str;
-    unsafe {
-        event_str = match CStr::from_ptr(event_json).to_str() {
-            Ok(s) => s,
-            Err(_) => return,
-        };
-    }
+    let frame = unsafe { read_frame(event_data as *const u8) };
+    dispatch_frame(frame);
+}
 
-    let event_value: Value = match serde_json::from_str(event_str) {
-        Ok(v) => v,
+/// Decodes a frame and forwards it to the channel of the operation that emitted it.
+/// Events for operations nobody is listening to are dropped.
+fn dispatch_frame(frame: &[u8]) {
+    let (operation_id, event) = match decode_frame(frame)
+        .and_then(|event| Ok((event.operation_id, DeploymentEvent::try_from(event)?)))
+    {
+        Ok(decoded) => decoded,
         Err(_) => return,
     };
 
-    // Send event through channel if available
-    if let Ok(sender_guard) = EVENT_SENDER.lock() {
-        if let Some(sender) = &*sender_guard {
-            let _ = sender.send(event_value);
+    if let Ok(senders) = EVENT_SENDERS.lock() {
+        if let Some(sender) = senders.get(&operation_id) {
+            let _ = sender.send(event);
         }
     }
 }
 
-/// Creates an event channel and registers the callback
-pub fn create_event_channel() -> Receiver<Value> {
-    let (sender, receiver) = channel();
-    
-    // Store the sender
-    if let Ok(mut sender_guard) = EVENT_SENDER.lock() {
-        *sender_guard = Some(sender);
+/// Borrows a `[4 bytes little-endian length][payload]` frame written by Go.
+///
+/// # Safety
+/// `ptr` must point to at least 4 bytes followed by `length` bytes of payload.
+unsafe fn read_frame<'a>(ptr: *const u8) -> &'a [u8] {
+    let length_bytes = unsafe { std::slice::from_raw_parts(ptr, 4) };
+    let length = u32::from_le_bytes([
+        length_bytes[0], length_bytes[1], length_bytes[2], length_bytes[3]
+    ]) as usize;
+
+    unsafe { std::slice::from_raw_parts(ptr, 4 + length) }
+}
+
+/// Decodes a length-prefixed frame into the raw protobuf `Event`.
+fn decode_frame(frame: &[u8]) -> Result<proto::pulumist::Event> {
+    if frame.len() < 4 {
+        return Err(PulumistError::Ffi(format!("Event frame too short: {} bytes", frame.len())));
     }
-    
-    // Register the callback with Go
-    unsafe {
-        super::RegisterEventCallback(Some(event_callback));
+
+    let length = u32::from_le_bytes([frame[0], frame[1], frame[2], frame[3]]) as usize;
+    let payload = frame.get(4..4 + length).ok_or_else(|| {
+        PulumistError::Ffi(format!(
+            "Event frame declares {} bytes but only {} are available",
+            length,
+            frame.len() - 4
+        ))
+    })?;
+
+    Ok(proto::pulumist::Event::decode(payload)?)
+}
+
+/// Decodes a length-prefixed protobuf `Event` frame into a [`DeploymentEvent`].
+pub fn decode_event(frame: &[u8]) -> Result<DeploymentEvent> {
+    DeploymentEvent::try_from(decode_frame(frame)?)
+}
+
+/// Encodes a [`DeploymentEvent`] into the same length-prefixed frame format Go emits.
+pub fn encode_event(event: &DeploymentEvent) -> Vec<u8> {
+    encode_frame(0, event)
+}
+
+pub(crate) fn encode_frame(operation_id: u64, event: &DeploymentEvent) -> Vec<u8> {
+    let mut message = proto::pulumist::Event::from(event);
+    message.operation_id = operation_id;
+    let payload = message.encode_to_vec();
+
+    let mut frame = Vec::with_capacity(4 + payload.len());
+    frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&payload);
+    frame
+}
+
+/// Creates an event channel for `operation_id` and registers the callback
+///
+/// The Go callback is shared by all operations; it is registered when the first
+/// channel is created and unregistered once the last one is cleaned up.
+pub fn create_event_channel(operation_id: u64) -> Receiver<DeploymentEvent> {
+    let (sender, receiver) = channel();
+
+    if let Ok(mut senders) = EVENT_SENDERS.lock() {
+        if senders.is_empty() {
+            unsafe {
+                super::RegisterEventCallback(Some(event_callback));
+            }
+        }
+        senders.insert(operation_id, sender);
     }
-    
+
     receiver
 }
 
-/// Unregisters the event callback
-pub fn cleanup_event_channel() {
-    // Clear the sender
-    if let Ok(mut sender_guard) = EVENT_SENDER.lock() {
-        *sender_guard = None;
-    }
-    
-    // Unregister the callback
-    unsafe {
-        super::UnregisterEventCallback();
+/// Removes the event channel of `operation_id`, closing its receiver
+pub fn cleanup_event_channel(operation_id: u64) {
+    if let Ok(mut senders) = EVENT_SENDERS.lock() {
+        if senders.remove(&operation_id).is_some() && senders.is_empty() {
+            unsafe {
+                super::UnregisterEventCallback();
+            }
+        }
     }
 }
-#[derive(Debug, Clone, Serialize, Deserialize)]
+
+/// Returns the resource name part of a URN (`urn:pulumi:<stack>::<project>::<type>::<name>`).
+pub(crate) fn resource_name_from_urn(urn: &str) -> &str {
+    urn.rsplit("::").next().unwrap_or_default()
+}
+
+/// Typed counterpart of the protobuf `Event` oneof.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum DeploymentEvent {
     #[serde(rename = "preludeEvent")]
     Prelude {
-        message: String,
+        config: HashMap<String, String>,
+    },
+
+    #[serde(rename = "summaryEvent")]
+    Summary {
+        may_change: bool,
+        duration_seconds: i32,
+        resource_changes: HashMap<String, i32>,
     },
 
     #[serde(rename = "resourcePreEvent")]
     ResourcePre {
         resource: ResourceEvent,
-        metadata: EventMetadata,
+        planning: bool,
     },
 
     #[serde(rename = "resourceOutputsEvent")]
     ResourceOutputs {
         resource: ResourceEvent,
-        metadata: EventMetadata,
+        planning: bool,
     },
 
     #[serde(rename = "resourceOperationFailedEvent")]
@@ -90,35 +177,48 @@ pub enum DeploymentEvent {
         resource: ResourceEvent,
         status: ResourceStatus,
         steps: i32,
-        metadata: EventMetadata,
     },
 
     #[serde(rename = "diagnosticEvent")]
     Diagnostic {
+        urn: Option<String>,
+        prefix: String,
+        message: String,
+        color: String,
         severity: DiagnosticSeverity,
+    },
+
+    #[serde(rename = "policyEvent")]
+    Policy {
+        resource_urn: Option<String>,
         message: String,
-        resource: Option<ResourceEvent>,
+        color: String,
+        policies: Vec<PolicyConfig>,
     },
 
-    #[serde(rename = "summaryEvent")]
-    Summary {
+    #[serde(rename = "progressEvent")]
+    Progress {
         message: String,
-        duration_seconds: f64,
     },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ResourceEvent {
     pub urn: String,
     #[serde(rename = "type")]
     pub resource_type: String,
+    /// Logical resource name, taken from the last segment of the URN
     pub name: String,
     pub operation: ResourceOperation,
+    pub old: bool,
+    pub new: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Step operations reported by the Pulumi engine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ResourceOperation {
+    Same,
     Create,
     Update,
     Delete,
@@ -126,18 +226,27 @@ pub enum ResourceOperation {
     CreateReplacement,
     DeleteReplaced,
     Read,
+    ReadReplacement,
+    Refresh,
+    ReadDiscard,
+    DiscardReplaced,
+    RemovePendingReplace,
     Import,
+    ImportReplacement,
+    /// An operation this version of pulumist does not know about
+    Other(String),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Mirrors Pulumi's `resource.Status` carried by failed operations
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ResourceStatus {
-    Success,
-    Failed,
-    Skipped,
+    Ok,
+    PartialFailure,
+    Unknown,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum DiagnosticSeverity {
     Debug,
@@ -146,16 +255,236 @@ pub enum DiagnosticSeverity {
     Error,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EventMetadata {
-    pub duration_seconds: Option<f64>,
-    pub progress: Option<Progress>,
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyConfig {
+    pub name: String,
+    pub version: String,
+}
+
+impl ResourceOperation {
+    /// Parses an engine op string such as `"create"` or `"delete-replaced"`
+    pub fn from_op(op: &str) -> Self {
+        match op {
+            "same" => ResourceOperation::Same,
+            "create" => ResourceOperation::Create,
+            "update" => ResourceOperation::Update,
+            "delete" => ResourceOperation::Delete,
+            "replace" => ResourceOperation::Replace,
+            "create-replacement" => ResourceOperation::CreateReplacement,
+            "delete-replaced" => ResourceOperation::DeleteReplaced,
+            "read" => ResourceOperation::Read,
+            "read-replacement" => ResourceOperation::ReadReplacement,
+            "refresh" => ResourceOperation::Refresh,
+            "read-discard" => ResourceOperation::ReadDiscard,
+            "discard-replaced" => ResourceOperation::DiscardReplaced,
+            "remove-pending-replace" => ResourceOperation::RemovePendingReplace,
+            "import" => ResourceOperation::Import,
+            "import-replacement" => ResourceOperation::ImportReplacement,
+            other => ResourceOperation::Other(other.to_string()),
+        }
+    }
+
+    /// Returns the engine op string for this operation
+    pub fn as_op(&self) -> &str {
+        match self {
+            ResourceOperation::Same => "same",
+            ResourceOperation::Create => "create",
+            ResourceOperation::Update => "update",
+            ResourceOperation::Delete => "delete",
+            ResourceOperation::Replace => "replace",
+            ResourceOperation::CreateReplacement => "create-replacement",
+            ResourceOperation::DeleteReplaced => "delete-replaced",
+            ResourceOperation::Read => "read",
+            ResourceOperation::ReadReplacement => "read-replacement",
+            ResourceOperation::Refresh => "refresh",
+            ResourceOperation::ReadDiscard => "read-discard",
+            ResourceOperation::DiscardReplaced => "discard-replaced",
+            ResourceOperation::RemovePendingReplace => "remove-pending-replace",
+            ResourceOperation::Import => "import",
+            ResourceOperation::ImportReplacement => "import-replacement",
+            ResourceOperation::Other(op) => op,
+        }
+    }
+}
+
+impl ResourceStatus {
+    fn from_code(code: i32) -> Self {
+        match code {
+            0 => ResourceStatus::Ok,
+            1 => ResourceStatus::PartialFailure,
+            _ => ResourceStatus::Unknown,
+        }
+    }
+
+    fn code(&self) -> i32 {
+        match self {
+            ResourceStatus::Ok => 0,
+            ResourceStatus::PartialFailure => 1,
+            ResourceStatus::Unknown => 2,
+        }
+    }
+}
+
+impl DiagnosticSeverity {
+    fn from_severity(severity: &str) -> Self {
+        match severity {
+            "debug" => DiagnosticSeverity::Debug,
+            "warning" | "warn" => DiagnosticSeverity::Warning,
+            "error" => DiagnosticSeverity::Error,
+            // Includes Pulumi's "info#err" (info written to stderr)
+            _ => DiagnosticSeverity::Info,
+        }
+    }
+
+    fn as_severity(&self) -> &'static str {
+        match self {
+            DiagnosticSeverity::Debug => "debug",
+            DiagnosticSeverity::Info => "info",
+            DiagnosticSeverity::Warning => "warning",
+            DiagnosticSeverity::Error => "error",
+        }
+    }
+}
+
+impl From<proto::pulumist::ResourceMetadata> for ResourceEvent {
+    fn from(metadata: proto::pulumist::ResourceMetadata) -> Self {
+        let name = resource_name_from_urn(&metadata.urn).to_string();
+        ResourceEvent {
+            name,
+            operation: ResourceOperation::from_op(&metadata.op),
+            urn: metadata.urn,
+            resource_type: metadata.r#type,
+            old: metadata.old,
+            new: metadata.new,
+        }
+    }
+}
+
+impl From<&ResourceEvent> for proto::pulumist::ResourceMetadata {
+    fn from(resource: &ResourceEvent) -> Self {
+        proto::pulumist::ResourceMetadata {
+            op: resource.operation.as_op().to_string(),
+            urn: resource.urn.clone(),
+            r#type: resource.resource_type.clone(),
+            old: resource.old,
+            new: resource.new,
+        }
+    }
+}
+
+impl TryFrom<proto::pulumist::Event> for DeploymentEvent {
+    type Error = PulumistError;
+
+    fn try_from(event: proto::pulumist::Event) -> Result<Self> {
+        use proto::pulumist::event::Event as PbEvent;
+
+        let non_empty = |s: String| if s.is_empty() { None } else { Some(s) };
+
+        let event = match event.event {
+            Some(PbEvent::Prelude(prelude)) => DeploymentEvent::Prelude {
+                config: prelude.config,
+            },
+            Some(PbEvent::Summary(summary)) => DeploymentEvent::Summary {
+                may_change: summary.may_change,
+                duration_seconds: summary.duration_seconds,
+                resource_changes: summary.resource_changes,
+            },
+            Some(PbEvent::ResourcePre(pre)) => DeploymentEvent::ResourcePre {
+                resource: pre.metadata.unwrap_or_default().into(),
+                planning: pre.planning,
+            },
+            Some(PbEvent::ResourceOutputs(outputs)) => DeploymentEvent::ResourceOutputs {
+                resource: outputs.metadata.unwrap_or_default().into(),
+                planning: outputs.planning,
+            },
+            Some(PbEvent::ResourceFailed(failed)) => DeploymentEvent::ResourceOperationFailed {
+                resource: failed.metadata.unwrap_or_default().into(),
+                status: ResourceStatus::from_code(failed.status),
+                steps: failed.steps,
+            },
+            Some(PbEvent::Diagnostic(diagnostic)) => DeploymentEvent::Diagnostic {
+                severity: DiagnosticSeverity::from_severity(&diagnostic.severity),
+                urn: non_empty(diagnostic.urn),
+                prefix: diagnostic.prefix,
+                message: diagnostic.message,
+                color: diagnostic.color,
+            },
+            Some(PbEvent::Policy(policy)) => DeploymentEvent::Policy {
+                resource_urn: non_empty(policy.resource_urn),
+                message: policy.message,
+                color: policy.color,
+                policies: policy.policy_config.into_iter()
+                    .map(|p| PolicyConfig { name: p.name, version: p.version })
+                    .collect(),
+            },
+            Some(PbEvent::Progress(progress)) => DeploymentEvent::Progress {
+                message: progress.message,
+            },
+            None => return Err(PulumistError::Ffi("Received an event without a payload".to_string())),
+        };
+
+        Ok(event)
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Progress {
-    pub current: i32,
-    pub total: i32,
+impl From<&DeploymentEvent> for proto::pulumist::Event {
+    fn from(event: &DeploymentEvent) -> Self {
+        use proto::pulumist::event::Event as PbEvent;
+        use proto::pulumist::{
+            DiagnosticEvent, PolicyEvent, PreludeEvent, ProgressEvent, ResOpFailedEvent,
+            ResOutputsEvent, ResourcePreEvent, SummaryEvent,
+        };
+
+        let event = match event {
+            DeploymentEvent::Prelude { config } => PbEvent::Prelude(PreludeEvent {
+                config: config.clone(),
+            }),
+            DeploymentEvent::Summary { may_change, duration_seconds, resource_changes } => {
+                PbEvent::Summary(SummaryEvent {
+                    may_change: *may_change,
+                    duration_seconds: *duration_seconds,
+                    resource_changes: resource_changes.clone(),
+                })
+            }
+            DeploymentEvent::ResourcePre { resource, planning } => PbEvent::ResourcePre(ResourcePreEvent {
+                metadata: Some(resource.into()),
+                planning: *planning,
+            }),
+            DeploymentEvent::ResourceOutputs { resource, planning } => PbEvent::ResourceOutputs(ResOutputsEvent {
+                metadata: Some(resource.into()),
+                planning: *planning,
+            }),
+            DeploymentEvent::ResourceOperationFailed { resource, status, steps } => {
+                PbEvent::ResourceFailed(ResOpFailedEvent {
+                    metadata: Some(resource.into()),
+                    status: status.code(),
+                    steps: *steps,
+                })
+            }
+            DeploymentEvent::Diagnostic { urn, prefix, message, color, severity } => {
+                PbEvent::Diagnostic(DiagnosticEvent {
+                    urn: urn.clone().unwrap_or_default(),
+                    prefix: prefix.clone(),
+                    message: message.clone(),
+                    color: color.clone(),
+                    severity: severity.as_severity().to_string(),
+                })
+            }
+            DeploymentEvent::Policy { resource_urn, message, color, policies } => PbEvent::Policy(PolicyEvent {
+                resource_urn: resource_urn.clone().unwrap_or_default(),
+                message: message.clone(),
+                color: color.clone(),
+                policy_config: policies.iter()
+                    .map(|p| proto::pulumist::PolicyConfig { name: p.name.clone(), version: p.version.clone() })
+                    .collect(),
+            }),
+            DeploymentEvent::Progress { message } => PbEvent::Progress(ProgressEvent {
+                message: message.clone(),
+            }),
+        };
+
+        proto::pulumist::Event { event: Some(event), operation_id: 0 }
+    }
 }
 
 /// Trait for handling deployment events
@@ -164,6 +493,7 @@ pub trait EventHandler: Send + Sync {
 }
 
 /// Simple event handler that prints to stdout
+#[derive(Default)]
 pub struct PrintEventHandler;
 
 impl PrintEventHandler {
@@ -175,36 +505,27 @@ impl PrintEventHandler {
 impl EventHandler for PrintEventHandler {
     fn handle_event(&self, event: DeploymentEvent) {
         match event {
-            DeploymentEvent::Prelude { message } => {
-                println!("🚀 {}", message);
+            DeploymentEvent::Prelude { config } => {
+                println!("🚀 Starting operation ({} config values)", config.len());
             }
-            DeploymentEvent::ResourcePre { resource, metadata } => {
+            DeploymentEvent::ResourcePre { resource, planning } => {
                 let op = match resource.operation {
                     ResourceOperation::Create => "Creating",
                     ResourceOperation::Update => "Updating",
                     ResourceOperation::Delete => "Deleting",
                     ResourceOperation::Replace => "Replacing",
+                    ResourceOperation::Refresh => "Refreshing",
+                    ResourceOperation::Import => "Importing",
                     _ => "Processing",
                 };
-
-                if let Some(progress) = metadata.progress {
-                    println!("[{}/{}] {} {} ({})",
-                             progress.current,
-                             progress.total,
-                             op,
-                             resource.resource_type,
-                             resource.name
-                    );
-                } else {
-                    println!("{} {} ({})", op, resource.resource_type, resource.name);
-                }
-            }
-            DeploymentEvent::ResourceOutputs { resource, metadata } => {
-                let duration = metadata.duration_seconds.unwrap_or(0.0);
-                println!("✅ {} {} created ({:.1}s)",
+                let suffix = if planning { " [preview]" } else { "" };
+                println!("{} {} ({}){}", op, resource.resource_type, resource.name, suffix);
+            }
+            DeploymentEvent::ResourceOutputs { resource, .. } => {
+                println!("✅ {} {} {}",
                          resource.resource_type,
                          resource.name,
-                         duration
+                         resource.operation.as_op()
                 );
             }
             DeploymentEvent::ResourceOperationFailed { resource, .. } => {
@@ -226,11 +547,209 @@ impl EventHandler for PrintEventHandler {
                     DiagnosticSeverity::Info => "ℹ️  INFO",
                     DiagnosticSeverity::Debug => "🔍 DEBUG",
                 };
-                println!("{}: {}", prefix, message);
+                println!("{}: {}", prefix, message.trim_end());
+            }
+            DeploymentEvent::Policy { message, policies, .. } => {
+                let names: Vec<&str> = policies.iter().map(|p| p.name.as_str()).collect();
+                println!("🛡️  POLICY [{}]: {}", names.join(", "), message.trim_end());
+            }
+            DeploymentEvent::Progress { message } => {
+                println!("⏳ {}", message);
+            }
+            DeploymentEvent::Summary { resource_changes, duration_seconds, .. } => {
+                let mut changes: Vec<String> = resource_changes.iter()
+                    .map(|(op, count)| format!("{} {}", count, op))
+                    .collect();
+                changes.sort();
+                println!("\n📊 Summary: {} (took {}s)", changes.join(", "), duration_seconds);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proto::pulumist::event::Event as GoEvent;
+
+    const PET_URN: &str = "urn:pulumi:dev::demo::random:index/randomPet:RandomPet::pet";
+
+    fn pet_metadata(op: &str, old: bool) -> Option<proto::pulumist::ResourceMetadata> {
+        Some(proto::pulumist::ResourceMetadata {
+            op: op.to_string(),
+            urn: PET_URN.to_string(),
+            r#type: "random:index/randomPet:RandomPet".to_string(),
+            old,
+            new: true,
+        })
+    }
+
+    fn resource_pre() -> GoEvent {
+        GoEvent::ResourcePre(proto::pulumist::ResourcePreEvent { metadata: pet_metadata("create", false), planning: false })
+    }
+
+    fn resource_outputs() -> GoEvent {
+        GoEvent::ResourceOutputs(proto::pulumist::ResOutputsEvent { metadata: pet_metadata("same", true), planning: true })
+    }
+
+    fn resource_failed() -> GoEvent {
+        GoEvent::ResourceFailed(proto::pulumist::ResOpFailedEvent { metadata: pet_metadata("update", true), status: 1, steps: 2 })
+    }
+
+    fn summary() -> GoEvent {
+        GoEvent::Summary(proto::pulumist::SummaryEvent {
+            may_change: true,
+            duration_seconds: 12,
+            resource_changes: HashMap::from([("create".to_string(), 2)]),
+        })
+    }
+
+    fn prelude() -> GoEvent {
+        GoEvent::Prelude(proto::pulumist::PreludeEvent {
+            config: HashMap::from([("aws:region".to_string(), "eu-west-1".to_string())]),
+        })
+    }
+
+    fn diagnostic() -> GoEvent {
+        GoEvent::Diagnostic(proto::pulumist::DiagnosticEvent {
+            prefix: "warning: ".to_string(),
+            message: "deprecated".to_string(),
+            severity: "warning".to_string(),
+            ..Default::default()
+        })
+    }
+
+    fn policy() -> GoEvent {
+        GoEvent::Policy(proto::pulumist::PolicyEvent {
+            resource_urn: "urn:pulumi:dev::demo::aws:s3/bucket:Bucket::logs".to_string(),
+            message: "must be private".to_string(),
+            color: String::new(),
+            policy_config: vec![proto::pulumist::PolicyConfig { name: "baseline".to_string(), version: "1.0.0".to_string() }],
+        })
+    }
+
+    fn progress() -> GoEvent {
+        GoEvent::Progress(proto::pulumist::ProgressEvent { message: "Downloading plugin".to_string() })
+    }
+
+    // Frames an event the way `emitEvent` on the Go side does
+    fn go_frame(event: GoEvent) -> Vec<u8> {
+        let payload = proto::pulumist::Event { event: Some(event), operation_id: 0 }.encode_to_vec();
+        let mut frame = (payload.len() as u32).to_le_bytes().to_vec();
+        frame.extend_from_slice(&payload);
+        frame
+    }
+
+    #[test]
+    fn test_decode_resource_events() {
+        match decode_event(&go_frame(resource_pre())).unwrap() {
+            DeploymentEvent::ResourcePre { resource, planning } => {
+                assert_eq!(resource.name, "pet");
+                assert_eq!(resource.resource_type, "random:index/randomPet:RandomPet");
+                assert_eq!(resource.operation, ResourceOperation::Create);
+                assert!(resource.new && !resource.old);
+                assert!(!planning);
             }
-            DeploymentEvent::Summary { message, duration_seconds } => {
-                println!("\n📊 Summary: {} (took {:.1}s)", message, duration_seconds);
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        match decode_event(&go_frame(resource_outputs())).unwrap() {
+            DeploymentEvent::ResourceOutputs { resource, planning } => {
+                assert_eq!(resource.operation, ResourceOperation::Same);
+                assert!(planning);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        match decode_event(&go_frame(resource_failed())).unwrap() {
+            DeploymentEvent::ResourceOperationFailed { resource, status, steps } => {
+                assert_eq!(resource.operation, ResourceOperation::Update);
+                assert_eq!(status, ResourceStatus::PartialFailure);
+                assert_eq!(steps, 2);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decode_policy_and_progress_events() {
+        match decode_event(&go_frame(policy())).unwrap() {
+            DeploymentEvent::Policy { resource_urn, message, policies, .. } => {
+                assert_eq!(resource_urn.as_deref(), Some("urn:pulumi:dev::demo::aws:s3/bucket:Bucket::logs"));
+                assert_eq!(message, "must be private");
+                assert_eq!(policies, vec![PolicyConfig { name: "baseline".into(), version: "1.0.0".into() }]);
             }
+            other => panic!("unexpected event: {:?}", other),
         }
+
+        assert_eq!(
+            decode_event(&go_frame(progress())).unwrap(),
+            DeploymentEvent::Progress { message: "Downloading plugin".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_decode_diagnostic_without_urn() {
+        match decode_event(&go_frame(diagnostic())).unwrap() {
+            DeploymentEvent::Diagnostic { urn, severity, message, prefix, .. } => {
+                assert_eq!(urn, None);
+                assert_eq!(severity, DiagnosticSeverity::Warning);
+                assert_eq!(message, "deprecated");
+                assert_eq!(prefix, "warning: ");
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_go_frames() {
+        for go_event in [
+            resource_pre(), resource_outputs(), resource_failed(), summary(),
+            prelude(), diagnostic(), policy(), progress(),
+        ] {
+            let frame = go_frame(go_event);
+            let event = decode_event(&frame).unwrap();
+            assert_eq!(encode_event(&event), frame, "round trip changed {:?}", event);
+        }
+    }
+
+    #[test]
+    fn test_dispatch_routes_by_operation_id() {
+        let first = next_operation_id();
+        let second = next_operation_id();
+        assert_ne!(first, second);
+
+        let (first_tx, first_rx) = channel();
+        let (second_tx, second_rx) = channel();
+        {
+            let mut senders = EVENT_SENDERS.lock().unwrap();
+            senders.insert(first, first_tx);
+            senders.insert(second, second_tx);
+        }
+
+        let progress = |message: &str| DeploymentEvent::Progress { message: message.to_string() };
+        dispatch_frame(&encode_frame(first, &progress("first")));
+        dispatch_frame(&encode_frame(second, &progress("second")));
+        dispatch_frame(&encode_frame(u64::MAX, &progress("nobody")));
+
+        {
+            let mut senders = EVENT_SENDERS.lock().unwrap();
+            senders.remove(&first);
+            senders.remove(&second);
+        }
+
+        assert_eq!(first_rx.try_iter().collect::<Vec<_>>(), vec![progress("first")]);
+        assert_eq!(second_rx.try_iter().collect::<Vec<_>>(), vec![progress("second")]);
+    }
+
+    #[test]
+    fn test_decode_rejects_truncated_frame() {
+        let mut frame = go_frame(summary());
+        frame.truncate(frame.len() - 1);
+        assert!(decode_event(&frame).is_err());
+        assert!(decode_event(&[1, 0]).is_err());
+        // A frame without a oneof payload is not a valid event
+        assert!(decode_event(&[0, 0, 0, 0]).is_err());
     }
 }
\ No newline at end of file
@@ -0,0 +1,186 @@
+use crate::dynamic::{DynamicResource, Invoke};
+use crate::error::{PulumistError, Result};
+use crate::outputs::find_output_references;
+use std::collections::HashMap;
+
+/// Names of the resources `resource` depends on: the resources its properties reference, its
+/// `depends_on` resources, its parent, its provider, its `deleted_with` resource and the previous
+/// parents named by its aliases, in that order and without duplicates. References to the outputs of
+/// other stacks are no dependencies, those stacks are read before any resource is registered.
+pub fn resource_dependencies(resource: &DynamicResource) -> Vec<String> {
+    let mut dependencies: Vec<String> = Vec::new();
+    let mut add = |name: &str| {
+        if !dependencies.iter().any(|dependency| dependency == name) {
+            dependencies.push(name.to_string());
+        }
+    };
+
+    for reference in find_output_references(&resource.properties).iter().filter(|reference| reference.stack().is_none()) {
+        add(&reference.resource_name);
+    }
+    if let Some(options) = &resource.options {
+        for name in options.depends_on.iter().flatten() {
+            add(name);
+        }
+        if let Some(parent) = &options.parent {
+            add(parent);
+        }
+        if let Some(provider) = &options.provider {
+            add(provider);
+        }
+        if let Some(deleted_with) = &options.deleted_with {
+            add(deleted_with);
+        }
+        for alias in options.aliases.iter().flatten() {
+            if let Some(parent) = &alias.parent {
+                add(parent);
+            }
+        }
+    }
+
+    dependencies
+}
+
+/// Orders resources so every resource comes after the resources it depends on, keeping the given
+/// order where dependencies allow. Dependencies on names that are not in `resources` are ignored, as
+/// they may refer to resources registered outside the program.
+///
+/// The program calls an invoke once its provider is registered, so a dependency on an invoke in
+/// `invokes` is a dependency on the provider it is called with.
+///
+/// Fails with [`PulumistError::DependencyCycle`] naming the resources of a cycle.
+pub fn order_resources(resources: Vec<DynamicResource>, invokes: &[Invoke]) -> Result<Vec<DynamicResource>> {
+    let mut indexes: HashMap<&str, usize> = HashMap::new();
+    for (index, resource) in resources.iter().enumerate() {
+        indexes.entry(resource.name.as_str()).or_insert(index);
+    }
+    let invoke_providers: HashMap<&str, &str> = invokes.iter()
+        .filter_map(|invoke| Some((invoke.name.as_str(), invoke.provider.as_deref()?)))
+        .collect();
+
+    // dependencies[i] holds the indexes of the resources resource i depends on
+    let dependencies: Vec<Vec<usize>> = resources.iter()
+        .map(|resource| resource_dependencies(resource).iter()
+            .map(|name| invoke_providers.get(name.as_str()).copied().unwrap_or(name))
+            .filter_map(|name| indexes.get(name).copied())
+            .collect())
+        .collect();
+
+    let mut order = Vec::with_capacity(resources.len());
+    let mut placed = vec![false; resources.len()];
+    while order.len() < resources.len() {
+        // Place the first resource whose dependencies are all placed, so independent resources keep their order
+        let next = (0..resources.len())
+            .find(|&i| !placed[i] && dependencies[i].iter().all(|&dependency| placed[dependency]));
+        match next {
+            Some(index) => {
+                placed[index] = true;
+                order.push(index);
+            }
+            None => {
+                let cycle = find_cycle(&dependencies, &placed);
+                return Err(PulumistError::DependencyCycle(
+                    cycle.into_iter().map(|index| resources[index].name.clone()).collect(),
+                ));
+            }
+        }
+    }
+
+    let mut resources: Vec<Option<DynamicResource>> = resources.into_iter().map(Some).collect();
+    Ok(order.into_iter().filter_map(|index| resources[index].take()).collect())
+}
+
+/// Follows the unplaced dependencies from the first unplaced resource until a resource repeats.
+/// Every unplaced resource has an unplaced dependency, so this always ends in a cycle.
+/// Returns the cycle with its first resource repeated at the end, e.g. `[a, b, a]`.
+fn find_cycle(dependencies: &[Vec<usize>], placed: &[bool]) -> Vec<usize> {
+    let mut path: Vec<usize> = Vec::new();
+    let mut current = placed.iter().position(|&placed| !placed).unwrap_or_default();
+
+    loop {
+        if let Some(start) = path.iter().position(|&index| index == current) {
+            let mut cycle = path.split_off(start);
+            cycle.push(current);
+            return cycle;
+        }
+        path.push(current);
+        match dependencies[current].iter().find(|&&dependency| !placed[dependency]) {
+            Some(&dependency) => current = dependency,
+            None => return path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamic::ResourceOptions;
+    use serde_json::json;
+
+    fn resource(name: &str, properties: serde_json::Value, depends_on: &[&str]) -> DynamicResource {
+        DynamicResource {
+            resource_type: "test:index:Resource".to_string(),
+            name: name.to_string(),
+            properties,
+            options: Some(ResourceOptions {
+                depends_on: Some(depends_on.iter().map(|name| name.to_string()).collect()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn names(resources: &[DynamicResource]) -> Vec<&str> {
+        resources.iter().map(|resource| resource.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_order_resources_by_dependencies() {
+        let resources = vec![
+            resource("container", json!({ "accountName": "${storage.name}" }), &[]),
+            resource("logs", json!({}), &["rg"]),
+            resource("storage", json!({ "resourceGroupName": "${rg.name}", "url": "https://${cdn.host}/" }), &[]),
+            resource("rg", json!({ "location": "westeurope" }), &[]),
+        ];
+
+        let ordered = order_resources(resources, &[]).unwrap();
+        assert_eq!(names(&ordered), vec!["rg", "logs", "storage", "container"]);
+    }
+
+    #[test]
+    fn test_order_resources_reports_cycle() {
+        let resources = vec![
+            resource("rg", json!({}), &[]),
+            resource("a", json!({ "value": "${b.id}" }), &[]),
+            resource("b", json!({}), &["c"]),
+            resource("c", json!({ "value": "${a.id}" }), &["rg"]),
+        ];
+
+        match order_resources(resources, &[]) {
+            Err(PulumistError::DependencyCycle(cycle)) => assert_eq!(cycle, vec!["a", "b", "c", "a"]),
+            other => panic!("expected a dependency cycle, got {:?}", other),
+        }
+
+        let resources = vec![resource("self", json!({ "value": "${self.id}" }), &[])];
+        match order_resources(resources, &[]) {
+            Err(error @ PulumistError::DependencyCycle(_)) => {
+                assert_eq!(error.to_string(), "Dependency cycle between resources: self -> self");
+            }
+            other => panic!("expected a dependency cycle, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_order_resources_after_invoke_providers() {
+        let resources = vec![
+            resource("vault", json!({ "tenantId": "${west-client.tenantId}", "owner": "${client.objectId}" }), &[]),
+            DynamicResource::provider("azure-native", "west", json!({ "location": "westeurope" })),
+        ];
+        let invokes = vec![
+            Invoke::new("west-client", "azure-native:authorization:getClientConfig").with_provider("west"),
+            Invoke::new("client", "azure-native:authorization:getClientConfig"),
+        ];
+
+        let ordered = order_resources(resources, &invokes).unwrap();
+        assert_eq!(names(&ordered), vec!["west", "vault"]);
+    }
+}
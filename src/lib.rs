@@ -6,6 +6,10 @@ pub mod engine;
 pub mod error;
 pub mod stack;
 pub mod dynamic;
+pub mod cancellation;
+pub mod results;
+pub mod graph;
+pub mod validation;
 
 use std::os::raw::c_char;
 
@@ -16,6 +20,11 @@ unsafe extern "C" {
     fn PulumiDynamicDestroy(request: *const c_char, request_len: i32) -> *mut c_char;
     fn PulumiDynamicGetOutputs(request: *const c_char, request_len: i32) -> *mut c_char;
     fn PulumiDynamicRefresh(request: *const c_char, request_len: i32) -> *mut c_char;
+    fn PulumiDynamicImport(request: *const c_char, request_len: i32) -> *mut c_char;
+    fn PulumiDynamicExportState(request: *const c_char, request_len: i32) -> *mut c_char;
+    fn PulumiDynamicImportState(request: *const c_char, request_len: i32) -> *mut c_char;
+    fn PulumiDynamicInvoke(request: *const c_char, request_len: i32) -> *mut c_char;
+    fn PulumiDynamicCancel(operation_id: u64);
     fn FreeAllocation(s: *mut c_char);
     fn RegisterEventCallback(callback: Option<unsafe extern "C" fn(*const c_char)>);
     fn UnregisterEventCallback();
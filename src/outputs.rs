@@ -1,27 +1,148 @@
+use crate::error::{PulumistError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt::Write;
 use regex::Regex;
 
-/// Represents an output reference like ${resourceName.outputProperty}
+/// Starts references to the outputs of other stacks, e.g. `${stack:acme/network/prod.vnetId}`, or
+/// `${stack:"acme/app/prod.eu".vnetId}` for stack names containing `.` or `[`.
+pub const STACK_REFERENCE_PREFIX: &str = "stack:";
+
+/// Represents an output reference like ${resourceName.outputProperty}, or ${stack:org/project/stack.outputName}
+/// for an output of another stack
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct OutputReference {
     pub resource_name: String,
+    /// Property path as written, e.g. `networkProfile.networkInterfaces[0].id`
     pub property_path: String,
+    /// The parsed property path
+    pub segments: Vec<PathSegment>,
+}
+
+/// One step of a reference's property path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PathSegment {
+    /// Object key, written `.key` or `["key"]` for keys containing `.`, `[` or `]`
+    Key(String),
+    /// Array index, written `[0]`
+    Index(usize),
 }
 
 impl OutputReference {
-    /// Parse a string like "resourceName.property.nested" into OutputReference
-    pub fn parse(reference: &str) -> Option<Self> {
-        let parts: Vec<&str> = reference.split('.').collect();
-        if parts.len() >= 2 {
-            Some(OutputReference {
-                resource_name: parts[0].to_string(),
-                property_path: parts[1..].join("."),
-            })
+    /// Parse a reference like "resourceName.property.nested", "vm.nics[0].id",
+    /// "kv.tags[\"cost-center\"]" or "stack:acme/network/prod.vnetId" (the text between `${` and `}`)
+    /// into OutputReference.
+    ///
+    /// For stack references the resource name is `stack:` followed by the stack name, and the path starts
+    /// with the name of the stack output. Stack names containing `.` or `[` are quoted, as in
+    /// `stack:"acme/app/prod.eu".vnetId`; the resource name holds them without the quotes.
+    pub fn parse(reference: &str) -> Result<Self> {
+        let error = |reason: String| PulumistError::Reference(format!("`${{{}}}` {}", reference, reason));
+        let is_stack = reference.starts_with(STACK_REFERENCE_PREFIX);
+
+        let (resource_name, path) = split_reference(reference)
+            .ok_or_else(|| error("has an unterminated quoted stack name".to_string()))?;
+
+        if path.is_empty() {
+            return Err(error(if is_stack { "names no output of the stack" } else { "has no property path" }.to_string()));
+        }
+        if resource_name.is_empty() {
+            return Err(error("has no resource name".to_string()));
+        }
+        if resource_name == STACK_REFERENCE_PREFIX {
+            return Err(error("has no stack name".to_string()));
+        }
+        if !path.starts_with(['.', '[']) {
+            return Err(error(format!("expected `.` or `[` after the quoted stack name, found `{}`", path)));
+        }
+
+        let property_path = path.strip_prefix('.').unwrap_or(path);
+        let segments = parse_property_path(property_path)?;
+        if let (true, Some(PathSegment::Index(index))) = (is_stack, segments.first()) {
+            return Err(error(format!("must name an output of the stack before index {}", index)));
+        }
+        Ok(OutputReference {
+            resource_name,
+            property_path: property_path.to_string(),
+            segments,
+        })
+    }
+
+    /// The referenced stack, e.g. `acme/network/prod`, if this references an output of another stack.
+    pub fn stack(&self) -> Option<&str> {
+        self.resource_name.strip_prefix(STACK_REFERENCE_PREFIX)
+    }
+}
+
+/// Parses a property path: keys separated by `.`, array indexes like `[0]` and quoted keys like
+/// `["cost-center"]`, e.g. `networkProfile.networkInterfaces[0].id`. The Go bridge accepts the same grammar.
+pub fn parse_property_path(path: &str) -> Result<Vec<PathSegment>> {
+    let invalid = |reason: String| PulumistError::Reference(format!("invalid property path `{}`: {}", path, reason));
+
+    if path.is_empty() {
+        return Err(invalid("the path is empty".to_string()));
+    }
+
+    let mut segments = Vec::new();
+    let mut rest = path;
+    while !rest.is_empty() {
+        if let Some(bracket) = rest.strip_prefix('[') {
+            if let Some(quoted) = bracket.strip_prefix('"') {
+                let end = quoted.find("\"]")
+                    .ok_or_else(|| invalid("unterminated quoted key".to_string()))?;
+                segments.push(PathSegment::Key(quoted[..end].to_string()));
+                rest = &quoted[end + 2..];
+            } else {
+                let end = bracket.find(']')
+                    .ok_or_else(|| invalid("unterminated array index".to_string()))?;
+                let index = &bracket[..end];
+                if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(invalid(format!("`[{}]` is not an array index or quoted key", index)));
+                }
+                let index = index.parse()
+                    .map_err(|_| invalid(format!("array index {} is too large", index)))?;
+                segments.push(PathSegment::Index(index));
+                rest = &bracket[end + 1..];
+            }
         } else {
-            None
+            let key = if segments.is_empty() {
+                rest
+            } else {
+                rest.strip_prefix('.')
+                    .ok_or_else(|| invalid(format!("expected `.` or `[` before `{}`", rest)))?
+            };
+            let end = key.find(['.', '[', ']', '"']).unwrap_or(key.len());
+            if end == 0 {
+                return Err(invalid("keys must not be empty, quote keys containing `.`, `[`, `]` or `\"`".to_string()));
+            }
+            segments.push(PathSegment::Key(key[..end].to_string()));
+            rest = &key[end..];
         }
     }
+
+    Ok(segments)
+}
+
+/// Formats a property path in the grammar accepted by [`parse_property_path`].
+pub fn format_property_path(segments: &[PathSegment]) -> String {
+    let mut path = String::new();
+    for segment in segments {
+        match segment {
+            PathSegment::Key(key) if !key.is_empty() && !key.contains(['.', '[', ']', '"']) => {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(key);
+            }
+            PathSegment::Key(key) => {
+                let _ = write!(path, "[\"{}\"]", key);
+            }
+            PathSegment::Index(index) => {
+                let _ = write!(path, "[{}]", index);
+            }
+        }
+    }
+    path
 }
 
 /// Finds all output references in a JSON value
@@ -37,7 +158,7 @@ fn find_references_recursive(value: &serde_json::Value, references: &mut Vec<Out
             // Look for ${...} patterns
             let re = Regex::new(r"\$\{([^}]+)\}").unwrap();
             for cap in re.captures_iter(s) {
-                if let Some(reference) = OutputReference::parse(&cap[1]) {
+                if let Ok(reference) = OutputReference::parse(&cap[1]) {
                     references.push(reference);
                 }
             }
@@ -56,69 +177,132 @@ fn find_references_recursive(value: &serde_json::Value, references: &mut Vec<Out
     }
 }
 
-/// Resolves output references in a JSON value using provided outputs
+/// Resolves output references in a JSON value using provided outputs.
+///
+/// A string that is exactly one reference is replaced by the referenced value, keeping its type.
+/// References inside a longer string are interpolated, with non-string values written as JSON.
+/// References to resources without outputs are kept as written. A reference to a path that does not
+/// exist in the resource's outputs fails with [`PulumistError::Reference`].
 pub fn resolve_output_references(
     value: &serde_json::Value,
     outputs: &HashMap<String, serde_json::Value>,
-) -> serde_json::Value {
+) -> Result<serde_json::Value> {
     match value {
         serde_json::Value::String(s) => {
             let re = Regex::new(r"\$\{([^}]+)\}").unwrap();
-            let mut result = s.clone();
             
+            if let Some(cap) = re.captures(s).filter(|cap| cap[0].len() == s.len()) {
+                if let Some(value) = resolve_reference(&cap[1], outputs)? {
+                    return Ok(value.clone());
+                }
+            }
+            
+            let mut result = String::new();
+            let mut position = 0;
             for cap in re.captures_iter(s) {
-                let full_match = &cap[0];
-                let reference_str = &cap[1];
+                let full_match = cap.get(0).unwrap();
+                result.push_str(&s[position..full_match.start()]);
+                position = full_match.end();
                 
-                if let Some(reference) = OutputReference::parse(reference_str) {
-                    if let Some(resource_outputs) = outputs.get(&reference.resource_name) {
-                        if let Some(value) = get_nested_value(resource_outputs, &reference.property_path) {
-                            // Convert the value to string for replacement
-                            let replacement = match value {
-                                serde_json::Value::String(s) => s.clone(),
-                                _ => value.to_string(),
-                            };
-                            result = result.replace(full_match, &replacement);
-                        }
-                    }
+                match resolve_reference(&cap[1], outputs)? {
+                    // Convert the value to string for replacement
+                    Some(serde_json::Value::String(value)) => result.push_str(value),
+                    Some(value) => result.push_str(&value.to_string()),
+                    None => result.push_str(full_match.as_str()),
                 }
             }
+            result.push_str(&s[position..]);
             
-            serde_json::Value::String(result)
+            Ok(serde_json::Value::String(result))
         }
         serde_json::Value::Array(arr) => {
-            serde_json::Value::Array(
+            Ok(serde_json::Value::Array(
                 arr.iter()
                     .map(|v| resolve_output_references(v, outputs))
-                    .collect(),
-            )
+                    .collect::<Result<_>>()?,
+            ))
         }
         serde_json::Value::Object(map) => {
             let mut new_map = serde_json::Map::new();
             for (k, v) in map {
-                new_map.insert(k.clone(), resolve_output_references(v, outputs));
+                new_map.insert(k.clone(), resolve_output_references(v, outputs)?);
             }
-            serde_json::Value::Object(new_map)
+            Ok(serde_json::Value::Object(new_map))
+        }
+        _ => Ok(value.clone()),
+    }
+}
+
+/// Splits a reference into its resource name and the rest, which holds the property path. A quoted stack
+/// name may contain `.` and `[`, so it ends at its closing quote; `None` if that quote is missing.
+fn split_reference(reference: &str) -> Option<(String, &str)> {
+    match reference.strip_prefix(STACK_REFERENCE_PREFIX).and_then(|stack| stack.strip_prefix('"')) {
+        Some(quoted) => {
+            let end = quoted.find('"')?;
+            Some((format!("{}{}", STACK_REFERENCE_PREFIX, &quoted[..end]), &quoted[end + 1..]))
+        }
+        None => {
+            let end = reference.find(['.', '[']).unwrap_or(reference.len());
+            Some((reference[..end].to_string(), &reference[end..]))
         }
-        _ => value.clone(),
     }
 }
 
-/// Gets a nested value from a JSON object using dot notation
-fn get_nested_value<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
-    let parts: Vec<&str> = path.split('.').collect();
+/// Looks up the value a reference points to, or `None` if it does not name a resource in `outputs`
+fn resolve_reference<'a>(
+    reference: &str,
+    outputs: &'a HashMap<String, serde_json::Value>,
+) -> Result<Option<&'a serde_json::Value>> {
+    // `${name}` without a property path is not a reference, e.g. a shell variable in a script
+    let Some((resource_name, _)) = split_reference(reference).filter(|(_, path)| !path.is_empty()) else {
+        return Ok(None);
+    };
+    let Some(resource_outputs) = outputs.get(&resource_name) else {
+        return Ok(None);
+    };
+    
+    let parsed = OutputReference::parse(reference)?;
+    get_nested_value(resource_outputs, &parsed.segments)
+        .map(Some)
+        .map_err(|reason| PulumistError::Reference(format!("`${{{}}}`: {}", reference, reason)))
+}
+
+/// Gets a nested value from a JSON value by following a property path, naming the first
+/// segment that does not exist if the path cannot be followed
+fn get_nested_value<'a>(value: &'a serde_json::Value, segments: &[PathSegment]) -> std::result::Result<&'a serde_json::Value, String> {
     let mut current = value;
     
-    for part in parts {
-        match current {
-            serde_json::Value::Object(map) => {
-                current = map.get(part)?;
+    for (i, segment) in segments.iter().enumerate() {
+        let parent = match i {
+            0 => "the resource".to_string(),
+            _ => format!("`{}`", format_property_path(&segments[..i])),
+        };
+        current = match (segment, current) {
+            (PathSegment::Key(key), serde_json::Value::Object(map)) => map.get(key)
+                .ok_or_else(|| format!("{} has no {} `{}`", parent, if i == 0 { "output" } else { "key" }, key))?,
+            (PathSegment::Index(index), serde_json::Value::Array(items)) => items.get(*index)
+                .ok_or_else(|| format!("{} has no index {} (length {})", parent, index, items.len()))?,
+            (PathSegment::Key(key), _) => {
+                return Err(format!("{} is {}, not an object with key `{}`", parent, value_kind(current), key));
             }
-            _ => return None,
-        }
+            (PathSegment::Index(index), _) => {
+                return Err(format!("{} is {}, not an array with index {}", parent, value_kind(current), index));
+            }
+        };
     }
     
-    Some(current)
+    Ok(current)
+}
+
+fn value_kind(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "a boolean",
+        serde_json::Value::Number(_) => "a number",
+        serde_json::Value::String(_) => "a string",
+        serde_json::Value::Array(_) => "an array",
+        serde_json::Value::Object(_) => "an object",
+    }
 }
 
 #[cfg(test)]
@@ -152,6 +336,39 @@ mod tests {
         assert_eq!(refs.len(), 4);
     }
 
+    #[test]
+    fn test_stack_references() {
+        let value = json!({
+            "subnetId": "${stack:acme/network/prod.subnets[0].id}",
+            "connection": "Server=${stack:acme/data/prod.dbHost};Port=5432",
+        });
+        let refs = find_output_references(&value);
+        let stacks: Vec<(Option<&str>, &str)> = refs.iter()
+            .map(|reference| (reference.stack(), reference.property_path.as_str()))
+            .collect();
+        assert_eq!(stacks, vec![(Some("acme/data/prod"), "dbHost"), (Some("acme/network/prod"), "subnets[0].id")]);
+        assert_eq!(OutputReference::parse("rg.name").unwrap().stack(), None);
+
+        let quoted = OutputReference::parse("stack:\"acme/app/prod.eu\".endpoints[\"web\"]").unwrap();
+        assert_eq!(quoted.stack(), Some("acme/app/prod.eu"));
+        assert_eq!(quoted.segments, vec![PathSegment::Key("endpoints".to_string()), PathSegment::Key("web".to_string())]);
+
+        for (reference, message) in [
+            ("stack:acme/network/prod", "`${stack:acme/network/prod}` names no output of the stack"),
+            ("stack:.vnetId", "`${stack:.vnetId}` has no stack name"),
+            ("stack:acme/network/prod[0]", "`${stack:acme/network/prod[0]}` must name an output of the stack before index 0"),
+            ("stack:\"acme/app/prod.eu\"", "`${stack:\"acme/app/prod.eu\"}` names no output of the stack"),
+            ("stack:\"acme/app/prod.eu.vnetId", "`${stack:\"acme/app/prod.eu.vnetId}` has an unterminated quoted stack name"),
+            ("stack:\"\".vnetId", "`${stack:\"\".vnetId}` has no stack name"),
+            ("stack:\"acme/app/prod\"eu.vnetId", "`${stack:\"acme/app/prod\"eu.vnetId}` expected `.` or `[` after the quoted stack name, found `eu.vnetId`"),
+        ] {
+            match OutputReference::parse(reference) {
+                Err(PulumistError::Reference(error)) => assert_eq!(error, message),
+                other => panic!("expected a reference error for {}, got {:?}", reference, other),
+            }
+        }
+    }
+
     #[test]
     fn test_resolve_output_references() {
         let value = json!({
@@ -165,8 +382,105 @@ mod tests {
             "location": "eastus"
         }));
         
-        let resolved = resolve_output_references(&value, &outputs);
+        let resolved = resolve_output_references(&value, &outputs).unwrap();
         assert_eq!(resolved["resourceGroupName"], "my-resource-group");
         assert_eq!(resolved["location"], "eastus");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_resolve_interpolated_references() {
+        let value = json!({
+            "connection": "${db.host}:${db.port}",
+            "url": "https://${app.defaultHostName}/api",
+            "port": "${db.port}",
+            "unknown": "${db.host}/${missing.name}"
+        });
+        
+        let mut outputs = HashMap::new();
+        outputs.insert("db".to_string(), json!({ "host": "db.example.com", "port": 5432 }));
+        outputs.insert("app".to_string(), json!({ "defaultHostName": "app.azurewebsites.net" }));
+        
+        let resolved = resolve_output_references(&value, &outputs).unwrap();
+        assert_eq!(resolved["connection"], "db.example.com:5432");
+        assert_eq!(resolved["url"], "https://app.azurewebsites.net/api");
+        assert_eq!(resolved["port"], 5432);
+        assert_eq!(resolved["unknown"], "db.example.com/${missing.name}");
+    }
+
+    #[test]
+    fn test_resolve_quoted_stack_reference() {
+        let value = json!({
+            "endpoint": "${stack:\"org/proj/dev.eu\".out}",
+            "url": "https://${stack:\"org/proj/dev.eu\".out}/api",
+        });
+
+        let mut outputs = HashMap::new();
+        outputs.insert("stack:org/proj/dev.eu".to_string(), json!({ "out": "app.example.com" }));
+
+        let resolved = resolve_output_references(&value, &outputs).unwrap();
+        assert_eq!(resolved["endpoint"], "app.example.com");
+        assert_eq!(resolved["url"], "https://app.example.com/api");
+    }
+
+    #[test]
+    fn test_parse_property_path() {
+        let reference = OutputReference::parse("vm.networkProfile.networkInterfaces[0].id").unwrap();
+        assert_eq!(reference.resource_name, "vm");
+        assert_eq!(reference.segments, vec![
+            PathSegment::Key("networkProfile".to_string()),
+            PathSegment::Key("networkInterfaces".to_string()),
+            PathSegment::Index(0),
+            PathSegment::Key("id".to_string()),
+        ]);
+        assert_eq!(format_property_path(&reference.segments), reference.property_path);
+        
+        let reference = OutputReference::parse(r#"kv.tags["cost-center"]"#).unwrap();
+        assert_eq!(reference.segments, vec![
+            PathSegment::Key("tags".to_string()),
+            PathSegment::Key("cost-center".to_string()),
+        ]);
+        
+        let reference = OutputReference::parse(r#"app["a.b"][2]"#).unwrap();
+        assert_eq!(reference.resource_name, "app");
+        assert_eq!(reference.segments, vec![PathSegment::Key("a.b".to_string()), PathSegment::Index(2)]);
+        assert_eq!(format_property_path(&reference.segments), r#"["a.b"][2]"#);
+        
+        for invalid in ["vm", ".id", "vm.", "vm.a..b", "vm.a[x]", "vm.a[-1]", "vm.a[0", r#"vm.a["b"#, "vm.a[0]b"] {
+            assert!(matches!(OutputReference::parse(invalid), Err(PulumistError::Reference(_))), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_resolve_deep_references() {
+        let value = json!({
+            "nic": "${vm.networkProfile.networkInterfaces[0].id}",
+            "costCenter": "${kv.tags[\"cost-center\"]}",
+            "script": "echo ${HOME}"
+        });
+        
+        let mut outputs = HashMap::new();
+        outputs.insert("vm".to_string(), json!({
+            "networkProfile": { "networkInterfaces": [{ "id": "/nics/vm-nic" }] }
+        }));
+        outputs.insert("kv".to_string(), json!({ "tags": { "cost-center": "1234" } }));
+        
+        let resolved = resolve_output_references(&value, &outputs).unwrap();
+        assert_eq!(resolved["nic"], "/nics/vm-nic");
+        assert_eq!(resolved["costCenter"], "1234");
+        assert_eq!(resolved["script"], "echo ${HOME}");
+        
+        let missing = json!("${vm.networkProfile.networkInterfaces[1].id}");
+        match resolve_output_references(&missing, &outputs) {
+            Err(PulumistError::Reference(message)) => {
+                assert!(message.contains("`networkProfile.networkInterfaces` has no index 1 (length 1)"), "{}", message);
+            }
+            other => panic!("expected a reference error, got {:?}", other),
+        }
+        
+        let missing = json!("${kv.tags.owner}");
+        match resolve_output_references(&missing, &outputs) {
+            Err(PulumistError::Reference(message)) => assert!(message.contains("`tags` has no key `owner`"), "{}", message),
+            other => panic!("expected a reference error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,482 @@
+//! Typed results of stack operations
+//!
+//! Previews, updates, destroys, refreshes and imports are decoded from the `OperationResult` of the Go response.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+use crate::dynamic::{DynamicResource, PulumiDynamic, ResourceOptions};
+use crate::events::ResourceOperation;
+use crate::proto;
+
+/// Number of resources per operation kind, keyed by engine op such as `"create"` or `"same"`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ResourceChanges(pub HashMap<String, u32>);
+
+impl ResourceChanges {
+    /// Number of resources with the given operation.
+    pub fn count(&self, op: &ResourceOperation) -> u32 {
+        self.0.get(op.as_op()).copied().unwrap_or_default()
+    }
+
+    /// Whether any resource was, or would be, changed, i.e. counted under an operation other than `same`.
+    pub fn has_changes(&self) -> bool {
+        self.0.iter().any(|(op, count)| op != "same" && *count > 0)
+    }
+}
+
+impl From<HashMap<String, i32>> for ResourceChanges {
+    fn from(changes: HashMap<String, i32>) -> Self {
+        ResourceChanges(changes.into_iter().map(|(op, count)| (op, count.max(0) as u32)).collect())
+    }
+}
+
+/// Status of an update as recorded by the backend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpdateStatus {
+    NotStarted,
+    InProgress,
+    Succeeded,
+    Failed,
+    /// A status this version of pulumist does not know about
+    Other(String),
+}
+
+impl UpdateStatus {
+    /// Parses a backend status such as `"succeeded"`, `None` if no update was recorded.
+    pub fn from_status(status: &str) -> Option<Self> {
+        match status {
+            "" => None,
+            "not-started" => Some(UpdateStatus::NotStarted),
+            "in-progress" => Some(UpdateStatus::InProgress),
+            "succeeded" => Some(UpdateStatus::Succeeded),
+            "failed" => Some(UpdateStatus::Failed),
+            other => Some(UpdateStatus::Other(other.to_string())),
+        }
+    }
+}
+
+/// Result of a preview, see [`PreviewBuilder`](crate::stack::PreviewBuilder).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PreviewResult {
+    /// Number of resources per operation the update would perform
+    pub resource_changes: ResourceChanges,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration: Duration,
+}
+
+/// Result of an update, see [`DeploymentBuilder`](crate::stack::DeploymentBuilder).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpResult {
+    pub resource_changes: ResourceChanges,
+    /// Stack outputs after the update
+    pub outputs: HashMap<String, Value>,
+    /// State of the deployed resources keyed by resource name
+    pub resources: HashMap<String, ResourceState>,
+    /// Status recorded by the backend, `None` if the backend did not record the update
+    pub status: Option<UpdateStatus>,
+    pub message: String,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration: Duration,
+}
+
+/// A deployed resource as recorded in the stack state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceState {
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub urn: String,
+    /// Provider-assigned ID of the cloud resource
+    pub id: String,
+    /// Outputs reported by the provider, with secret values replaced by `"[secret]"`
+    pub outputs: HashMap<String, Value>,
+}
+
+impl From<proto::pulumist::ResourceState> for ResourceState {
+    fn from(state: proto::pulumist::ResourceState) -> Self {
+        ResourceState {
+            resource_type: state.r#type,
+            urn: state.urn,
+            id: state.id,
+            outputs: json_map(&state.outputs),
+        }
+    }
+}
+
+/// Result of a destroy, see [`Stack::destroy`](crate::stack::Stack::destroy).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DestroyResult {
+    pub resource_changes: ResourceChanges,
+    pub status: Option<UpdateStatus>,
+    pub message: String,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration: Duration,
+}
+
+/// Result of a refresh, see [`RefreshBuilder`](crate::stack::RefreshBuilder).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RefreshResult {
+    /// The changes were only computed; the stack state was not written
+    pub preview_only: bool,
+    /// Resources whose state was, or would be, updated to match the actual cloud resources
+    pub updated: Vec<String>,
+    /// Resources that no longer exist and were, or would be, removed from the state
+    pub deleted: Vec<String>,
+    /// Number of resources per operation, e.g. `"same"`, `"update"` or `"delete"`
+    pub resource_changes: ResourceChanges,
+    /// Status recorded by the backend, `None` for preview-only refreshes
+    pub status: Option<UpdateStatus>,
+    /// Summary message of the update, empty for preview-only refreshes
+    pub message: String,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration: Duration,
+}
+
+impl RefreshResult {
+    /// Whether the stack state differs from the actual cloud resources.
+    pub fn has_drift(&self) -> bool {
+        !self.updated.is_empty() || !self.deleted.is_empty()
+    }
+}
+
+impl From<proto::pulumist::OperationResult> for PreviewResult {
+    fn from(result: proto::pulumist::OperationResult) -> Self {
+        PreviewResult {
+            duration: duration(&result),
+            resource_changes: result.resource_changes.into(),
+            stdout: result.stdout,
+            stderr: result.stderr,
+        }
+    }
+}
+
+impl From<proto::pulumist::OperationResult> for UpResult {
+    fn from(result: proto::pulumist::OperationResult) -> Self {
+        UpResult {
+            duration: duration(&result),
+            status: UpdateStatus::from_status(&result.status),
+            resource_changes: result.resource_changes.into(),
+            outputs: json_map(&result.outputs),
+            resources: result.resources.into_iter()
+                .map(|state| (state.name.clone(), state.into()))
+                .collect(),
+            message: result.message,
+            stdout: result.stdout,
+            stderr: result.stderr,
+        }
+    }
+}
+
+impl From<proto::pulumist::OperationResult> for DestroyResult {
+    fn from(result: proto::pulumist::OperationResult) -> Self {
+        DestroyResult {
+            duration: duration(&result),
+            status: UpdateStatus::from_status(&result.status),
+            resource_changes: result.resource_changes.into(),
+            message: result.message,
+            stdout: result.stdout,
+            stderr: result.stderr,
+        }
+    }
+}
+
+/// `preview_only` is not part of the response; set it from the request.
+impl From<proto::pulumist::OperationResult> for RefreshResult {
+    fn from(result: proto::pulumist::OperationResult) -> Self {
+        RefreshResult {
+            preview_only: false,
+            duration: duration(&result),
+            status: UpdateStatus::from_status(&result.status),
+            updated: resource_names(&result.updated),
+            deleted: resource_names(&result.deleted),
+            resource_changes: result.resource_changes.into(),
+            message: result.message,
+            stdout: result.stdout,
+            stderr: result.stderr,
+        }
+    }
+}
+
+fn json_map(values: &HashMap<String, proto::pulumist::Value>) -> HashMap<String, Value> {
+    values.iter()
+        .map(|(name, value)| (name.clone(), PulumiDynamic::pb_value_to_json(value)))
+        .collect()
+}
+
+fn duration(result: &proto::pulumist::OperationResult) -> Duration {
+    Duration::try_from_secs_f64(result.duration_seconds).unwrap_or_default()
+}
+
+// Go reports resources by URN; results name them like events do
+fn resource_names(urns: &[String]) -> Vec<String> {
+    urns.iter()
+        .map(|urn| crate::events::resource_name_from_urn(urn).to_string())
+        .collect()
+}
+
+/// Result of an import, see [`ImportBuilder`](crate::stack::ImportBuilder).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportResult {
+    /// The imported resources with the properties read from the cloud. Pass them to
+    /// [`DeploymentBuilder::with_resource`](crate::stack::DeploymentBuilder::with_resource) so later
+    /// deployments keep managing them instead of deleting them.
+    pub imported: Vec<DynamicResource>,
+    /// Resources that could not be imported; they were left out of the stack state
+    pub failures: Vec<ImportFailure>,
+    /// Paths of the secret inputs of imported resources, by resource name, e.g. `"siteConfig.password"`.
+    /// The state only holds their ciphertext, so they are left out of the properties and must be set
+    /// before deploying the resources.
+    #[serde(rename = "secretInputs")]
+    pub secret_inputs: HashMap<String, Vec<String>>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// A resource that could not be imported.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportFailure {
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub name: String,
+    pub id: String,
+    pub message: String,
+}
+
+impl ImportResult {
+    /// Whether every requested resource was imported.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl From<proto::pulumist::OperationResult> for ImportResult {
+    fn from(result: proto::pulumist::OperationResult) -> Self {
+        let mut secret_inputs = HashMap::new();
+
+        ImportResult {
+            imported: result.imported.into_iter()
+                .map(|resource| {
+                    let mut properties = Value::Object(json_map(&resource.inputs).into_iter().collect());
+                    // Bridged providers record which inputs were defaulted; that is state bookkeeping, not a property
+                    if let Value::Object(map) = &mut properties {
+                        map.remove("__defaults");
+                    }
+
+                    let mut secrets = vec![];
+                    strip_secrets(&mut properties, "", &mut secrets);
+                    if !secrets.is_empty() {
+                        secret_inputs.insert(resource.name.clone(), secrets);
+                    }
+
+                    // Go imports resources protected, so later deployments must keep them protected
+                    let parent = Some(resource.parent).filter(|parent| !parent.is_empty());
+                    let provider = Some(resource.provider).filter(|provider| !provider.is_empty());
+                    DynamicResource {
+                        resource_type: resource.r#type,
+                        name: resource.name,
+                        properties,
+                        options: (resource.protect || parent.is_some() || provider.is_some()).then(|| ResourceOptions {
+                            parent,
+                            provider,
+                            protect: resource.protect.then_some(true),
+                            ..Default::default()
+                        }),
+                    }
+                })
+                .collect(),
+            failures: result.failures.into_iter()
+                .map(|failure| ImportFailure {
+                    resource_type: failure.r#type,
+                    name: failure.name,
+                    id: failure.id,
+                    message: failure.message,
+                })
+                .collect(),
+            secret_inputs,
+            stdout: result.stdout,
+            stderr: result.stderr,
+        }
+    }
+}
+
+/// Key that marks an encrypted secret value in the stack state.
+const SECRET_SIGNATURE: &str = "4dabf18193072939515e22adb298388d";
+
+/// Removes the encrypted secrets from a state value and collects their paths.
+/// Secret array elements become null, so the other elements keep their index.
+fn strip_secrets(value: &mut Value, path: &str, secrets: &mut Vec<String>) {
+    let is_secret = |value: &Value| value.as_object().is_some_and(|map| map.contains_key(SECRET_SIGNATURE));
+
+    match value {
+        Value::Object(map) => {
+            let keys: Vec<String> = map.keys().cloned().collect();
+            for key in keys {
+                let path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                if is_secret(&map[&key]) {
+                    map.remove(&key);
+                    secrets.push(path);
+                } else if let Some(item) = map.get_mut(&key) {
+                    strip_secrets(item, &path, secrets);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                let path = format!("{}[{}]", path, i);
+                if is_secret(item) {
+                    *item = Value::Null;
+                    secrets.push(path);
+                } else {
+                    strip_secrets(item, &path, secrets);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_results_from_operation_result() {
+        let result = proto::pulumist::OperationResult {
+            stdout: "Updating (dev)".to_string(),
+            resource_changes: HashMap::from([("create".to_string(), 2), ("same".to_string(), 1)]),
+            outputs: HashMap::from([("endpoint".to_string(), proto::pulumist::Value {
+                value: Some(proto::pulumist::value::Value::StringValue("https://example.com".to_string())),
+            })]),
+            status: "succeeded".to_string(),
+            duration_seconds: 12.5,
+            resources: vec![proto::pulumist::ResourceState {
+                name: "storage".to_string(),
+                r#type: "azure-native:storage:StorageAccount".to_string(),
+                urn: "urn:pulumi:dev::proj::azure-native:storage:StorageAccount::storage".to_string(),
+                id: "/subscriptions/0/resourceGroups/rg/providers/Microsoft.Storage/storageAccounts/storage".to_string(),
+                outputs: HashMap::from([("primaryEndpoints".to_string(), proto::pulumist::Value {
+                    value: Some(proto::pulumist::value::Value::MapValue(proto::pulumist::ValueMap {
+                        fields: HashMap::from([("blob".to_string(), proto::pulumist::Value {
+                            value: Some(proto::pulumist::value::Value::StringValue("https://storage.blob.core.windows.net/".to_string())),
+                        })]),
+                    })),
+                })]),
+            }],
+            ..Default::default()
+        };
+
+        let up = UpResult::from(result.clone());
+        assert_eq!(up.status, Some(UpdateStatus::Succeeded));
+        assert_eq!(up.resource_changes.count(&ResourceOperation::Create), 2);
+        assert_eq!(up.resource_changes.count(&ResourceOperation::Delete), 0);
+        assert!(up.resource_changes.has_changes());
+        assert_eq!(up.outputs["endpoint"], json!("https://example.com"));
+        assert_eq!(
+            up.resources["storage"].outputs["primaryEndpoints"],
+            json!({ "blob": "https://storage.blob.core.windows.net/" }),
+        );
+        assert_eq!(up.duration, Duration::from_millis(12500));
+        assert_eq!(up.stdout, "Updating (dev)");
+
+        let preview = PreviewResult::from(proto::pulumist::OperationResult {
+            resource_changes: HashMap::from([("same".to_string(), 3)]),
+            ..Default::default()
+        });
+        assert!(!preview.resource_changes.has_changes());
+    }
+
+    #[test]
+    fn test_refresh_result_names_changed_resources() {
+        let refresh = RefreshResult::from(proto::pulumist::OperationResult {
+            updated: vec!["urn:pulumi:dev::proj::azure-native:resources:ResourceGroup::rg".to_string()],
+            deleted: vec!["urn:pulumi:dev::proj::azure-native:storage:StorageAccount::sa".to_string()],
+            ..Default::default()
+        });
+        assert!(refresh.has_drift());
+        assert_eq!(refresh.updated, vec!["rg"]);
+        assert_eq!(refresh.deleted, vec!["sa"]);
+        assert_eq!(refresh.status, None);
+
+        assert!(!RefreshResult::default().has_drift());
+    }
+
+    #[test]
+    fn test_import_result_from_operation_result() {
+        let inputs = json!({ "accountName": "sa", "publicAccess": "None", "__defaults": [] });
+        let result = ImportResult::from(proto::pulumist::OperationResult {
+            stdout: "Importing (dev)".to_string(),
+            imported: vec![proto::pulumist::ImportedResource {
+                r#type: "azure-native:storage:BlobContainer".to_string(),
+                name: "logs".to_string(),
+                id: "/subscriptions/0/resourceGroups/rg/providers/Microsoft.Storage/storageAccounts/sa/blobServices/default/containers/logs".to_string(),
+                urn: "urn:pulumi:dev::proj::azure-native:storage:BlobContainer::logs".to_string(),
+                provider: "westeurope".to_string(),
+                protect: true,
+                inputs: PulumiDynamic::new().json_to_pb_map(&inputs),
+                ..Default::default()
+            }],
+            failures: vec![proto::pulumist::ImportFailure {
+                r#type: "azure-native:resources:ResourceGroup".to_string(),
+                name: "missing".to_string(),
+                id: "/subscriptions/0/resourceGroups/missing".to_string(),
+                message: "error: resource 'missing' does not exist".to_string(),
+            }],
+            ..Default::default()
+        });
+        assert!(!result.is_complete());
+        assert_eq!(result.stdout, "Importing (dev)");
+
+        let container = &result.imported[0];
+        assert_eq!(container.name, "logs");
+        assert_eq!(container.properties, json!({ "accountName": "sa", "publicAccess": "None" }));
+        let options = container.options.as_ref().unwrap();
+        assert_eq!(options.parent, None);
+        assert_eq!(options.provider.as_deref(), Some("westeurope"));
+        assert_eq!(options.protect, Some(true));
+        assert!(result.secret_inputs.is_empty());
+
+        assert_eq!(result.failures[0].name, "missing");
+        assert_eq!(result.failures[0].message, "error: resource 'missing' does not exist");
+    }
+
+    #[test]
+    fn test_import_result_leaves_out_secret_inputs() {
+        let secret = json!({ "4dabf18193072939515e22adb298388d": "1b47061264138c4ac30d75fd1eb44270", "ciphertext": "v1:abc" });
+        let inputs = json!({
+            "name": "site",
+            "siteConfig": {
+                "alwaysOn": true,
+                "appSettings": [{ "name": "PORT", "value": "8080" }, secret],
+            },
+            "clientCertPassword": secret,
+        });
+        let result = ImportResult::from(proto::pulumist::OperationResult {
+            imported: vec![proto::pulumist::ImportedResource {
+                r#type: "azure-native:web:WebApp".to_string(),
+                name: "site".to_string(),
+                id: "/subscriptions/0/resourceGroups/rg/providers/Microsoft.Web/sites/site".to_string(),
+                inputs: PulumiDynamic::new().json_to_pb_map(&inputs),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        let site = &result.imported[0];
+        assert_eq!(site.properties, json!({
+            "name": "site",
+            "siteConfig": { "alwaysOn": true, "appSettings": [{ "name": "PORT", "value": "8080" }, null] },
+        }));
+        let mut secrets = result.secret_inputs["site"].clone();
+        secrets.sort();
+        assert_eq!(secrets, vec!["clientCertPassword", "siteConfig.appSettings[1]"]);
+        assert!(site.options.is_none());
+    }
+}
@@ -1,6 +1,10 @@
 use crate::error::{Result, PulumistError};
-use crate::events::{DeploymentEvent, EventHandler};
-use crate::dynamic::{PulumiDynamic, StackRequest, DynamicResource, ImportRequest};
+use crate::cancellation::CancellationToken;
+use crate::config::PulumiConfig;
+use crate::events::EventHandler;
+use crate::dynamic::{PulumiDynamic, StackRequest, ComponentResource, DynamicResource, ImportRequest, ImportResource, Invoke, StackOutput, UntypedDeployment};
+use crate::validation::validate_deployment;
+use crate::results::{DestroyResult, ImportResult, PreviewResult, RefreshResult, UpResult};
 use serde_json::Value;
 use std::sync::Arc;
 use std::thread;
@@ -8,8 +12,9 @@ use std::thread;
 pub struct Stack {
     name: String,
     project: String,
-    backend: Option<String>,
     config: serde_json::Map<String, Value>,
+    secret_config: serde_json::Map<String, Value>,
+    pulumi_config: PulumiConfig,
     dynamic: PulumiDynamic,
 }
 
@@ -17,77 +22,181 @@ impl Stack {
     pub(crate) fn new(
         name: String,
         project: String,
-        backend: Option<String>,
         config: serde_json::Map<String, Value>,
+        secret_config: serde_json::Map<String, Value>,
+        pulumi_config: PulumiConfig,
         dynamic: PulumiDynamic,
     ) -> Result<Self> {
         Ok(Self {
             name,
             project,
-            backend,
             config,
+            secret_config,
+            pulumi_config,
             dynamic,
         })
     }
     
-    pub fn deploy(&self) -> DeploymentBuilder {
+    pub fn deploy(&self) -> DeploymentBuilder<'_> {
         DeploymentBuilder::new(self)
     }
     
-    pub fn preview(&self) -> PreviewBuilder {
+    pub fn preview(&self) -> PreviewBuilder<'_> {
         PreviewBuilder::new(self)
     }
     
-    pub fn destroy(&self) -> Result<()> {
-        let request = StackRequest {
-            project: self.project.clone(),
-            stack: self.name.clone(),
-            backend: self.backend.clone(),
-            config: self.config.clone(),
-            resources: vec![],
-        };
-        
-        self.dynamic.destroy(request).map_err(|e| PulumistError::StackOperation(e))?;
-        Ok(())
+    pub fn destroy(&self) -> DestroyBuilder<'_> {
+        DestroyBuilder::new(self)
     }
     
-    pub fn refresh(&self) -> RefreshBuilder {
+    pub fn refresh(&self) -> RefreshBuilder<'_> {
         RefreshBuilder::new(self)
     }
     
-    pub fn import(&self) -> ImportBuilder {
+    pub fn import(&self) -> ImportBuilder<'_> {
         ImportBuilder::new(self)
     }
     
-    pub fn export(&self) -> Result<Value> {
+    /// Exports the full deployment checkpoint of the stack, e.g. to back it up or inspect its resources.
+    pub fn export(&self) -> Result<UntypedDeployment> {
+        let request = self.request();
+        
+        self.dynamic.export_stack(request)
+    }
+    
+    /// Replaces the stack's state with `deployment`, e.g. one returned by [`export`](Self::export).
+    /// The stack is created if it does not exist, so state can be restored or migrated to another stack or backend.
+    pub fn import_state(&self, deployment: &UntypedDeployment) -> Result<()> {
+        let request = self.request();
+        
+        self.dynamic.import_stack(request, deployment)
+    }
+    
+    /// Calls the provider function `token` with `args` outside of a deployment and returns its result,
+    /// e.g. `stack.invoke("azure-native:authorization:getClientConfig", Map::new())`.
+    /// The function uses the default provider, configured from the stack configuration. It is called from a
+    /// temporary stack with a copy of that configuration, so this stack's state and settings are left untouched.
+    pub fn invoke(&self, token: &str, args: serde_json::Map<String, Value>) -> Result<serde_json::Map<String, Value>> {
         let request = StackRequest {
-            project: self.project.clone(),
-            stack: self.name.clone(),
-            backend: self.backend.clone(),
-            config: self.config.clone(),
-            resources: vec![],
+            invokes: vec![Invoke { args, ..Invoke::new(token, token) }],
+            ..self.request()
         };
         
-        self.dynamic.export_stack(request).map_err(|e| PulumistError::StackOperation(e))
+        self.dynamic.invoke(request)
     }
     
     pub fn get_outputs(&self) -> Result<Value> {
-        let request = StackRequest {
+        let request = self.request();
+        
+        self.dynamic.get_outputs(request)
+    }
+    
+    /// A request for this stack without resources, using the stack's default timeout.
+    /// Operations fill in what they need with struct update syntax.
+    fn request(&self) -> StackRequest {
+        StackRequest {
+            operation_id: crate::events::next_operation_id(),
             project: self.project.clone(),
             stack: self.name.clone(),
-            backend: self.backend.clone(),
             config: self.config.clone(),
+            secret_config: self.secret_config.clone(),
             resources: vec![],
-        };
-        
-        self.dynamic.get_outputs(request).map_err(|e| PulumistError::StackOperation(e))
+            timeout_seconds: self.pulumi_config.runtime.timeout_seconds,
+            preview_only: false,
+            outputs: vec![],
+            components: vec![],
+            invokes: vec![],
+            pulumi_config: self.pulumi_config.clone(),
+        }
+    }
+}
+
+/// A prepared stack operation.
+///
+/// The Go call blocks for the whole Pulumi run, so [`Operation::run`] moves it onto
+/// tokio's blocking pool while [`Operation::run_blocking`] runs it on the calling thread.
+struct Operation<T> {
+    operation_id: u64,
+    event_handler: Option<Arc<dyn EventHandler>>,
+    cancellation_token: Option<CancellationToken>,
+    call: Box<dyn FnOnce() -> Result<T> + Send>,
+}
+
+impl<T: Send + 'static> Operation<T> {
+    fn new<F>(operation_id: u64, call: F) -> Self
+    where
+        F: FnOnce() -> Result<T> + Send + 'static,
+    {
+        Self {
+            operation_id,
+            event_handler: None,
+            cancellation_token: None,
+            call: Box::new(call),
+        }
+    }
+
+    fn with_event_handler(mut self, handler: Option<Arc<dyn EventHandler>>) -> Self {
+        self.event_handler = handler;
+        self
+    }
+
+    fn with_cancellation_token(mut self, token: Option<CancellationToken>) -> Self {
+        self.cancellation_token = token;
+        self
+    }
+
+    /// Runs the operation on the blocking thread pool so the calling task yields.
+    ///
+    /// Dropping the returned future does not stop the Pulumi run already in progress.
+    async fn run(self) -> Result<T> {
+        tokio::task::spawn_blocking(move || self.run_blocking())
+            .await
+            .map_err(|e| PulumistError::StackOperation(format!("Stack operation task failed: {}", e)))?
+    }
+
+    fn run_blocking(self) -> Result<T> {
+        if let Some(token) = &self.cancellation_token {
+            token.attach(self.operation_id)?;
+        }
+
+        // If event handler is provided, forward this operation's events on a separate thread.
+        // The thread exits once the channel is cleaned up below.
+        let forwarder = self.event_handler.map(|handler| {
+            let event_receiver = crate::events::create_event_channel(self.operation_id);
+
+            thread::spawn(move || {
+                while let Ok(event) = event_receiver.recv() {
+                    handler.handle_event(event);
+                }
+            })
+        });
+
+        let result = (self.call)();
+
+        // Cleanup event channel, then wait until the handler has seen every event of the operation.
+        // A panicking handler only loses its remaining events, so its panic is not propagated.
+        crate::events::cleanup_event_channel(self.operation_id);
+        if let Some(forwarder) = forwarder {
+            let _ = forwarder.join();
+        }
+
+        if let Some(token) = &self.cancellation_token {
+            token.detach(self.operation_id);
+        }
+
+        result
     }
 }
 
 pub struct DeploymentBuilder<'a> {
     stack: &'a Stack,
     resources: Vec<DynamicResource>,
+    outputs: Vec<StackOutput>,
+    components: Vec<ComponentResource>,
+    invokes: Vec<Invoke>,
     event_handler: Option<Arc<dyn EventHandler>>,
+    cancellation_token: Option<CancellationToken>,
+    timeout_seconds: Option<u32>,
 }
 
 impl<'a> DeploymentBuilder<'a> {
@@ -95,7 +204,12 @@ impl<'a> DeploymentBuilder<'a> {
         Self {
             stack,
             resources: vec![],
+            outputs: vec![],
+            components: vec![],
+            invokes: vec![],
             event_handler: None,
+            cancellation_token: None,
+            timeout_seconds: None,
         }
     }
     
@@ -104,48 +218,95 @@ impl<'a> DeploymentBuilder<'a> {
         self
     }
     
+    /// Registers `component` and its children, see [`ComponentResource`].
+    pub fn with_component(mut self, component: ComponentResource) -> Self {
+        self.components.push(component);
+        self
+    }
+    
+    /// Calls the provider function `invoke` before the resources that reference its result, see [`Invoke`].
+    pub fn with_invoke(mut self, invoke: Invoke) -> Self {
+        self.invokes.push(invoke);
+        self
+    }
+    
+    /// Exports `value` as the stack output `name`, replacing an output of the same name.
+    /// Strings may reference resources, e.g. `"${storage.primaryEndpoints.blob}"`.
+    pub fn with_output(self, name: &str, value: impl Into<Value>) -> Self {
+        self.add_output(name, value.into(), false)
+    }
+    
+    /// Like [`with_output`](Self::with_output), but the output is stored encrypted with the stack's secrets provider.
+    pub fn with_secret_output(self, name: &str, value: impl Into<Value>) -> Self {
+        self.add_output(name, value.into(), true)
+    }
+    
+    fn add_output(mut self, name: &str, value: Value, secret: bool) -> Self {
+        self.outputs.retain(|output| output.name != name);
+        self.outputs.push(StackOutput { name: name.to_string(), value, secret });
+        self
+    }
+    
     pub fn with_event_handler(mut self, handler: Arc<dyn EventHandler>) -> Self {
         self.event_handler = Some(handler);
         self
     }
     
-    pub async fn execute(self) -> Result<Value> {
+    /// Lets `token` cancel this operation, see [`CancellationToken`].
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+    
+    /// Fails the operation with [`PulumistError::Timeout`] if it runs longer than `seconds`.
+    /// Overrides the stack's [`RuntimeOptions::timeout_seconds`](crate::config::RuntimeOptions::timeout_seconds).
+    pub fn with_timeout_seconds(mut self, seconds: u32) -> Self {
+        self.timeout_seconds = Some(seconds);
+        self
+    }
+    
+    /// Checks the resources for problems such as unknown references or duplicate names without calling
+    /// Pulumi, see [`validate_deployment`].
+    pub fn validate(&self) -> Result<()> {
+        validate_deployment(&self.resources, &self.components, &self.invokes)
+    }
+    
+    /// Runs the deploy without blocking the async runtime.
+    pub async fn execute(self) -> Result<UpResult> {
+        self.into_operation().run().await
+    }
+
+    /// Runs the deploy on the current thread, for callers without an async runtime.
+    pub fn execute_blocking(self) -> Result<UpResult> {
+        self.into_operation().run_blocking()
+    }
+
+    fn into_operation(self) -> Operation<UpResult> {
         let request = StackRequest {
-            project: self.stack.project.clone(),
-            stack: self.stack.name.clone(),
-            backend: self.stack.backend.clone(),
-            config: self.stack.config.clone(),
             resources: self.resources,
+            timeout_seconds: self.timeout_seconds.or(self.stack.pulumi_config.runtime.timeout_seconds),
+            outputs: self.outputs,
+            components: self.components,
+            invokes: self.invokes,
+            ..self.stack.request()
         };
-        
-        // If event handler is provided, set up event channel
-        if let Some(handler) = self.event_handler {
-            let event_receiver = crate::events::create_event_channel();
-            
-            // Spawn a thread to handle events
-            thread::spawn(move || {
-                while let Ok(event_json) = event_receiver.recv() {
-                    if let Ok(event) = serde_json::from_value::<DeploymentEvent>(event_json) {
-                        handler.handle_event(event);
-                    }
-                }
-            });
-        }
-        
-        let result = self.stack.dynamic.deploy(request)
-            .map_err(|e| PulumistError::StackOperation(e));
-            
-        // Cleanup event channel
-        crate::events::cleanup_event_channel();
-        
-        result
+
+        let dynamic = self.stack.dynamic.clone();
+        Operation::new(request.operation_id, move || dynamic.deploy(request))
+            .with_event_handler(self.event_handler)
+            .with_cancellation_token(self.cancellation_token)
     }
 }
 
 pub struct PreviewBuilder<'a> {
     stack: &'a Stack,
     resources: Vec<DynamicResource>,
+    outputs: Vec<StackOutput>,
+    components: Vec<ComponentResource>,
+    invokes: Vec<Invoke>,
     event_handler: Option<Arc<dyn EventHandler>>,
+    cancellation_token: Option<CancellationToken>,
+    timeout_seconds: Option<u32>,
 }
 
 impl<'a> PreviewBuilder<'a> {
@@ -153,7 +314,12 @@ impl<'a> PreviewBuilder<'a> {
         Self {
             stack,
             resources: vec![],
+            outputs: vec![],
+            components: vec![],
+            invokes: vec![],
             event_handler: None,
+            cancellation_token: None,
+            timeout_seconds: None,
         }
     }
     
@@ -162,47 +328,92 @@ impl<'a> PreviewBuilder<'a> {
         self
     }
     
+    /// Registers `component` and its children, see [`ComponentResource`].
+    pub fn with_component(mut self, component: ComponentResource) -> Self {
+        self.components.push(component);
+        self
+    }
+    
+    /// Calls the provider function `invoke` before the resources that reference its result, see [`Invoke`].
+    pub fn with_invoke(mut self, invoke: Invoke) -> Self {
+        self.invokes.push(invoke);
+        self
+    }
+    
+    /// Exports `value` as the stack output `name`, replacing an output of the same name.
+    /// Strings may reference resources, e.g. `"${storage.primaryEndpoints.blob}"`.
+    pub fn with_output(self, name: &str, value: impl Into<Value>) -> Self {
+        self.add_output(name, value.into(), false)
+    }
+    
+    /// Like [`with_output`](Self::with_output), but the output is stored encrypted with the stack's secrets provider.
+    pub fn with_secret_output(self, name: &str, value: impl Into<Value>) -> Self {
+        self.add_output(name, value.into(), true)
+    }
+    
+    fn add_output(mut self, name: &str, value: Value, secret: bool) -> Self {
+        self.outputs.retain(|output| output.name != name);
+        self.outputs.push(StackOutput { name: name.to_string(), value, secret });
+        self
+    }
+    
     pub fn with_event_handler(mut self, handler: Arc<dyn EventHandler>) -> Self {
         self.event_handler = Some(handler);
         self
     }
     
-    pub async fn execute(self) -> Result<Value> {
+    /// Lets `token` cancel this operation, see [`CancellationToken`].
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+    
+    /// Fails the operation with [`PulumistError::Timeout`] if it runs longer than `seconds`.
+    /// Overrides the stack's [`RuntimeOptions::timeout_seconds`](crate::config::RuntimeOptions::timeout_seconds).
+    pub fn with_timeout_seconds(mut self, seconds: u32) -> Self {
+        self.timeout_seconds = Some(seconds);
+        self
+    }
+    
+    /// Checks the resources for problems such as unknown references or duplicate names without calling
+    /// Pulumi, see [`validate_deployment`].
+    pub fn validate(&self) -> Result<()> {
+        validate_deployment(&self.resources, &self.components, &self.invokes)
+    }
+    
+    /// Runs the preview without blocking the async runtime.
+    pub async fn execute(self) -> Result<PreviewResult> {
+        self.into_operation().run().await
+    }
+
+    /// Runs the preview on the current thread, for callers without an async runtime.
+    pub fn execute_blocking(self) -> Result<PreviewResult> {
+        self.into_operation().run_blocking()
+    }
+
+    fn into_operation(self) -> Operation<PreviewResult> {
         let request = StackRequest {
-            project: self.stack.project.clone(),
-            stack: self.stack.name.clone(),
-            backend: self.stack.backend.clone(),
-            config: self.stack.config.clone(),
             resources: self.resources,
+            timeout_seconds: self.timeout_seconds.or(self.stack.pulumi_config.runtime.timeout_seconds),
+            outputs: self.outputs,
+            components: self.components,
+            invokes: self.invokes,
+            ..self.stack.request()
         };
-        
-        // If event handler is provided, set up event channel
-        if let Some(handler) = self.event_handler {
-            let event_receiver = crate::events::create_event_channel();
-            
-            // Spawn a thread to handle events
-            thread::spawn(move || {
-                while let Ok(event_json) = event_receiver.recv() {
-                    if let Ok(event) = serde_json::from_value::<DeploymentEvent>(event_json) {
-                        handler.handle_event(event);
-                    }
-                }
-            });
-        }
-        
-        let result = self.stack.dynamic.preview(request)
-            .map_err(|e| PulumistError::StackOperation(e));
-            
-        // Cleanup event channel
-        crate::events::cleanup_event_channel();
-        
-        result
+
+        let dynamic = self.stack.dynamic.clone();
+        Operation::new(request.operation_id, move || dynamic.preview(request))
+            .with_event_handler(self.event_handler)
+            .with_cancellation_token(self.cancellation_token)
     }
 }
 
 pub struct RefreshBuilder<'a> {
     stack: &'a Stack,
     event_handler: Option<Arc<dyn EventHandler>>,
+    cancellation_token: Option<CancellationToken>,
+    timeout_seconds: Option<u32>,
+    preview_only: bool,
 }
 
 impl<'a> RefreshBuilder<'a> {
@@ -210,6 +421,9 @@ impl<'a> RefreshBuilder<'a> {
         Self {
             stack,
             event_handler: None,
+            cancellation_token: None,
+            timeout_seconds: None,
+            preview_only: false,
         }
     }
     
@@ -218,36 +432,105 @@ impl<'a> RefreshBuilder<'a> {
         self
     }
     
-    pub async fn execute(self) -> Result<Value> {
+    /// Lets `token` cancel this operation, see [`CancellationToken`].
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+    
+    /// Fails the operation with [`PulumistError::Timeout`] if it runs longer than `seconds`.
+    /// Overrides the stack's [`RuntimeOptions::timeout_seconds`](crate::config::RuntimeOptions::timeout_seconds).
+    pub fn with_timeout_seconds(mut self, seconds: u32) -> Self {
+        self.timeout_seconds = Some(seconds);
+        self
+    }
+    
+    /// Only computes the changes a refresh would make, like `pulumi refresh --preview-only`.
+    /// The stack state is left untouched, so this detects drift without accepting it.
+    pub fn with_preview_only(mut self, preview_only: bool) -> Self {
+        self.preview_only = preview_only;
+        self
+    }
+    
+    /// Runs the refresh without blocking the async runtime.
+    pub async fn execute(self) -> Result<RefreshResult> {
+        self.into_operation().run().await
+    }
+
+    /// Runs the refresh on the current thread, for callers without an async runtime.
+    pub fn execute_blocking(self) -> Result<RefreshResult> {
+        self.into_operation().run_blocking()
+    }
+
+    fn into_operation(self) -> Operation<RefreshResult> {
         let request = StackRequest {
-            project: self.stack.project.clone(),
-            stack: self.stack.name.clone(),
-            backend: self.stack.backend.clone(),
-            config: self.stack.config.clone(),
-            resources: vec![],
+            timeout_seconds: self.timeout_seconds.or(self.stack.pulumi_config.runtime.timeout_seconds),
+            preview_only: self.preview_only,
+            ..self.stack.request()
         };
-        
-        // If event handler is provided, set up event channel
-        if let Some(handler) = self.event_handler {
-            let event_receiver = crate::events::create_event_channel();
-            
-            // Spawn a thread to handle events
-            thread::spawn(move || {
-                while let Ok(event_json) = event_receiver.recv() {
-                    if let Ok(event) = serde_json::from_value::<DeploymentEvent>(event_json) {
-                        handler.handle_event(event);
-                    }
-                }
-            });
+
+        let dynamic = self.stack.dynamic.clone();
+        Operation::new(request.operation_id, move || dynamic.refresh(request))
+            .with_event_handler(self.event_handler)
+            .with_cancellation_token(self.cancellation_token)
+    }
+}
+
+pub struct DestroyBuilder<'a> {
+    stack: &'a Stack,
+    event_handler: Option<Arc<dyn EventHandler>>,
+    cancellation_token: Option<CancellationToken>,
+    timeout_seconds: Option<u32>,
+}
+
+impl<'a> DestroyBuilder<'a> {
+    fn new(stack: &'a Stack) -> Self {
+        Self {
+            stack,
+            event_handler: None,
+            cancellation_token: None,
+            timeout_seconds: None,
         }
-        
-        let result = self.stack.dynamic.refresh(request)
-            .map_err(|e| PulumistError::StackOperation(e));
-            
-        // Cleanup event channel
-        crate::events::cleanup_event_channel();
-        
-        result
+    }
+    
+    pub fn with_event_handler(mut self, handler: Arc<dyn EventHandler>) -> Self {
+        self.event_handler = Some(handler);
+        self
+    }
+    
+    /// Lets `token` cancel this operation, see [`CancellationToken`].
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+    
+    /// Fails the operation with [`PulumistError::Timeout`] if it runs longer than `seconds`.
+    /// Overrides the stack's [`RuntimeOptions::timeout_seconds`](crate::config::RuntimeOptions::timeout_seconds).
+    pub fn with_timeout_seconds(mut self, seconds: u32) -> Self {
+        self.timeout_seconds = Some(seconds);
+        self
+    }
+    
+    /// Runs the destroy without blocking the async runtime.
+    pub async fn execute(self) -> Result<DestroyResult> {
+        self.into_operation().run().await
+    }
+
+    /// Runs the destroy on the current thread, for callers without an async runtime.
+    pub fn execute_blocking(self) -> Result<DestroyResult> {
+        self.into_operation().run_blocking()
+    }
+
+    fn into_operation(self) -> Operation<DestroyResult> {
+        let request = StackRequest {
+            timeout_seconds: self.timeout_seconds.or(self.stack.pulumi_config.runtime.timeout_seconds),
+            ..self.stack.request()
+        };
+
+        let dynamic = self.stack.dynamic.clone();
+        Operation::new(request.operation_id, move || dynamic.destroy(request))
+            .with_event_handler(self.event_handler)
+            .with_cancellation_token(self.cancellation_token)
     }
 }
 
@@ -256,8 +539,10 @@ pub struct ImportBuilder<'a> {
     resource_type: Option<String>,
     resource_name: Option<String>,
     resource_id: Option<String>,
-    resources: Vec<DynamicResource>,
+    resources: Vec<ImportResource>,
     event_handler: Option<Arc<dyn EventHandler>>,
+    cancellation_token: Option<CancellationToken>,
+    timeout_seconds: Option<u32>,
 }
 
 impl<'a> ImportBuilder<'a> {
@@ -269,9 +554,12 @@ impl<'a> ImportBuilder<'a> {
             resource_id: None,
             resources: vec![],
             event_handler: None,
+            cancellation_token: None,
+            timeout_seconds: None,
         }
     }
     
+    /// Type of a single resource to import, set together with its name and ID.
     pub fn with_resource_type(mut self, resource_type: String) -> Self {
         self.resource_type = Some(resource_type);
         self
@@ -287,8 +575,15 @@ impl<'a> ImportBuilder<'a> {
         self
     }
     
-    pub fn with_resources(mut self, resources: Vec<DynamicResource>) -> Self {
-        self.resources = resources;
+    /// Adds a resource to import, e.g. with a parent or an explicit provider.
+    pub fn with_resource(mut self, resource: ImportResource) -> Self {
+        self.resources.push(resource);
+        self
+    }
+    
+    /// Adds resources to import in bulk, like the entries of a Pulumi import file.
+    pub fn with_resources(mut self, resources: Vec<ImportResource>) -> Self {
+        self.resources.extend(resources);
         self
     }
     
@@ -297,39 +592,120 @@ impl<'a> ImportBuilder<'a> {
         self
     }
     
-    pub async fn execute(self) -> Result<Value> {
+    /// Lets `token` cancel this operation, see [`CancellationToken`].
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+    
+    /// Fails the operation with [`PulumistError::Timeout`] if it runs longer than `seconds`.
+    /// Overrides the stack's [`RuntimeOptions::timeout_seconds`](crate::config::RuntimeOptions::timeout_seconds).
+    pub fn with_timeout_seconds(mut self, seconds: u32) -> Self {
+        self.timeout_seconds = Some(seconds);
+        self
+    }
+    
+    /// Runs the import without blocking the async runtime.
+    pub async fn execute(self) -> Result<ImportResult> {
+        self.into_operation()?.run().await
+    }
+
+    /// Runs the import on the current thread, for callers without an async runtime.
+    pub fn execute_blocking(self) -> Result<ImportResult> {
+        self.into_operation()?.run_blocking()
+    }
+
+    fn into_operation(self) -> Result<Operation<ImportResult>> {
+        let mut resources = self.resources;
+        match (self.resource_type, self.resource_name, self.resource_id) {
+            (None, None, None) => {}
+            (Some(resource_type), Some(name), Some(id)) => {
+                resources.insert(0, ImportResource::new(&resource_type, &name, &id));
+            }
+            _ => {
+                return Err(PulumistError::ConfigError(
+                    "resource_type, resource_name and resource_id are all required to import a resource".to_string(),
+                ));
+            }
+        }
+        if resources.is_empty() {
+            return Err(PulumistError::ConfigError("No resources to import".to_string()));
+        }
+
         let request = ImportRequest {
+            operation_id: crate::events::next_operation_id(),
             project: self.stack.project.clone(),
             stack: self.stack.name.clone(),
-            backend: self.stack.backend.clone(),
-            resource_type: self.resource_type.ok_or_else(|| PulumistError::ConfigError("resource_type is required for import".to_string()))?,
-            resource_name: self.resource_name.ok_or_else(|| PulumistError::ConfigError("resource_name is required for import".to_string()))?,
-            resource_id: self.resource_id.ok_or_else(|| PulumistError::ConfigError("resource_id is required for import".to_string()))?,
-            resources: self.resources,
+            resources,
             config: self.stack.config.clone(),
-            outputs: serde_json::Map::new(),
+            secret_config: self.stack.secret_config.clone(),
+            timeout_seconds: self.timeout_seconds.or(self.stack.pulumi_config.runtime.timeout_seconds),
+            pulumi_config: self.stack.pulumi_config.clone(),
         };
-        
-        // If event handler is provided, set up event channel
-        if let Some(handler) = self.event_handler {
-            let event_receiver = crate::events::create_event_channel();
-            
-            // Spawn a thread to handle events
-            thread::spawn(move || {
-                while let Ok(event_json) = event_receiver.recv() {
-                    if let Ok(event) = serde_json::from_value::<DeploymentEvent>(event_json) {
-                        handler.handle_event(event);
-                    }
-                }
-            });
+
+        let dynamic = self.stack.dynamic.clone();
+        Ok(Operation::new(request.operation_id, move || dynamic.import(request))
+            .with_event_handler(self.event_handler)
+            .with_cancellation_token(self.cancellation_token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::DeploymentEvent;
+    use std::sync::{mpsc, Mutex};
+    use std::time::Duration;
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_operation_yields_to_runtime() {
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let operation = Operation::new(crate::events::next_operation_id(), move || {
+            // Blocks until a task on the same single-threaded runtime releases it
+            release_rx.recv_timeout(Duration::from_secs(5))
+                .map_err(|e| PulumistError::StackOperation(e.to_string()))?;
+            Ok(Value::Bool(true))
+        });
+
+        let running = tokio::spawn(operation.run());
+        tokio::spawn(async move { release_tx.send(()).unwrap() }).await.unwrap();
+
+        assert_eq!(running.await.unwrap().unwrap(), Value::Bool(true));
+    }
+
+    #[test]
+    fn test_cancelled_token_skips_operation() {
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let operation: Operation<Value> = Operation::new(crate::events::next_operation_id(), || panic!("operation should not run"))
+            .with_cancellation_token(Some(token));
+        assert!(matches!(operation.run_blocking(), Err(PulumistError::Cancelled)));
+    }
+
+    #[test]
+    fn test_operation_returns_after_handler_saw_every_event() {
+        struct SlowHandler(Mutex<Vec<DeploymentEvent>>);
+        impl EventHandler for SlowHandler {
+            fn handle_event(&self, event: DeploymentEvent) {
+                thread::sleep(Duration::from_millis(10));
+                self.0.lock().unwrap().push(event);
+            }
         }
-        
-        let result = self.stack.dynamic.import(request)
-            .map_err(|e| PulumistError::StackOperation(e));
-            
-        // Cleanup event channel
-        crate::events::cleanup_event_channel();
-        
-        result
+
+        let handler = Arc::new(SlowHandler(Mutex::new(vec![])));
+        let operation_id = crate::events::next_operation_id();
+        let operation = Operation::new(operation_id, move || {
+            // Emits the events like the Go side does, through the registered callback
+            for i in 0..5 {
+                let frame = crate::events::encode_frame(operation_id, &DeploymentEvent::Progress { message: i.to_string() });
+                unsafe { crate::events::event_callback(frame.as_ptr().cast()) };
+            }
+            Ok(Value::Null)
+        })
+        .with_event_handler(Some(handler.clone()));
+
+        operation.run_blocking().unwrap();
+        assert_eq!(handler.0.lock().unwrap().len(), 5);
     }
-}
\ No newline at end of file
+}
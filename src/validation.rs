@@ -0,0 +1,519 @@
+use crate::dynamic::{flatten_components, ComponentResource, DynamicResource, Invoke, PROVIDER_TYPE_PREFIX};
+use crate::error::{PulumistError, Result};
+use crate::outputs::{find_output_references, format_property_path, OutputReference, PathSegment, STACK_REFERENCE_PREFIX};
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt;
+
+/// A problem with a resource found by [`validate_resources`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    /// Name of the resource or invoke the problem was found in
+    pub resource: String,
+    /// Where in the resource the problem is, e.g. `properties.sku.name` or `options.parent`,
+    /// empty for the resource as a whole
+    pub path: String,
+    pub message: String,
+}
+
+impl Issue {
+    fn new(resource: &str, path: impl Into<String>, message: impl Into<String>) -> Self {
+        Issue {
+            resource: resource.to_string(),
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}: {}", self.resource, self.message)
+        } else {
+            write!(f, "{} at {}: {}", self.resource, self.path, self.message)
+        }
+    }
+}
+
+/// Checks a set of resources before it is sent to Pulumi and reports every problem at once as
+/// [`PulumistError::Validation`]:
+///
+/// - empty resource types or names, and duplicate names
+/// - references to resources that are not in the set, and references without or with an invalid property path
+/// - references to other stacks without a stack name or output name, see [`OutputReference::parse`]
+/// - `parent`, `provider`, `depends_on`, `deleted_with` and alias parent names that are not in the set, and providers that are not provider resources
+/// - read resources with an empty ID or lifecycle options such as `protect`, and provider resources to read
+/// - aliases that set more than one previous parent or do not name a previous identity
+/// - dependency cycles
+///
+/// `${name}` without a property path is only reported if `name` is a resource in the set, so other
+/// `${...}` placeholders, e.g. variables in scripts, are left alone.
+pub fn validate_resources(resources: &[DynamicResource]) -> Result<()> {
+    validate_deployment(resources, &[], &[])
+}
+
+/// Like [`validate_resources`] for resources deployed along with components and invokes. Component
+/// children are checked like other resources, and the references in component outputs like resource
+/// properties. Component outputs are registered after all resources, so only stack outputs can reference them.
+///
+/// Resources may reference the results of invokes. Invokes are checked for empty or duplicate names,
+/// empty tokens, unknown providers and arguments that reference resources or other invokes.
+pub fn validate_deployment(resources: &[DynamicResource], components: &[ComponentResource], invokes: &[Invoke]) -> Result<()> {
+    let (resources, _) = flatten_components(resources.to_vec(), components.to_vec());
+    let known = KnownNames {
+        resources: resources.iter().map(|resource| resource.name.as_str()).collect(),
+        components: components.iter().map(|component| component.name.as_str()).collect(),
+        invokes: invokes.iter().map(|invoke| invoke.name.as_str()).collect(),
+    };
+    let names = &known.resources;
+    let mut issues = Vec::new();
+    let mut seen = HashSet::new();
+
+    for resource in &resources {
+        if resource.resource_type.is_empty() {
+            issues.push(Issue::new(&resource.name, "type", "resource type is empty"));
+        }
+        if resource.name.is_empty() {
+            issues.push(Issue::new(&resource.name, "name", "resource name is empty"));
+        } else if !seen.insert(resource.name.as_str()) {
+            issues.push(Issue::new(&resource.name, "name", "another resource has the same name"));
+        }
+
+        let mut path = vec![];
+        check_references(&resource.name, "properties", &resource.properties, &mut path, &known, &mut issues);
+
+        if let Some(options) = &resource.options {
+            for (i, dependency) in options.depends_on.iter().flatten().enumerate() {
+                if !names.contains(dependency.as_str()) {
+                    issues.push(Issue::new(
+                        &resource.name,
+                        format!("options.dependsOn[{}]", i),
+                        format!("depends on unknown resource `{}`", dependency),
+                    ));
+                }
+            }
+            if let Some(parent) = options.parent.as_deref().filter(|parent| !names.contains(parent)) {
+                issues.push(Issue::new(&resource.name, "options.parent", format!("unknown parent resource `{}`", parent)));
+            }
+            match options.id.as_deref() {
+                Some("") => issues.push(Issue::new(&resource.name, "options.id", "ID of the resource to read is empty")),
+                Some(_) if resource.is_provider() => {
+                    issues.push(Issue::new(&resource.name, "options.id", "provider resources cannot be read by ID"));
+                }
+                _ => {}
+            }
+            if resource.is_read() {
+                // Read resources are never created, updated or deleted, so lifecycle options mean nothing for them
+                let lifecycle = [
+                    ("protect", options.protect.is_some()),
+                    ("ignoreChanges", options.ignore_changes.is_some()),
+                    ("retainOnDelete", options.retain_on_delete.is_some()),
+                    ("replaceOnChanges", options.replace_on_changes.is_some()),
+                    ("deleteBeforeReplace", options.delete_before_replace.is_some()),
+                    ("customTimeouts", options.custom_timeouts.is_some()),
+                    ("deletedWith", options.deleted_with.is_some()),
+                    ("aliases", options.aliases.is_some()),
+                ];
+                for (option, _) in lifecycle.iter().filter(|(_, set)| *set) {
+                    issues.push(Issue::new(
+                        &resource.name,
+                        format!("options.{}", option),
+                        "lifecycle options have no effect on resources read by ID",
+                    ));
+                }
+            }
+            if let Some(deleted_with) = options.deleted_with.as_deref().filter(|name| !names.contains(name)) {
+                issues.push(Issue::new(&resource.name, "options.deletedWith", format!("unknown resource `{}`", deleted_with)));
+            }
+            for (i, alias) in options.aliases.iter().flatten().enumerate() {
+                let path = format!("options.aliases[{}]", i);
+                if let Some(parent) = alias.parent.as_deref().filter(|parent| !names.contains(parent)) {
+                    issues.push(Issue::new(&resource.name, format!("{}.parent", path), format!(
+                        "unknown parent resource `{}`, use parent_urn for parents that are no longer deployed", parent,
+                    )));
+                }
+                let parents = [alias.parent.is_some(), alias.parent_urn.is_some(), alias.no_parent];
+                if parents.iter().filter(|&&set| set).count() > 1 {
+                    issues.push(Issue::new(&resource.name, path.clone(), "only one of parent, parent_urn and no_parent can be set"));
+                }
+                let changes = [&alias.name, &alias.resource_type, &alias.parent, &alias.parent_urn, &alias.stack, &alias.project, &alias.urn];
+                if !alias.no_parent && changes.iter().all(|field| field.is_none()) {
+                    issues.push(Issue::new(&resource.name, path, "alias does not name a previous identity"));
+                }
+            }
+            if let Some(provider) = options.provider.as_deref() {
+                check_provider(&resource.name, "options.provider", provider, &resources, &mut issues);
+            }
+        }
+    }
+
+    for invoke in invokes {
+        if invoke.name.is_empty() {
+            issues.push(Issue::new(&invoke.name, "name", "invoke name is empty"));
+        } else if !seen.insert(invoke.name.as_str()) {
+            issues.push(Issue::new(&invoke.name, "name", "another resource or invoke has the same name"));
+        }
+        if invoke.token.is_empty() {
+            issues.push(Issue::new(&invoke.name, "token", "function token is empty"));
+        }
+        for (key, value) in &invoke.args {
+            for reference in find_output_references(value) {
+                if reference.stack().is_some() || known.is_referenceable(&reference.resource_name) {
+                    issues.push(Issue::new(&invoke.name, format!("args.{}", key), format!(
+                        "arguments must be literal values, they cannot reference `{}`",
+                        reference.resource_name,
+                    )));
+                }
+            }
+        }
+        if let Some(provider) = invoke.provider.as_deref() {
+            check_provider(&invoke.name, "provider", provider, &resources, &mut issues);
+        }
+    }
+
+    for component in components {
+        for (key, value) in &component.outputs {
+            let mut path = vec![PathSegment::Key(key.clone())];
+            check_references(&component.name, "outputs", value, &mut path, &known, &mut issues);
+        }
+    }
+
+    // Cycles are only meaningful between uniquely named resources
+    if issues.is_empty() {
+        if let Err(PulumistError::DependencyCycle(cycle)) = crate::graph::order_resources(resources.clone(), invokes) {
+            issues.push(Issue::new(&cycle[0], "", format!("dependency cycle: {}", cycle.join(" -> "))));
+        }
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(PulumistError::Validation(issues))
+    }
+}
+
+/// Names of the resources, including components, of the components and of the invokes of a deployment
+struct KnownNames<'a> {
+    resources: HashSet<&'a str>,
+    components: HashSet<&'a str>,
+    invokes: HashSet<&'a str>,
+}
+
+impl KnownNames<'_> {
+    /// Whether `${name...}` references the outputs of a resource or the result of an invoke
+    fn is_referenceable(&self, name: &str) -> bool {
+        self.resources.contains(name) || self.invokes.contains(name)
+    }
+}
+
+/// Reports `provider` if it does not name a provider resource in `resources`
+fn check_provider(owner: &str, path: &str, provider: &str, resources: &[DynamicResource], issues: &mut Vec<Issue>) {
+    match resources.iter().find(|candidate| candidate.name == provider) {
+        None => issues.push(Issue::new(owner, path, format!("unknown provider resource `{}`", provider))),
+        Some(candidate) if !candidate.is_provider() => issues.push(Issue::new(
+            owner,
+            path,
+            format!("`{}` is not a provider resource ({}<package>)", provider, PROVIDER_TYPE_PREFIX),
+        )),
+        Some(_) => {}
+    }
+}
+
+fn check_references(
+    resource: &str,
+    prefix: &str,
+    value: &serde_json::Value,
+    path: &mut Vec<PathSegment>,
+    known: &KnownNames,
+    issues: &mut Vec<Issue>,
+) {
+    match value {
+        serde_json::Value::String(s) => {
+            let re = Regex::new(r"\$\{([^}]+)\}").unwrap();
+            for cap in re.captures_iter(s) {
+                let reference = &cap[1];
+                let location = || format!("{}.{}", prefix, format_property_path(path));
+                let report_invalid = |issues: &mut Vec<Issue>| {
+                    if let Err(error) = OutputReference::parse(reference) {
+                        let message = match error {
+                            PulumistError::Reference(message) => message,
+                            other => other.to_string(),
+                        };
+                        issues.push(Issue::new(resource, location(), message));
+                    }
+                };
+                match reference.find(['.', '[']) {
+                    // Other stacks are read when the program runs, so only the form of the reference is checked
+                    _ if reference.starts_with(STACK_REFERENCE_PREFIX) => report_invalid(issues),
+                    None if known.is_referenceable(reference) => issues.push(Issue::new(
+                        resource,
+                        location(),
+                        format!("reference `${{{}}}` has no property path", reference),
+                    )),
+                    None => {}
+                    Some(end) if !known.is_referenceable(&reference[..end]) => issues.push(Issue::new(
+                        resource,
+                        location(),
+                        format!("reference `${{{}}}` names unknown resource `{}`", reference, &reference[..end]),
+                    )),
+                    Some(end) if known.components.contains(&reference[..end]) => issues.push(Issue::new(
+                        resource,
+                        location(),
+                        format!("reference `${{{}}}` names component `{}`, whose outputs only stack outputs can reference", reference, &reference[..end]),
+                    )),
+                    Some(_) => report_invalid(issues),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                path.push(PathSegment::Index(i));
+                check_references(resource, prefix, item, path, known, issues);
+                path.pop();
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for (key, item) in map {
+                path.push(PathSegment::Key(key.clone()));
+                check_references(resource, prefix, item, path, known, issues);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamic::{Alias, ResourceOptions};
+    use serde_json::json;
+
+    fn resource(resource_type: &str, name: &str, properties: serde_json::Value) -> DynamicResource {
+        DynamicResource {
+            resource_type: resource_type.to_string(),
+            name: name.to_string(),
+            properties,
+            options: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_reports_every_issue() {
+        let mut storage = resource("azure-native:storage:StorageAccount", "storage", json!({
+            "resourceGroupName": "${rg.name}",
+            "sku": { "name": "${sku.name}" },
+            "rules": [{ "source": "${rg}" }],
+            "tags": { "cost-center": "${rg.tags[x]}" },
+            "script": "echo ${HOME}"
+        }));
+        storage.options = Some(ResourceOptions {
+            parent: Some("app".to_string()),
+            depends_on: Some(vec!["rg".to_string(), "network".to_string()]),
+            provider: Some("azure-west".to_string()),
+            ..Default::default()
+        });
+        let resources = vec![
+            resource("azure-native:resources:ResourceGroup", "rg", json!({})),
+            storage,
+            resource("", "rg", json!({})),
+        ];
+
+        let issues = match validate_resources(&resources) {
+            Err(PulumistError::Validation(issues)) => issues,
+            other => panic!("expected validation issues, got {:?}", other),
+        };
+        let locations: Vec<(&str, &str)> = issues.iter()
+            .map(|issue| (issue.resource.as_str(), issue.path.as_str()))
+            .collect();
+        assert_eq!(locations, vec![
+            ("storage", "properties.rules[0].source"),
+            ("storage", "properties.sku.name"),
+            ("storage", "properties.tags.cost-center"),
+            ("storage", "options.dependsOn[1]"),
+            ("storage", "options.parent"),
+            ("storage", "options.provider"),
+            ("rg", "type"),
+            ("rg", "name"),
+        ]);
+        assert_eq!(issues[1].to_string(), "storage at properties.sku.name: reference `${sku.name}` names unknown resource `sku`");
+    }
+
+    #[test]
+    fn test_validate_reports_cycles() {
+        let resources = vec![
+            resource("test:index:Resource", "a", json!({ "value": "${b.id}" })),
+            resource("test:index:Resource", "b", json!({ "value": "${a.id}" })),
+        ];
+
+        match validate_resources(&resources) {
+            Err(PulumistError::Validation(issues)) => {
+                assert_eq!(issues, vec![Issue::new("a", "", "dependency cycle: a -> b -> a")]);
+            }
+            other => panic!("expected validation issues, got {:?}", other),
+        }
+        assert!(validate_resources(&resources[..1]).is_err());
+        assert!(validate_resources(&[]).is_ok());
+    }
+
+    #[test]
+    fn test_validate_provider_references() {
+        let mut storage = resource("aws:s3:Bucket", "bucket", json!({}));
+        storage.options = Some(ResourceOptions { provider: Some("aws-west".to_string()), ..Default::default() });
+        let mut resources = vec![
+            DynamicResource::provider("aws", "aws-west", json!({ "region": "us-west-2" })),
+            storage,
+        ];
+        assert!(validate_resources(&resources).is_ok());
+
+        resources[0] = resource("aws:s3:Bucket", "aws-west", json!({}));
+        match validate_resources(&resources) {
+            Err(PulumistError::Validation(issues)) => assert_eq!(issues, vec![Issue::new(
+                "bucket",
+                "options.provider",
+                "`aws-west` is not a provider resource (pulumi:providers:<package>)",
+            )]),
+            other => panic!("expected validation issues, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_aliases() {
+        let mut app = resource("azure-native:web:WebApp", "app", json!({}));
+        app.options = Some(ResourceOptions {
+            aliases: Some(vec![
+                Alias { name: Some("legacy-app".to_string()), parent: Some("plan".to_string()), ..Default::default() },
+                Alias { parent: Some("old-group".to_string()), no_parent: true, ..Default::default() },
+                Alias::default(),
+            ]),
+            ..Default::default()
+        });
+        let resources = vec![resource("azure-native:web:AppServicePlan", "plan", json!({})), app];
+
+        match validate_resources(&resources) {
+            Err(PulumistError::Validation(issues)) => {
+                let paths: Vec<&str> = issues.iter().map(|issue| issue.path.as_str()).collect();
+                assert_eq!(paths, vec!["options.aliases[1].parent", "options.aliases[1]", "options.aliases[2]"]);
+            }
+            other => panic!("expected validation issues, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_components() {
+        let site = ComponentResource::new("acme:web:StaticSite", "site")
+            .with_child(resource("azure-native:storage:StorageAccount", "site-storage", json!({})))
+            .with_output("endpoint", "${site-storage.primaryEndpoints.web}")
+            .with_output("cdn", "${cdn.hostName}");
+        let resources = vec![resource("azure-native:cdn:Endpoint", "dns", json!({ "target": "${site.endpoint}" }))];
+
+        match validate_deployment(&resources, std::slice::from_ref(&site), &[]) {
+            Err(PulumistError::Validation(issues)) => {
+                let locations: Vec<(&str, &str)> = issues.iter()
+                    .map(|issue| (issue.resource.as_str(), issue.path.as_str()))
+                    .collect();
+                assert_eq!(locations, vec![("dns", "properties.target"), ("site", "outputs.cdn")]);
+            }
+            other => panic!("expected validation issues, got {:?}", other),
+        }
+
+        let site = ComponentResource { outputs: serde_json::Map::new(), ..site }
+            .with_output("endpoint", "${site-storage.primaryEndpoints.web}");
+        assert!(validate_deployment(&[], &[site], &[]).is_ok());
+    }
+
+    #[test]
+    fn test_validate_read_resources() {
+        let vnet = DynamicResource::get(
+            "azure-native:network:VirtualNetwork",
+            "shared-vnet",
+            "/subscriptions/0/resourceGroups/network/providers/Microsoft.Network/virtualNetworks/shared",
+        );
+        assert!(vnet.is_read());
+        let subnet = resource("azure-native:network:Subnet", "app-subnet", json!({
+            "virtualNetworkName": "${shared-vnet.name}"
+        }));
+        assert!(validate_resources(&[vnet, subnet.clone()]).is_ok());
+
+        let mut provider = DynamicResource::provider("azure-native", "azure", json!({}));
+        provider.options = Some(ResourceOptions { id: Some("azure".to_string()), ..Default::default() });
+        let unnamed = DynamicResource::get("azure-native:network:VirtualNetwork", "shared-vnet", "");
+        let mut protected = DynamicResource::get("azure-native:network:VirtualNetwork", "hub-vnet", "/subscriptions/0/hub");
+        if let Some(options) = protected.options.as_mut() {
+            options.protect = Some(true);
+            options.ignore_changes = Some(vec!["tags".to_string()]);
+            options.depends_on = Some(vec!["shared-vnet".to_string()]);
+        }
+        match validate_resources(&[provider, unnamed, protected, subnet]) {
+            Err(PulumistError::Validation(issues)) => {
+                let paths: Vec<(&str, &str)> = issues.iter().map(|issue| (issue.resource.as_str(), issue.path.as_str())).collect();
+                assert_eq!(paths, vec![
+                    ("azure", "options.id"),
+                    ("shared-vnet", "options.id"),
+                    ("hub-vnet", "options.protect"),
+                    ("hub-vnet", "options.ignoreChanges"),
+                ]);
+            }
+            other => panic!("expected validation issues, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_stack_references() {
+        let subnet = resource("azure-native:network:Subnet", "app-subnet", json!({
+            "virtualNetworkName": "${stack:acme/network/prod.vnetName}",
+            "addressPrefix": "${stack:acme/network/prod.prefixes[\"app\"]}",
+            "routeTableId": "${stack:\"acme/network/prod.eu\".routeTableId}",
+        }));
+        assert!(validate_resources(std::slice::from_ref(&subnet)).is_ok());
+
+        let vault = resource("azure-native:keyvault:Vault", "vault", json!({
+            "tenantId": "${stack:acme/platform/prod}",
+            "subnetId": "${stack:acme/network/prod[0]}",
+        }));
+        let lookup = Invoke::new("vnet", "azure-native:network:getVirtualNetwork")
+            .with_arg("virtualNetworkName", "${stack:acme/network/prod.vnetName}");
+        match validate_deployment(&[subnet, vault], &[], &[lookup]) {
+            Err(PulumistError::Validation(issues)) => {
+                let locations: Vec<(&str, &str)> = issues.iter()
+                    .map(|issue| (issue.resource.as_str(), issue.path.as_str()))
+                    .collect();
+                assert_eq!(locations, vec![
+                    ("vault", "properties.subnetId"),
+                    ("vault", "properties.tenantId"),
+                    ("vnet", "args.virtualNetworkName"),
+                ]);
+            }
+            other => panic!("expected validation issues, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_invokes() {
+        let vault = resource("azure-native:keyvault:Vault", "vault", json!({
+            "tenantId": "${client.tenantId}",
+            "owner": "${client}",
+        }));
+        let client = Invoke::new("client", "azure-native:authorization:getClientConfig");
+        assert!(validate_deployment(std::slice::from_ref(&vault), &[], std::slice::from_ref(&client)).is_err());
+
+        let vault = resource("azure-native:keyvault:Vault", "vault", json!({ "tenantId": "${client.tenantId}" }));
+        assert!(validate_deployment(std::slice::from_ref(&vault), &[], std::slice::from_ref(&client)).is_ok());
+
+        let invokes = vec![
+            client,
+            Invoke::new("vault", "").with_arg("name", "${vault.name}"),
+            Invoke::new("rg", "azure-native:resources:getResourceGroup").with_provider("azure-west"),
+        ];
+        match validate_deployment(&[vault], &[], &invokes) {
+            Err(PulumistError::Validation(issues)) => {
+                let locations: Vec<(&str, &str)> = issues.iter()
+                    .map(|issue| (issue.resource.as_str(), issue.path.as_str()))
+                    .collect();
+                assert_eq!(locations, vec![("vault", "name"), ("vault", "token"), ("vault", "args.name"), ("rg", "provider")]);
+            }
+            other => panic!("expected validation issues, got {:?}", other),
+        }
+    }
+}